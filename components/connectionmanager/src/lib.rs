@@ -12,6 +12,7 @@ use itertools::Itertools;
 use kaspa_addressmanager::{AddressManager, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::Peer;
+use kaspa_utils::networking::Whitelist;
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
 use tokio::{
@@ -27,6 +28,7 @@ pub struct ConnectionManager {
     p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
     outbound_target: usize,
     inbound_limit: usize,
+    whitelist: Whitelist,
     dns_seeders: &'static [&'static str],
     default_port: u16,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
@@ -53,6 +55,7 @@ impl ConnectionManager {
         p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
         outbound_target: usize,
         inbound_limit: usize,
+        whitelist: Whitelist,
         dns_seeders: &'static [&'static str],
         default_port: u16,
         address_manager: Arc<ParkingLotMutex<AddressManager>>,
@@ -63,6 +66,7 @@ impl ConnectionManager {
             p2p_adaptor,
             outbound_target,
             inbound_limit,
+            whitelist,
             address_manager,
             connection_requests: Default::default(),
             force_next_iteration: tx,
@@ -219,7 +223,9 @@ impl ConnectionManager {
     }
 
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
-        let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
+        // Whitelisted peers do not count towards the inbound limit
+        let active_inbound =
+            peer_by_address.values().filter(|peer| !peer.is_outbound() && !self.is_whitelisted(peer.net_address().ip())).collect_vec();
         let active_inbound_len = active_inbound.len();
         if self.inbound_limit >= active_inbound_len {
             return;
@@ -265,7 +271,7 @@ impl ConnectionManager {
     ///
     /// _GO-KASPAD: BanByIP_
     pub async fn ban(&self, ip: IpAddr) {
-        if self.ip_has_permanent_connection(ip).await {
+        if self.is_whitelisted(ip) || self.ip_has_permanent_connection(ip).await {
             return;
         }
         for peer in self.p2p_adaptor.active_peers() {
@@ -278,7 +284,14 @@ impl ConnectionManager {
 
     /// Returns whether the given address is banned.
     pub async fn is_banned(&self, address: &SocketAddr) -> bool {
-        !self.is_permanent(address).await && self.address_manager.lock().is_banned(address.ip().into())
+        !self.is_whitelisted(address.ip())
+            && !self.is_permanent(address).await
+            && self.address_manager.lock().is_banned(address.ip().into())
+    }
+
+    /// Returns whether the given IP belongs to a whitelisted network.
+    pub fn is_whitelisted(&self, ip: IpAddr) -> bool {
+        self.whitelist.contains(ip)
     }
 
     /// Returns whether the given address is a permanent request.
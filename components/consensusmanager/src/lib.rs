@@ -174,11 +174,14 @@ impl StagingConsensus {
     }
 
     pub fn cancel(self) {
-        self.staging.ctl.stop();
+        let ConsensusInner { consensus, ctl } = self.staging;
+        ctl.stop();
         for handle in self.handles {
             handle.join().unwrap();
         }
-        self.staging.ctl.delete();
+        // Release our reference to the staging consensus so that its resources can be deleted
+        drop(consensus);
+        ctl.delete();
     }
 }
 
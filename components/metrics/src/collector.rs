@@ -0,0 +1,158 @@
+use crate::encoder::{MetricType, MetricsEncoder};
+use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_database::prelude::DB;
+use kaspa_notify::{events::EVENT_TYPE_ARRAY, notifier::DynNotifierStatsProvider};
+use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_p2p_lib::{TrafficCounter, TrafficSnapshot};
+use std::sync::Arc;
+
+/// RocksDB integer properties exported for each database, along with their metric names and descriptions
+const DB_PROPERTIES: [(&str, &str, &str); 4] = [
+    ("rocksdb.estimate-num-keys", "kaspa_db_estimated_keys", "Estimated number of keys in the database"),
+    ("rocksdb.estimate-live-data-size", "kaspa_db_live_data_size_bytes", "Estimated size of the live data of the database"),
+    ("rocksdb.total-sst-files-size", "kaspa_db_sst_files_size_bytes", "Total size of the SST files of the database"),
+    ("rocksdb.cur-size-all-mem-tables", "kaspa_db_memtables_size_bytes", "Size of the active and unflushed memtables of the database"),
+];
+
+/// Name of the consensus database in the metric labels
+const CONSENSUS_DB: &str = "consensus";
+
+/// Gathers the current values of all node metrics
+pub struct MetricsCollector {
+    counters: Arc<ProcessingCounters>,
+    consensus_manager: Arc<ConsensusManager>,
+    consensus_factory: Arc<ConsensusFactory>,
+    flow_context: Arc<FlowContext>,
+    /// Non-consensus databases, by name
+    databases: Vec<(&'static str, Arc<DB>)>,
+    notifiers: Vec<DynNotifierStatsProvider>,
+}
+
+impl MetricsCollector {
+    pub fn new(
+        counters: Arc<ProcessingCounters>,
+        consensus_manager: Arc<ConsensusManager>,
+        consensus_factory: Arc<ConsensusFactory>,
+        flow_context: Arc<FlowContext>,
+        databases: Vec<(&'static str, Arc<DB>)>,
+        notifiers: Vec<DynNotifierStatsProvider>,
+    ) -> Self {
+        Self { counters, consensus_manager, consensus_factory, flow_context, databases, notifiers }
+    }
+
+    /// Collects all metrics and returns them encoded in the Prometheus text format
+    pub async fn collect(&self) -> String {
+        let mut encoder = MetricsEncoder::new();
+        self.collect_processing_counters(&mut encoder);
+        self.collect_consensus(&mut encoder).await;
+        self.collect_pools(&mut encoder).await;
+        self.collect_p2p(&mut encoder);
+        self.collect_databases(&mut encoder);
+        self.collect_notifiers(&mut encoder);
+        encoder.finish()
+    }
+
+    fn collect_processing_counters(&self, encoder: &mut MetricsEncoder) {
+        let snapshot = self.counters.snapshot();
+        encoder
+            .counter("kaspa_blocks_submitted_total", "Number of blocks submitted to consensus", snapshot.blocks_submitted)
+            .counter("kaspa_headers_processed_total", "Number of processed headers", snapshot.header_counts)
+            .counter("kaspa_dependencies_processed_total", "Number of processed header parent references", snapshot.dep_counts)
+            .counter("kaspa_bodies_processed_total", "Number of processed block bodies", snapshot.body_counts)
+            .counter("kaspa_transactions_processed_total", "Number of transactions in processed block bodies", snapshot.txs_counts)
+            .counter(
+                "kaspa_chain_blocks_processed_total",
+                "Number of blocks added to the virtual selected chain",
+                snapshot.chain_block_counts,
+            )
+            .counter("kaspa_mass_processed_total", "Total mass of processed block bodies", snapshot.mass_counts);
+    }
+
+    async fn collect_consensus(&self, encoder: &mut MetricsEncoder) {
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        encoder.gauge("kaspa_virtual_daa_score", "DAA score of the virtual block", session.get_virtual_daa_score());
+        if let Ok(ghostdag_data) = session.get_ghostdag_data(session.get_sink()) {
+            encoder.gauge("kaspa_sink_blue_score", "Blue score of the sink block", ghostdag_data.blue_score);
+        }
+    }
+
+    async fn collect_pools(&self, encoder: &mut MetricsEncoder) {
+        let mining_manager = self.flow_context.mining_manager();
+        encoder
+            .gauge(
+                "kaspa_mempool_transactions",
+                "Number of transactions in the mempool",
+                mining_manager.transaction_count(true, false),
+            )
+            .gauge(
+                "kaspa_mempool_orphan_transactions",
+                "Number of orphan transactions in the mempool",
+                mining_manager.transaction_count(false, true),
+            )
+            .gauge("kaspa_orphan_blocks", "Number of blocks in the orphan blocks pool", self.flow_context.orphan_blocks_count().await);
+    }
+
+    fn collect_p2p(&self, encoder: &mut MetricsEncoder) {
+        let hub = self.flow_context.hub();
+        let peers = hub.active_peers();
+        let outbound = peers.iter().filter(|peer| peer.is_outbound()).count();
+        encoder
+            .family("kaspa_peers", "Number of connected peers", MetricType::Gauge)
+            .sample("kaspa_peers", &[("direction", "inbound")], peers.len() - outbound)
+            .sample("kaspa_peers", &[("direction", "outbound")], outbound);
+
+        // Counters are exported by message type so that per flow rates can be derived by the monitoring system
+        let traffic = hub.traffic();
+        encoder.family("kaspa_p2p_messages_total", "Number of P2P messages by direction and message type", MetricType::Counter);
+        Self::collect_traffic(encoder, &traffic, "kaspa_p2p_messages_total", |counter| counter.messages);
+        encoder.family("kaspa_p2p_bytes_total", "Size of P2P messages by direction and message type", MetricType::Counter);
+        Self::collect_traffic(encoder, &traffic, "kaspa_p2p_bytes_total", |counter| counter.bytes);
+    }
+
+    fn collect_traffic(encoder: &mut MetricsEncoder, traffic: &TrafficSnapshot, name: &str, value: impl Fn(&TrafficCounter) -> u64) {
+        for (direction, counters) in [("inbound", &traffic.inbound), ("outbound", &traffic.outbound)] {
+            // Sort by type name for a stable output
+            let mut counters =
+                counters.iter().map(|(msg_type, counter)| (format!("{msg_type:?}"), value(counter))).collect::<Vec<_>>();
+            counters.sort();
+            for (msg_type, value) in counters {
+                encoder.sample(name, &[("direction", direction), ("type", &msg_type)], value);
+            }
+        }
+    }
+
+    fn collect_databases(&self, encoder: &mut MetricsEncoder) {
+        let consensus_db = self.consensus_factory.active_consensus_db();
+        let databases = consensus_db.iter().map(|db| (CONSENSUS_DB, db)).chain(self.databases.iter().map(|(name, db)| (*name, db)));
+        let databases = databases.collect::<Vec<_>>();
+        for (property, metric, help) in DB_PROPERTIES {
+            encoder.family(metric, help, MetricType::Gauge);
+            for (name, db) in databases.iter() {
+                if let Ok(Some(value)) = db.property_int_value(property) {
+                    encoder.sample(metric, &[("db", name)], value);
+                }
+            }
+        }
+    }
+
+    fn collect_notifiers(&self, encoder: &mut MetricsEncoder) {
+        let stats = self.notifiers.iter().map(|notifier| notifier.stats()).collect::<Vec<_>>();
+        encoder.family("kaspa_notifier_listeners", "Number of listeners registered to a notifier", MetricType::Gauge);
+        for stats in stats.iter() {
+            encoder.sample("kaspa_notifier_listeners", &[("notifier", stats.name)], stats.listeners);
+        }
+        encoder.family("kaspa_notifier_subscriptions", "Number of active notifier subscriptions by event type", MetricType::Gauge);
+        for stats in stats.iter() {
+            for event in EVENT_TYPE_ARRAY {
+                let event_name = format!("{event:?}");
+                encoder.sample(
+                    "kaspa_notifier_subscriptions",
+                    &[("notifier", stats.name), ("event", &event_name)],
+                    stats.subscriptions[event],
+                );
+            }
+        }
+    }
+}
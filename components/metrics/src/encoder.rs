@@ -0,0 +1,108 @@
+use std::fmt::{Display, Write};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+}
+
+impl MetricType {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+        }
+    }
+}
+
+/// Writes metrics in the Prometheus text exposition format (version 0.0.4)
+#[derive(Default)]
+pub struct MetricsEncoder {
+    output: String,
+}
+
+impl MetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new metric family. All samples of the family must follow
+    pub fn family(&mut self, name: &str, help: &str, metric_type: MetricType) -> &mut Self {
+        writeln!(self.output, "# HELP {name} {}", escape(help, false)).unwrap();
+        writeln!(self.output, "# TYPE {name} {}", metric_type.as_str()).unwrap();
+        self
+    }
+
+    /// Adds a sample with the provided labels to the current metric family
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) -> &mut Self {
+        self.output.push_str(name);
+        if !labels.is_empty() {
+            self.output.push('{');
+            for (i, (label, label_value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.output.push(',');
+                }
+                write!(self.output, "{label}=\"{}\"", escape(label_value, true)).unwrap();
+            }
+            self.output.push('}');
+        }
+        writeln!(self.output, " {value}").unwrap();
+        self
+    }
+
+    /// Writes an unlabeled counter family with a single sample
+    pub fn counter(&mut self, name: &str, help: &str, value: impl Display) -> &mut Self {
+        self.family(name, help, MetricType::Counter).sample(name, &[], value)
+    }
+
+    /// Writes an unlabeled gauge family with a single sample
+    pub fn gauge(&mut self, name: &str, help: &str, value: impl Display) -> &mut Self {
+        self.family(name, help, MetricType::Gauge).sample(name, &[], value)
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+}
+
+/// Escapes backslashes and line feeds, and also double quotes in label values
+fn escape(s: &str, is_label_value: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if is_label_value => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoder() {
+        let mut encoder = MetricsEncoder::new();
+        encoder.counter("kaspa_blocks_total", "Number of blocks", 12).gauge("kaspa_score", "Current score\nof the node", 7);
+        encoder
+            .family("kaspa_peers", "Number of peers", MetricType::Gauge)
+            .sample("kaspa_peers", &[("direction", "inbound")], 3)
+            .sample("kaspa_peers", &[("direction", "out\"bound"), ("path", "a\\b")], 5);
+        let expected = "\
+# HELP kaspa_blocks_total Number of blocks
+# TYPE kaspa_blocks_total counter
+kaspa_blocks_total 12
+# HELP kaspa_score Current score\\nof the node
+# TYPE kaspa_score gauge
+kaspa_score 7
+# HELP kaspa_peers Number of peers
+# TYPE kaspa_peers gauge
+kaspa_peers{direction=\"inbound\"} 3
+kaspa_peers{direction=\"out\\\"bound\",path=\"a\\\\b\"} 5
+";
+        assert_eq!(encoder.finish(), expected);
+    }
+}
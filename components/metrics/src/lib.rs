@@ -0,0 +1,105 @@
+//!
+//! Prometheus metrics exporter -- an optional HTTP server exposing the node metrics at the `/metrics` path
+//!
+
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace,
+};
+use kaspa_utils::{networking::NetAddress, triggers::DuplexTrigger};
+use std::{convert::Infallible, sync::Arc};
+
+pub mod collector;
+pub mod encoder;
+
+pub use collector::MetricsCollector;
+
+/// The default port of the metrics server
+pub const DEFAULT_METRICS_PORT: u16 = 19110;
+
+const METRICS_SERVER: &str = "metrics-server";
+const METRICS_PATH: &str = "/metrics";
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub struct MetricsServer {
+    address: NetAddress,
+    collector: Arc<MetricsCollector>,
+    shutdown: DuplexTrigger,
+}
+
+impl MetricsServer {
+    pub fn new(address: NetAddress, collector: Arc<MetricsCollector>) -> Self {
+        Self { address, collector, shutdown: DuplexTrigger::default() }
+    }
+}
+
+async fn handle_request(collector: Arc<MetricsCollector>, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let response = if request.method() == Method::GET && request.uri().path() == METRICS_PATH {
+        Response::builder().header(CONTENT_TYPE, METRICS_CONTENT_TYPE).body(Body::from(collector.collect().await))
+    } else {
+        Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty())
+    };
+    Ok(response.unwrap())
+}
+
+impl AsyncService for MetricsServer {
+    fn ident(self: Arc<Self>) -> &'static str {
+        METRICS_SERVER
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", METRICS_SERVER);
+
+        let collector = self.collector.clone();
+        let address = self.address;
+
+        // Prepare a start shutdown signal receiver and a shutdown ended signal sender
+        let shutdown_signal = self.shutdown.request.listener.clone();
+        let shutdown_executed = self.shutdown.response.trigger.clone();
+
+        // Return a future launching the HTTP server and waiting for it to shutdown
+        Box::pin(async move {
+            let make_service = make_service_fn(move |_| {
+                let collector = collector.clone();
+                async move { Ok::<_, Infallible>(service_fn(move |request| handle_request(collector.clone(), request))) }
+            });
+
+            info!("Metrics server starting on: {}", address);
+            let result = match Server::try_bind(&address.into()) {
+                Ok(builder) => builder.serve(make_service).with_graceful_shutdown(shutdown_signal).await,
+                Err(err) => Err(err),
+            }
+            .map_err(|err| AsyncServiceError::Service(format!("Metrics server exited with error `{err}`")));
+
+            if result.is_ok() {
+                trace!("Metrics server exited gracefully");
+            }
+
+            // Send a signal telling the shutdown is done
+            shutdown_executed.trigger();
+            result
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", METRICS_SERVER);
+        self.shutdown.request.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} stopping", METRICS_SERVER);
+        let shutdown_executed_signal = self.shutdown.response.listener.clone();
+        Box::pin(async move {
+            // Wait for the HTTP server to gracefully shutdown
+            shutdown_executed_signal.await;
+            trace!("{} exiting", METRICS_SERVER);
+            Ok(())
+        })
+    }
+}
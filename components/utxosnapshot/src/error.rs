@@ -0,0 +1,47 @@
+use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
+use kaspa_hashes::Hash;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    #[error("the file is not a UTXO set snapshot")]
+    InvalidMagic,
+
+    #[error("unsupported snapshot version {0}, expected version {1}")]
+    UnsupportedVersion(u32, u32),
+
+    #[error("snapshot network mismatch - local: {0}, snapshot: {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("the node has no active consensus to export")]
+    MissingActiveConsensus,
+
+    #[error("the node has no pruning point to export yet")]
+    MissingPruningPoint,
+
+    #[error("the proof pruning point {0} is not equal to the snapshot pruning point {1}")]
+    PruningPointMismatch(Hash, Hash),
+
+    #[error("the UTXO set multiset hash {1} does not match the pruning point UTXO commitment {0}")]
+    UtxoCommitmentMismatch(Hash, Hash),
+
+    #[error("invalid snapshot trusted data: {0}")]
+    TrustedData(String),
+
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+
+    #[error(transparent)]
+    PruningImport(#[from] PruningImportError),
+
+    #[error(transparent)]
+    Rule(#[from] RuleError),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
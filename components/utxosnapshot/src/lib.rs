@@ -0,0 +1,241 @@
+//!
+//! UTXO set snapshots -- a portable file containing the pruning point UTXO set along with the headers proof and
+//! the trusted data required for bootstrapping a new node from it, without performing a network IBD.
+//!
+//! A snapshot file starts with a magic prefix followed by a sequence of bincode encoded records:
+//! 1. A header identifying the network, the pruning point and its UTXO commitment
+//! 2. The pruning point proof
+//! 3. The past pruning points headers
+//! 4. The trusted data: the pruning point anticone blocks along with the DAA and GHOSTDAG windows
+//! 5. The pruning point UTXO set in chunks, terminated by an empty chunk
+//!
+
+use kaspa_consensus_core::{
+    api::ConsensusApi,
+    block::Block,
+    errors::consensus::ConsensusResult,
+    header::Header,
+    muhash::MuHashExtensions,
+    pruning::{PruningPointProof, PruningPointsList},
+    trusted::{ExternalGhostdagData, TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+};
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_p2p_lib::convert::model::trusted::{TrustedDataEntry, TrustedDataPackage};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufReader, BufWriter, Read, Write},
+    sync::Arc,
+};
+
+mod error;
+
+pub use error::{SnapshotError, SnapshotResult};
+
+const SNAPSHOT_MAGIC: [u8; 8] = *b"KASUTXOS";
+
+/// The current snapshot file format version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const UTXO_CHUNK_SIZE: usize = 1000;
+
+type UtxoChunk = Vec<(TransactionOutpoint, UtxoEntry)>;
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    version: u32,
+    network: String,
+    pruning_point: Hash,
+    utxo_commitment: Hash,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotTrustedData {
+    daa_window: Vec<(Arc<Header>, ExternalGhostdagData)>,
+    ghostdag_window: Vec<(Hash, ExternalGhostdagData)>,
+    /// The pruning point anticone blocks, starting with the pruning point itself
+    blocks: Vec<(Arc<Header>, Arc<Vec<Transaction>>)>,
+}
+
+/// Summary of an exported or imported snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub pruning_point: Hash,
+    pub utxo_count: u64,
+}
+
+/// Writes a snapshot of the current pruning point of `consensus` to `writer`. The exported UTXO set is verified
+/// against the pruning point UTXO commitment, which is the multiset hash consensus maintains for the pruning point.
+pub fn export_snapshot(consensus: &dyn ConsensusApi, network: &str, writer: impl Write) -> SnapshotResult<SnapshotSummary> {
+    let mut writer = BufWriter::new(writer);
+    let pruning_point = consensus.pruning_point().ok_or(SnapshotError::MissingPruningPoint)?;
+    let utxo_commitment = consensus.get_header(pruning_point)?.utxo_commitment;
+    info!("Exporting a UTXO set snapshot of pruning point {}", pruning_point);
+
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    let header = SnapshotHeader { version: SNAPSHOT_VERSION, network: network.to_owned(), pruning_point, utxo_commitment };
+    bincode::serialize_into(&mut writer, &header)?;
+    bincode::serialize_into(&mut writer, consensus.get_pruning_point_proof().as_ref())?;
+    bincode::serialize_into(&mut writer, &consensus.pruning_point_headers())?;
+
+    let trusted_data = consensus.get_pruning_point_anticone_and_trusted_data()?;
+    let blocks = trusted_data
+        .anticone
+        .iter()
+        .map(|&hash| consensus.get_block(hash).map(|block| (block.header, block.transactions)))
+        .collect::<ConsensusResult<Vec<_>>>()?;
+    let trusted_data = SnapshotTrustedData {
+        daa_window: trusted_data.daa_window_blocks.iter().map(|th| (th.header.clone(), th.ghostdag.clone())).collect(),
+        ghostdag_window: trusted_data.ghostdag_blocks.iter().map(|tg| (tg.hash, tg.ghostdag.clone())).collect(),
+        blocks,
+    };
+    bincode::serialize_into(&mut writer, &trusted_data)?;
+
+    let mut multiset = MuHash::new();
+    let mut utxo_count = 0;
+    let mut from_outpoint = None;
+    loop {
+        let chunk = consensus.get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some())?;
+        for (outpoint, entry) in chunk.iter() {
+            multiset.add_utxo(outpoint, entry);
+        }
+        utxo_count += chunk.len() as u64;
+        if !chunk.is_empty() {
+            bincode::serialize_into(&mut writer, &chunk)?;
+        }
+        if chunk.len() < UTXO_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = Some(chunk.last().expect("not empty by prev condition").0);
+    }
+    bincode::serialize_into(&mut writer, &UtxoChunk::new())?;
+
+    let multiset_hash = multiset.finalize();
+    if multiset_hash != utxo_commitment {
+        return Err(SnapshotError::UtxoCommitmentMismatch(utxo_commitment, multiset_hash));
+    }
+    writer.flush()?;
+
+    info!("Exported {} UTXOs of pruning point {}", utxo_count, pruning_point);
+    Ok(SnapshotSummary { pruning_point, utxo_count })
+}
+
+/// Bootstraps `consensus` from a snapshot read from `reader`. Expected to be called on a fresh (staging)
+/// consensus, following the same steps as IBD with a headers proof. The UTXO set is not imported if
+/// `import_utxo_set` is false (e.g. for light nodes).
+pub async fn import_snapshot(
+    consensus: &dyn ConsensusApi,
+    network: &str,
+    reader: impl Read,
+    import_utxo_set: bool,
+) -> SnapshotResult<SnapshotSummary> {
+    let mut reader = BufReader::new(reader);
+    let header = read_header(&mut reader, network)?;
+    let pruning_point = header.pruning_point;
+    info!("Importing a UTXO set snapshot of pruning point {}", pruning_point);
+
+    let proof: PruningPointProof = bincode::deserialize_from(&mut reader)?;
+    consensus.validate_pruning_proof(&proof)?;
+    let proof_pruning_point = proof[0].last().expect("was just ensured by validation").hash;
+    if proof_pruning_point != pruning_point {
+        return Err(SnapshotError::PruningPointMismatch(proof_pruning_point, pruning_point));
+    }
+
+    let pruning_points: PruningPointsList = bincode::deserialize_from(&mut reader)?;
+    if pruning_points.last().map(|header| header.hash) != Some(pruning_point) {
+        return Err(SnapshotError::TrustedData("the pruning point is not equal to the last pruning point in the list".to_owned()));
+    }
+
+    let trusted_data: SnapshotTrustedData = bincode::deserialize_from(&mut reader)?;
+    if trusted_data.blocks.first().map(|(header, _)| header.hash) != Some(pruning_point) {
+        return Err(SnapshotError::TrustedData("the pruning point is not the first trusted block".to_owned()));
+    }
+    let package = TrustedDataPackage::new(
+        trusted_data.daa_window.into_iter().map(|(header, ghostdag)| TrustedHeader::new(header, ghostdag)).collect(),
+        trusted_data.ghostdag_window.into_iter().map(|(hash, ghostdag)| TrustedGhostdagData::new(hash, ghostdag)).collect(),
+    );
+    let entries = trusted_data
+        .blocks
+        .into_iter()
+        .map(|(header, transactions)| TrustedDataEntry::new(Block::from_arcs(header, transactions), vec![], vec![]))
+        .collect();
+    let trusted_set = package.build_trusted_subdag(entries).map_err(|err| SnapshotError::TrustedData(err.to_string()))?;
+
+    consensus.apply_pruning_proof(proof, &trusted_set);
+    consensus.import_pruning_points(pruning_points);
+
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        consensus.validate_and_insert_trusted_block(tb).await?;
+    }
+
+    let mut utxo_count = 0;
+    if import_utxo_set {
+        let mut multiset = MuHash::new();
+        loop {
+            let chunk: UtxoChunk = bincode::deserialize_from(&mut reader)?;
+            if chunk.is_empty() {
+                break;
+            }
+            utxo_count += chunk.len() as u64;
+            consensus.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+        }
+        consensus.import_pruning_point_utxo_set(pruning_point, &mut multiset)?;
+    }
+
+    info!("Imported {} UTXOs of pruning point {}", utxo_count, pruning_point);
+    Ok(SnapshotSummary { pruning_point, utxo_count })
+}
+
+fn read_header(reader: &mut impl Read, network: &str) -> SnapshotResult<SnapshotHeader> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::InvalidMagic);
+    }
+    let header: SnapshotHeader = bincode::deserialize_from(reader)?;
+    if header.version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(header.version, SNAPSHOT_VERSION));
+    }
+    if header.network != network {
+        return Err(SnapshotError::NetworkMismatch(network.to_owned(), header.network));
+    }
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_header(magic: &[u8], header: &SnapshotHeader) -> Vec<u8> {
+        let mut bytes = magic.to_vec();
+        bincode::serialize_into(&mut bytes, header).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_read_header() {
+        let header = SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            network: "kaspa-devnet".to_owned(),
+            pruning_point: 1.into(),
+            utxo_commitment: 2.into(),
+        };
+
+        let bytes = encode_header(&SNAPSHOT_MAGIC, &header);
+        let decoded = read_header(&mut bytes.as_slice(), "kaspa-devnet").unwrap();
+        assert_eq!(decoded.pruning_point, header.pruning_point);
+        assert_eq!(decoded.utxo_commitment, header.utxo_commitment);
+
+        assert!(matches!(read_header(&mut bytes.as_slice(), "kaspa-mainnet"), Err(SnapshotError::NetworkMismatch(..))));
+        assert!(matches!(read_header(&mut &bytes[..4], "kaspa-devnet"), Err(SnapshotError::Io(_))));
+
+        let bytes = encode_header(b"NOTSNAPS", &header);
+        assert!(matches!(read_header(&mut bytes.as_slice(), "kaspa-devnet"), Err(SnapshotError::InvalidMagic)));
+
+        let bytes = encode_header(&SNAPSHOT_MAGIC, &SnapshotHeader { version: SNAPSHOT_VERSION + 1, ..header });
+        assert!(matches!(read_header(&mut bytes.as_slice(), "kaspa-devnet"), Err(SnapshotError::UnsupportedVersion(..))));
+    }
+}
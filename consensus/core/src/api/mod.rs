@@ -88,6 +88,12 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the chain changes between the given block and the headers selected tip. Unlike
+    /// `get_virtual_chain_from_block`, this requires only headers and is hence available for light nodes as well
+    fn get_headers_selected_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
+        unimplemented!()
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         unimplemented!()
     }
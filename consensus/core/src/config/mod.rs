@@ -1,7 +1,9 @@
 pub mod constants;
 pub mod genesis;
+pub mod network;
 pub mod params;
 
+use kaspa_utils::networking::Whitelist;
 use std::ops::Deref;
 
 use {
@@ -27,6 +29,11 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Indicates whether this node is a light node, which keeps only headers and the pruning proof.
+    /// A light node validates headers (PoW and GHOSTDAG) and tracks the headers selected chain, but
+    /// does not download block bodies nor maintain a UTXO set
+    pub is_light: bool,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -42,6 +49,13 @@ pub struct Config {
     pub enable_unsynced_mining: bool,
 
     pub user_agent_comments: Vec<String>,
+
+    /// Optional limit (in bytes per second) on the aggregated upload rate used for serving IBD data to peers
+    pub ibd_upload_rate_limit: Option<u64>,
+
+    /// IP networks of trusted peers. Such peers are exempt from the inbound limit and from banning,
+    /// receive transactions without relay batching and are preferred for IBD
+    pub whitelist: Whitelist,
 }
 
 impl Config {
@@ -51,11 +65,14 @@ impl Config {
             perf: PERF_PARAMS,
             process_genesis: true,
             is_archival: false,
+            is_light: false,
             enable_sanity_checks: false,
             utxoindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             user_agent_comments: Default::default(),
+            ibd_upload_rate_limit: None,
+            whitelist: Default::default(),
         }
     }
 
@@ -118,6 +135,11 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn set_light(mut self) -> Self {
+        self.config.is_light = true;
+        self
+    }
+
     pub fn enable_sanity_checks(mut self) -> Self {
         self.config.enable_sanity_checks = true;
         self
@@ -0,0 +1,356 @@
+//!
+//! Custom network definitions -- allow running private networks with their own genesis block and consensus
+//! params, loaded from a TOML file instead of the hard-coded [`Params`] constants
+//!
+
+use super::{genesis::GenesisBlock, params::Params};
+use crate::{header::Header, merkle::calc_hash_merkle_root, networktype::NetworkType, BlockLevel, KType};
+use kaspa_addresses::Prefix;
+use kaspa_hashes::Hash;
+use kaspa_muhash::EMPTY_MUHASH;
+use serde::{de::Error as _, Deserialize, Deserializer};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NetworkDefinitionError {
+    #[error("cannot read network file {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+
+    #[error("invalid network file {0}: {1}")]
+    Parse(PathBuf, toml::de::Error),
+
+    #[error("a custom network cannot be based on mainnet")]
+    MainnetBase,
+
+    #[error("network suffix {0} is reserved for {1}")]
+    ReservedSuffix(u32, NetworkType),
+
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+
+    #[error("genesis hash merkle root {0} does not match the computed merkle root {1}")]
+    GenesisMerkleRootMismatch(Hash, Hash),
+
+    #[error("genesis hash {0} does not match the computed hash {1}")]
+    GenesisHashMismatch(Hash, Hash),
+}
+
+pub type NetworkDefinitionResult<T> = std::result::Result<T, NetworkDefinitionError>;
+
+/// Definition of a custom network, as read from a TOML network file.
+///
+/// A custom network is based on one of the predefined non-mainnet networks: its params and genesis serve as
+/// defaults for any value the file does not specify. The network is identified by the base network type along
+/// with a mandatory suffix (ie. `kaspa-devnet-<suffix>`), which also separates its data directory from the
+/// one of the base network. DNS seeders are never inherited from the base network.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkDefinition {
+    pub network: NetworkType,
+    pub suffix: u32,
+    #[serde(default, deserialize_with = "deserialize_option_prefix")]
+    pub prefix: Option<Prefix>,
+    #[serde(default)]
+    pub p2p_port: Option<u16>,
+    #[serde(default)]
+    pub rpc_port: Option<u16>,
+    #[serde(default)]
+    pub dns_seeders: Vec<String>,
+    #[serde(default)]
+    pub params: ParamsOverrides,
+    #[serde(default)]
+    pub genesis: Option<GenesisDefinition>,
+}
+
+impl NetworkDefinition {
+    pub fn load(path: &Path) -> NetworkDefinitionResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|err| NetworkDefinitionError::Read(path.to_owned(), err))?;
+        Self::parse(path, &content)
+    }
+
+    pub fn parse(path: &Path, content: &str) -> NetworkDefinitionResult<Self> {
+        toml::from_str(content).map_err(|err| NetworkDefinitionError::Parse(path.to_owned(), err))
+    }
+
+    /// Builds the network params. Since [`Params`] only holds static references, the DNS seeders and the genesis
+    /// coinbase payload are leaked, so this is expected to be called once per process.
+    pub fn into_params(self) -> NetworkDefinitionResult<Params> {
+        if self.network == NetworkType::Mainnet {
+            return Err(NetworkDefinitionError::MainnetBase);
+        }
+        let mut params = Params::from(self.network);
+        if params.net_suffix == Some(self.suffix) {
+            return Err(NetworkDefinitionError::ReservedSuffix(self.suffix, self.network));
+        }
+        params.net_suffix = Some(self.suffix);
+        params.custom_prefix = self.prefix;
+        params.custom_p2p_port = self.p2p_port;
+        params.custom_rpc_port = self.rpc_port;
+        params.dns_seeders = Box::leak(self.dns_seeders.into_iter().map(|seeder| &*Box::leak(seeder.into_boxed_str())).collect());
+        if let Some(genesis) = self.genesis {
+            params.genesis = genesis.into_genesis_block()?;
+        }
+        self.params.apply(&mut params);
+        validate_params(&params)?;
+        Ok(params)
+    }
+}
+
+/// Loads the params of the custom network defined in the file at `path`
+pub fn load_network_params(path: &Path) -> NetworkDefinitionResult<Params> {
+    NetworkDefinition::load(path)?.into_params()
+}
+
+/// Overrides of consensus [`Params`] values
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ParamsOverrides {
+    pub ghostdag_k: Option<KType>,
+    pub timestamp_deviation_tolerance: Option<u64>,
+    pub target_time_per_block: Option<u64>,
+    pub max_block_parents: Option<u8>,
+    pub difficulty_window_size: Option<usize>,
+    pub mergeset_size_limit: Option<u64>,
+    pub merge_depth: Option<u64>,
+    pub finality_depth: Option<u64>,
+    pub pruning_depth: Option<u64>,
+    pub coinbase_payload_script_public_key_max_len: Option<u8>,
+    pub max_coinbase_payload_len: Option<usize>,
+    pub mass_per_tx_byte: Option<u64>,
+    pub mass_per_script_pub_key_byte: Option<u64>,
+    pub mass_per_sig_op: Option<u64>,
+    pub max_block_mass: Option<u64>,
+    pub deflationary_phase_daa_score: Option<u64>,
+    pub pre_deflationary_phase_base_subsidy: Option<u64>,
+    pub coinbase_maturity: Option<u64>,
+    pub skip_proof_of_work: Option<bool>,
+    pub max_block_level: Option<BlockLevel>,
+    pub pruning_proof_m: Option<u64>,
+}
+
+impl ParamsOverrides {
+    pub fn apply(&self, params: &mut Params) {
+        override_value(&mut params.ghostdag_k, self.ghostdag_k);
+        override_value(&mut params.timestamp_deviation_tolerance, self.timestamp_deviation_tolerance);
+        override_value(&mut params.target_time_per_block, self.target_time_per_block);
+        override_value(&mut params.max_block_parents, self.max_block_parents);
+        override_value(&mut params.difficulty_window_size, self.difficulty_window_size);
+        override_value(&mut params.mergeset_size_limit, self.mergeset_size_limit);
+        override_value(&mut params.merge_depth, self.merge_depth);
+        override_value(&mut params.finality_depth, self.finality_depth);
+        override_value(&mut params.pruning_depth, self.pruning_depth);
+        override_value(&mut params.coinbase_payload_script_public_key_max_len, self.coinbase_payload_script_public_key_max_len);
+        override_value(&mut params.max_coinbase_payload_len, self.max_coinbase_payload_len);
+        override_value(&mut params.mass_per_tx_byte, self.mass_per_tx_byte);
+        override_value(&mut params.mass_per_script_pub_key_byte, self.mass_per_script_pub_key_byte);
+        override_value(&mut params.mass_per_sig_op, self.mass_per_sig_op);
+        override_value(&mut params.max_block_mass, self.max_block_mass);
+        override_value(&mut params.deflationary_phase_daa_score, self.deflationary_phase_daa_score);
+        override_value(&mut params.pre_deflationary_phase_base_subsidy, self.pre_deflationary_phase_base_subsidy);
+        override_value(&mut params.coinbase_maturity, self.coinbase_maturity);
+        override_value(&mut params.skip_proof_of_work, self.skip_proof_of_work);
+        override_value(&mut params.max_block_level, self.max_block_level);
+        override_value(&mut params.pruning_proof_m, self.pruning_proof_m);
+    }
+}
+
+/// Definition of a genesis block. The hash and hash merkle root are computed from the other fields
+/// if omitted, and verified against them otherwise.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisDefinition {
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub hash: Option<Hash>,
+    #[serde(default)]
+    pub version: u16,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub hash_merkle_root: Option<Hash>,
+    /// Defaults to the commitment of an empty UTXO set
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub utxo_commitment: Option<Hash>,
+    pub timestamp: u64,
+    pub bits: u32,
+    pub nonce: u64,
+    #[serde(default)]
+    pub daa_score: u64,
+    /// Hex encoded payload of the genesis coinbase transaction
+    #[serde(deserialize_with = "deserialize_hex")]
+    pub coinbase_payload: Vec<u8>,
+}
+
+impl GenesisDefinition {
+    fn into_genesis_block(self) -> NetworkDefinitionResult<GenesisBlock> {
+        let mut genesis = GenesisBlock {
+            hash: Default::default(),
+            version: self.version,
+            hash_merkle_root: Default::default(),
+            utxo_commitment: self.utxo_commitment.unwrap_or(EMPTY_MUHASH),
+            timestamp: self.timestamp,
+            bits: self.bits,
+            nonce: self.nonce,
+            daa_score: self.daa_score,
+            coinbase_payload: Box::leak(self.coinbase_payload.into_boxed_slice()),
+        };
+
+        let hash_merkle_root = calc_hash_merkle_root(genesis.build_genesis_transactions().iter());
+        match self.hash_merkle_root {
+            Some(expected) if expected != hash_merkle_root => {
+                return Err(NetworkDefinitionError::GenesisMerkleRootMismatch(expected, hash_merkle_root))
+            }
+            _ => genesis.hash_merkle_root = hash_merkle_root,
+        }
+
+        let hash = Header::from(&genesis).hash;
+        match self.hash {
+            Some(expected) if expected != hash => return Err(NetworkDefinitionError::GenesisHashMismatch(expected, hash)),
+            _ => genesis.hash = hash,
+        }
+
+        Ok(genesis)
+    }
+}
+
+fn validate_params(params: &Params) -> NetworkDefinitionResult<()> {
+    if params.ghostdag_k == 0 {
+        return Err(NetworkDefinitionError::InvalidParams("ghostdag_k must be positive".to_owned()));
+    }
+    if params.target_time_per_block == 0 {
+        return Err(NetworkDefinitionError::InvalidParams("target_time_per_block must be positive".to_owned()));
+    }
+    if params.difficulty_window_size == 0 {
+        return Err(NetworkDefinitionError::InvalidParams("difficulty_window_size must be positive".to_owned()));
+    }
+    if params.pruning_depth < params.anticone_finalization_depth() {
+        return Err(NetworkDefinitionError::InvalidParams(format!(
+            "pruning_depth must be at least the anticone finalization depth ({})",
+            params.anticone_finalization_depth()
+        )));
+    }
+    Ok(())
+}
+
+fn override_value<T>(target: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *target = value;
+    }
+}
+
+fn deserialize_option_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Option::<String>::deserialize(deserializer)?.map(|s| T::from_str(&s).map_err(D::Error::custom)).transpose()
+}
+
+fn deserialize_option_prefix<'de, D>(deserializer: D) -> Result<Option<Prefix>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?.map(|s| Prefix::try_from(s.as_str()).map_err(D::Error::custom)).transpose()
+}
+
+fn deserialize_hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    let mut bytes = vec![0u8; s.len() / 2];
+    faster_hex::hex_decode(s.as_bytes(), &mut bytes).map_err(D::Error::custom)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{genesis::DEVNET_GENESIS, params::DEVNET_PARAMS};
+
+    const NETWORK_FILE: &str = "devnet-7.toml";
+
+    #[test]
+    fn test_network_definition() {
+        let content = r#"
+            network = "devnet"
+            suffix = 7
+            p2p_port = 26611
+            dns_seeders = ["seeder.example.org"]
+
+            [params]
+            ghostdag_k = 20
+            target_time_per_block = 500
+            mergeset_size_limit = 200
+        "#;
+        let params = NetworkDefinition::parse(Path::new(NETWORK_FILE), content).unwrap().into_params().unwrap();
+        assert_eq!(params.network_name(), "kaspa-devnet-7");
+        assert_eq!(params.prefix(), Prefix::Devnet);
+        assert_eq!(params.default_p2p_port(), 26611);
+        assert_eq!(params.default_rpc_port(), DEVNET_PARAMS.default_rpc_port());
+        assert_eq!(params.dns_seeders, &["seeder.example.org"]);
+        assert_eq!(params.ghostdag_k, 20);
+        assert_eq!(params.target_time_per_block, 500);
+        assert_eq!(params.mergeset_size_limit, 200);
+        assert_eq!(params.finality_depth, DEVNET_PARAMS.finality_depth);
+        assert_eq!(params.genesis.hash, DEVNET_GENESIS.hash);
+    }
+
+    #[test]
+    fn test_genesis_definition() {
+        let genesis = |hash: Option<Hash>| GenesisDefinition {
+            hash,
+            version: DEVNET_GENESIS.version,
+            hash_merkle_root: None,
+            utxo_commitment: None,
+            timestamp: DEVNET_GENESIS.timestamp,
+            bits: DEVNET_GENESIS.bits,
+            nonce: DEVNET_GENESIS.nonce,
+            daa_score: DEVNET_GENESIS.daa_score,
+            coinbase_payload: DEVNET_GENESIS.coinbase_payload.to_vec(),
+        };
+
+        // The computed hash and merkle root match the hard-coded devnet genesis
+        let block = genesis(None).into_genesis_block().unwrap();
+        assert_eq!(block.hash, DEVNET_GENESIS.hash);
+        assert_eq!(block.hash_merkle_root, DEVNET_GENESIS.hash_merkle_root);
+        assert!(genesis(Some(DEVNET_GENESIS.hash)).into_genesis_block().is_ok());
+        assert!(matches!(genesis(Some(1.into())).into_genesis_block(), Err(NetworkDefinitionError::GenesisHashMismatch(..))));
+
+        // Genesis tables are parsed with hex encoded hashes and payload
+        let content = format!(
+            "network = \"testnet\"\nsuffix = 12\nprefix = \"kaspasim\"\n[genesis]\nhash = \"{}\"\ntimestamp = {}\nbits = {}\nnonce = {}\ncoinbase_payload = \"{}\"",
+            DEVNET_GENESIS.hash,
+            DEVNET_GENESIS.timestamp,
+            DEVNET_GENESIS.bits,
+            DEVNET_GENESIS.nonce,
+            faster_hex::hex_string(DEVNET_GENESIS.coinbase_payload),
+        );
+        let params = NetworkDefinition::parse(Path::new(NETWORK_FILE), &content).unwrap().into_params().unwrap();
+        assert_eq!(params.network_name(), "kaspa-testnet-12");
+        assert_eq!(params.prefix(), Prefix::Simnet);
+        assert_eq!(params.genesis.hash, DEVNET_GENESIS.hash);
+        assert_eq!(params.genesis.coinbase_payload, DEVNET_GENESIS.coinbase_payload);
+    }
+
+    #[test]
+    fn test_network_definition_errors() {
+        let path = Path::new(NETWORK_FILE);
+        let into_params = |content: &str| NetworkDefinition::parse(path, content).and_then(|definition| definition.into_params());
+        assert!(matches!(into_params("network = \"devnet\""), Err(NetworkDefinitionError::Parse(..))));
+        assert!(matches!(into_params("network = \"devnet\"\nsuffix = 1\nghostdag_k = 4"), Err(NetworkDefinitionError::Parse(..))));
+        assert!(matches!(
+            into_params("network = \"devnet\"\nsuffix = 1\nprefix = \"kaspax\""),
+            Err(NetworkDefinitionError::Parse(..))
+        ));
+        assert!(matches!(into_params("network = \"mainnet\"\nsuffix = 1"), Err(NetworkDefinitionError::MainnetBase)));
+        assert!(matches!(into_params("network = \"testnet\"\nsuffix = 10"), Err(NetworkDefinitionError::ReservedSuffix(10, _))));
+        assert!(matches!(
+            into_params("network = \"devnet\"\nsuffix = 1\n[params]\npruning_depth = 1000"),
+            Err(NetworkDefinitionError::InvalidParams(_))
+        ));
+    }
+}
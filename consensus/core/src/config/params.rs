@@ -12,6 +12,12 @@ pub struct Params {
     pub dns_seeders: &'static [&'static str],
     pub net: NetworkType,
     pub net_suffix: Option<u32>,
+    /// Address prefix overriding the prefix of `net` (set by custom network definitions)
+    pub custom_prefix: Option<Prefix>,
+    /// Default P2P port overriding the port of `net` (set by custom network definitions)
+    pub custom_p2p_port: Option<u16>,
+    /// Default RPC port overriding the port of `net` (set by custom network definitions)
+    pub custom_rpc_port: Option<u16>,
     pub genesis: GenesisBlock,
     pub ghostdag_k: KType,
     pub timestamp_deviation_tolerance: u64,
@@ -73,15 +79,15 @@ impl Params {
     }
 
     pub fn prefix(&self) -> Prefix {
-        self.net.into()
+        self.custom_prefix.unwrap_or_else(|| self.net.into())
     }
 
     pub fn default_p2p_port(&self) -> u16 {
-        self.net.default_p2p_port()
+        self.custom_p2p_port.unwrap_or_else(|| self.net.default_p2p_port())
     }
 
     pub fn default_rpc_port(&self) -> u16 {
-        self.net.default_rpc_port()
+        self.custom_rpc_port.unwrap_or_else(|| self.net.default_rpc_port())
     }
 }
 
@@ -127,6 +133,9 @@ pub const MAINNET_PARAMS: Params = Params {
     ],
     net: NetworkType::Mainnet,
     net_suffix: None,
+    custom_prefix: None,
+    custom_p2p_port: None,
+    custom_rpc_port: None,
     genesis: GENESIS,
     ghostdag_k: DEFAULT_GHOSTDAG_K,
     timestamp_deviation_tolerance: 132,
@@ -178,6 +187,9 @@ pub const TESTNET_PARAMS: Params = Params {
     ],
     net: NetworkType::Testnet,
     net_suffix: Some(10),
+    custom_prefix: None,
+    custom_p2p_port: None,
+    custom_rpc_port: None,
     genesis: TESTNET_GENESIS,
     ghostdag_k: DEFAULT_GHOSTDAG_K,
     timestamp_deviation_tolerance: 132,
@@ -225,6 +237,9 @@ pub const SIMNET_PARAMS: Params = Params {
     dns_seeders: &[],
     net: NetworkType::Simnet,
     net_suffix: None,
+    custom_prefix: None,
+    custom_p2p_port: None,
+    custom_rpc_port: None,
     genesis: SIMNET_GENESIS,
     ghostdag_k: DEFAULT_GHOSTDAG_K,
     timestamp_deviation_tolerance: 132,
@@ -272,6 +287,9 @@ pub const DEVNET_PARAMS: Params = Params {
     dns_seeders: &[],
     net: NetworkType::Devnet,
     net_suffix: None,
+    custom_prefix: None,
+    custom_p2p_port: None,
+    custom_rpc_port: None,
     genesis: DEVNET_GENESIS,
     ghostdag_k: DEFAULT_GHOSTDAG_K,
     timestamp_deviation_tolerance: 132,
@@ -7,6 +7,15 @@ pub enum ConfigError {
 
     #[error("Configuration: --logdir and --nologfiles cannot be used together")]
     MixedLogDirAndNoLogFiles,
+
+    #[error("Configuration: --networkfile cannot be used together with --testnet, --devnet or --simnet")]
+    MixedNetworkFileAndNetworkType,
+
+    #[error("Configuration: --light and --archival cannot be used together")]
+    MixedLightAndArchival,
+
+    #[error("Configuration: --light and --utxoindex cannot be used together")]
+    MixedLightAndUtxoIndex,
 }
 
 pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
@@ -1,5 +1,5 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, tx::MutableTransaction, utxo::utxo_diff::UtxoDiff};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -41,6 +41,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionAdded notification: transaction {}", "_0.transaction.id()")]
+    MempoolTransactionAdded(MempoolTransactionAddedNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: transaction {}, reason {:?}", "_0.transaction.id()", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -172,3 +178,46 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+#[derive(Debug, Clone)]
+pub struct MempoolTransactionAddedNotification {
+    /// The added transaction, with its UTXO entries populated as far as they are known
+    pub transaction: Arc<MutableTransaction>,
+    pub is_orphan: bool,
+}
+
+impl MempoolTransactionAddedNotification {
+    pub fn new(transaction: Arc<MutableTransaction>, is_orphan: bool) -> Self {
+        Self { transaction, is_orphan }
+    }
+}
+
+/// The reason why a transaction left the mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MempoolRemovalReason {
+    /// The transaction was accepted in a block
+    Accepted,
+    /// The low priority transaction stayed in the mempool for too long
+    Expired,
+    /// The transaction was evicted to make room for a transaction paying a higher fee rate
+    Evicted,
+    /// The transaction, or one of its ancestors, was double spent by a block or a replacing transaction
+    DoubleSpent,
+    /// The orphan got all its inputs resolved and moved to the transaction pool
+    OrphanResolved,
+    /// The transaction failed revalidation
+    Invalid,
+}
+
+#[derive(Debug, Clone)]
+pub struct MempoolTransactionRemovedNotification {
+    pub transaction: Arc<MutableTransaction>,
+    pub is_orphan: bool,
+    pub reason: MempoolRemovalReason,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub fn new(transaction: Arc<MutableTransaction>, is_orphan: bool, reason: MempoolRemovalReason) -> Self {
+        Self { transaction, is_orphan, reason }
+    }
+}
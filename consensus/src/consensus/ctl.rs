@@ -1,65 +1,90 @@
-use super::{factory::MultiConsensusManagementStore, Consensus};
+use super::{
+    factory::{ActiveConsensusDb, MultiConsensusManagementStore},
+    Consensus,
+};
 use kaspa_consensusmanager::ConsensusCtl;
+use kaspa_core::{info, warn};
 use kaspa_database::prelude::DB;
 use parking_lot::RwLock;
 use std::{
     path::PathBuf,
     sync::{Arc, Weak},
     thread::JoinHandle,
+    time::Duration,
 };
 
+/// The maximum time to wait for all references to a deleted consensus DB to be released
+const DB_RELEASE_TIMEOUT: Duration = Duration::from_secs(8);
+
 pub struct Ctl {
     management_store: Arc<RwLock<MultiConsensusManagementStore>>,
+    active_consensus_db: ActiveConsensusDb,
+    /// The key of the consensus entry controlled by this instance
+    key: u64,
     consensus_db_ref: Weak<DB>,
     consensus_db_path: PathBuf,
-    consensus: Arc<Consensus>,
+    /// A weak reference, so that deleting this consensus does not depend on this controller being dropped
+    consensus: Weak<Consensus>,
 }
 
 impl Ctl {
     pub fn new(
         management_store: Arc<RwLock<MultiConsensusManagementStore>>,
+        active_consensus_db: ActiveConsensusDb,
+        key: u64,
         consensus_db: Arc<DB>,
         consensus: Arc<Consensus>,
     ) -> Self {
         let consensus_db_path = consensus_db.path().to_owned();
         let consensus_db_ref = Arc::downgrade(&consensus_db);
-        Self { management_store, consensus_db_ref, consensus_db_path, consensus }
+        Self { management_store, active_consensus_db, key, consensus_db_ref, consensus_db_path, consensus: Arc::downgrade(&consensus) }
+    }
+
+    /// Waits for all references to the consensus DB to be released and destroys it. Returns whether the DB was destroyed
+    fn destroy_db(&self) -> bool {
+        let start = std::time::Instant::now();
+        while self.consensus_db_ref.strong_count() > 0 {
+            if start.elapsed() > DB_RELEASE_TIMEOUT {
+                return false;
+            }
+            // Sometimes another thread is shutting-down and cleaning resources
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        if DB::destroy(&rocksdb::Options::default(), &self.consensus_db_path).is_err() {
+            return false;
+        }
+        // Remove the directory itself along with any remaining non-DB files
+        std::fs::remove_dir_all(&self.consensus_db_path).is_ok() || !self.consensus_db_path.exists()
     }
 }
 
 impl ConsensusCtl for Ctl {
     fn start(&self) -> Vec<JoinHandle<()>> {
-        self.consensus.run_processors()
+        self.consensus.upgrade().map(|consensus| consensus.run_processors()).unwrap_or_default()
     }
 
     fn stop(&self) {
-        self.consensus.signal_exit()
+        if let Some(consensus) = self.consensus.upgrade() {
+            consensus.signal_exit()
+        }
     }
 
     fn make_active(&self) {
-        // TODO: pass a value to make sure the correct consensus is committed
-        self.management_store.write().commit_staging_consensus().unwrap();
-
-        // TODO: delete previous active
+        self.management_store.write().commit_staging_consensus(self.key).unwrap();
+        *self.active_consensus_db.write() = self.consensus_db_ref.clone();
     }
 
     fn delete(&self) {
-        // TODO: see above
-        self.management_store.write().cancel_staging_consensus().unwrap();
+        // First unset the staging key, so that the entry is considered inactive from this point on. If the process
+        // is interrupted or the DB cannot be destroyed now, the entry will be purged on the next startup
+        self.management_store.write().cancel_staging_consensus(self.key).unwrap();
 
-        // TODO: delete staging
-        // for _ in 0..16 {
-        //     if self.consensus_db_ref.strong_count() > 0 {
-        //         // Sometimes another thread is shuting-down and cleaning resources
-        //         std::thread::sleep(std::time::Duration::from_millis(500));
-        //     } else {
-        //         break;
-        //     }
-        // }
-        // assert_eq!(self.consensus_db_ref.strong_count(), 0, "DB has strong references and cannot be deleted");
-        // let options = rocksdb::Options::default();
-        // DB::destroy(&options, self.consensus_db_path.clone())
-        //     .expect("DB is expected to be deletable since there are no references to it");
+        if self.destroy_db() {
+            info!("Deleted consensus directory {}", self.consensus_db_path.display());
+            self.management_store.write().delete_inactive_entry(self.key).unwrap();
+        } else {
+            warn!("Consensus directory {} is still in use and will be deleted on the next startup", self.consensus_db_path.display());
+        }
     }
 }
 
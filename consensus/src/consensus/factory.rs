@@ -3,16 +3,22 @@ use crate::{model::stores::U64Key, pipeline::ProcessingCounters};
 use kaspa_consensus_core::config::Config;
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensusmanager::{ConsensusFactory, ConsensusInstance, DynConsensusCtl, SessionLock};
-use kaspa_core::time::unix_now;
+use kaspa_core::{info, time::unix_now, warn};
 use kaspa_database::prelude::{
     BatchDbWriter, CachedDbAccess, CachedDbItem, DirectDbWriter, StoreError, StoreResult, StoreResultExtensions, DB,
 };
 use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+};
+
+const CONSENSUS_DIR_PREFIX: &str = "consensus-";
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ConsensusEntry {
     key: u64,
     directory_name: String,
@@ -25,7 +31,19 @@ impl ConsensusEntry {
     }
 
     pub fn from_key(key: u64) -> Self {
-        Self { key, directory_name: format!("consensus-{:0>3}", key), creation_timestamp: unix_now() }
+        Self { key, directory_name: format!("{CONSENSUS_DIR_PREFIX}{:0>3}", key), creation_timestamp: unix_now() }
+    }
+
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    pub fn directory_name(&self) -> &str {
+        &self.directory_name
+    }
+
+    pub fn creation_timestamp(&self) -> u64 {
+        self.creation_timestamp
     }
 }
 
@@ -34,7 +52,7 @@ pub enum ConsensusEntryType {
     New(ConsensusEntry),
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct MultiConsensusMetadata {
     current_consensus_key: Option<u64>,
     staging_consensus_key: Option<u64>,
@@ -43,6 +61,24 @@ pub struct MultiConsensusMetadata {
     is_archival_node: bool,
 }
 
+impl MultiConsensusMetadata {
+    pub fn current_consensus_key(&self) -> Option<u64> {
+        self.current_consensus_key
+    }
+
+    pub fn staging_consensus_key(&self) -> Option<u64> {
+        self.staging_consensus_key
+    }
+
+    pub fn max_key_used(&self) -> u64 {
+        self.max_key_used
+    }
+
+    pub fn is_archival_node(&self) -> bool {
+        self.is_archival_node
+    }
+}
+
 const CONSENSUS_ENTRIES_PREFIX: &[u8] = b"consensus-entries-prefix";
 const MULTI_CONSENSUS_METADATA_KEY: &[u8] = b"multi-consensus-metadata-key";
 
@@ -51,17 +87,42 @@ pub struct MultiConsensusManagementStore {
     db: Arc<DB>,
     entries: CachedDbAccess<U64Key, ConsensusEntry>,
     metadata: CachedDbItem<MultiConsensusMetadata>,
+    /// The key of a staging consensus found incomplete on init, if any
+    interrupted_staging_key: Option<u64>,
 }
 
 impl MultiConsensusManagementStore {
     pub fn new(db: Arc<DB>) -> Self {
-        let mut store = Self {
+        let mut store = Self::new_read_only(db);
+        store.init();
+        store
+    }
+
+    /// Creates the store without initializing it or recovering an interrupted staging consensus, hence the DB can be
+    /// opened in read-only mode. Meant for inspection purposes only
+    pub fn new_read_only(db: Arc<DB>) -> Self {
+        Self {
             db: db.clone(),
             entries: CachedDbAccess::new(db.clone(), 16, CONSENSUS_ENTRIES_PREFIX.to_vec()),
             metadata: CachedDbItem::new(db, MULTI_CONSENSUS_METADATA_KEY.to_vec()),
-        };
-        store.init();
-        store
+            interrupted_staging_key: None,
+        }
+    }
+
+    pub fn metadata(&self) -> StoreResult<MultiConsensusMetadata> {
+        self.metadata.read()
+    }
+
+    /// Returns the current active consensus entry, if such exists. Unlike `active_consensus_entry`, never captures a new key
+    pub fn current_consensus_entry(&self) -> StoreResult<Option<ConsensusEntry>> {
+        match self.metadata.read()?.current_consensus_key {
+            Some(key) => Ok(Some(self.entries.read(key.into())?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn consensus_entries(&self) -> Vec<ConsensusEntry> {
+        self.entries.iterator().map(|r| r.unwrap().1).collect()
     }
 
     fn init(&mut self) {
@@ -72,7 +133,60 @@ impl MultiConsensusManagementStore {
             self.db.write(batch).unwrap();
         }
 
-        // TODO: iterate through consensus entries and remove non active ones (if not archival)
+        // A staging consensus key which is still set means the previous run was interrupted before the staging
+        // consensus was committed or cancelled. We reset it here, which leaves its entry as an inactive one to be
+        // purged (see `inactive_consensus_entries`)
+        let metadata = self.metadata.read().unwrap();
+        if let Some(key) = metadata.staging_consensus_key {
+            warn!("Found an incomplete staging consensus (key {}) from a previous run", key);
+            self.interrupted_staging_key = Some(key);
+            self.metadata
+                .update(DirectDbWriter::new(&self.db), |mut data| {
+                    data.staging_consensus_key = None;
+                    data
+                })
+                .unwrap();
+        }
+    }
+
+    pub fn interrupted_staging_key(&self) -> Option<u64> {
+        self.interrupted_staging_key
+    }
+
+    /// Memorizes whether this node is an archival node and returns whether it was one on the previous run
+    pub fn set_is_archival_node(&mut self, is_archival_node: bool) -> StoreResult<bool> {
+        let was_archival_node = self.metadata.read()?.is_archival_node;
+        if was_archival_node != is_archival_node {
+            self.metadata.update(DirectDbWriter::new(&self.db), |mut data| {
+                data.is_archival_node = is_archival_node;
+                data
+            })?;
+        }
+        Ok(was_archival_node)
+    }
+
+    /// Returns all entries other than the current active and the current staging consensus. These are left-overs of
+    /// previously active, cancelled or interrupted consensus instances
+    pub fn inactive_consensus_entries(&self) -> StoreResult<Vec<ConsensusEntry>> {
+        let metadata = self.metadata.read()?;
+        Ok(self
+            .entries
+            .iterator()
+            .map(|r| r.unwrap().1)
+            .filter(|entry| Some(entry.key) != metadata.current_consensus_key && Some(entry.key) != metadata.staging_consensus_key)
+            .collect())
+    }
+
+    /// Returns the directory names of all consensus entries
+    pub fn entry_directory_names(&self) -> Vec<String> {
+        self.entries.iterator().map(|r| r.unwrap().1.directory_name).collect()
+    }
+
+    /// Deletes an inactive entry. Should be called only after the entry directory was deleted
+    pub fn delete_inactive_entry(&mut self, key: u64) -> StoreResult<()> {
+        let metadata = self.metadata.read()?;
+        assert!(Some(key) != metadata.current_consensus_key && Some(key) != metadata.staging_consensus_key);
+        self.entries.delete(DirectDbWriter::new(&self.db), key.into())
     }
 
     /// The entry type signifies whether the returned entry is an existing/new consensus
@@ -107,7 +221,11 @@ impl MultiConsensusManagementStore {
     pub fn new_staging_consensus_entry(&mut self) -> StoreResult<ConsensusEntry> {
         let mut metadata = self.metadata.read()?;
 
-        // TODO: handle the case where `staging_consensus_key` is already some (perhaps from a previous interrupted run)
+        // A staging key from a previous interrupted run is reset on init, so an existing key here means a staging
+        // consensus of this run was abandoned without being cancelled. Its entry is left to be purged as inactive
+        if let Some(key) = metadata.staging_consensus_key {
+            warn!("Replacing an abandoned staging consensus (key {})", key);
+        }
 
         metadata.max_key_used += 1;
         let new_key = metadata.max_key_used;
@@ -122,16 +240,33 @@ impl MultiConsensusManagementStore {
         Ok(new_entry)
     }
 
-    pub fn commit_staging_consensus(&mut self) -> StoreResult<()> {
+    /// Atomically sets the staging consensus `key` as the current active consensus. The previously active entry
+    /// becomes inactive and is purged on the next startup
+    pub fn commit_staging_consensus(&mut self, key: u64) -> StoreResult<()> {
+        let metadata = self.metadata.read()?;
+        if metadata.staging_consensus_key != Some(key) {
+            return Err(StoreError::DataInconsistency(format!(
+                "cannot commit consensus {key} since it is not the staging consensus (staging: {:?})",
+                metadata.staging_consensus_key
+            )));
+        }
         self.metadata.update(DirectDbWriter::new(&self.db), |mut data| {
-            assert!(data.staging_consensus_key.is_some());
             data.current_consensus_key = data.staging_consensus_key.take();
             data
         })?;
         Ok(())
     }
 
-    pub fn cancel_staging_consensus(&mut self) -> StoreResult<()> {
+    /// Atomically unsets the staging consensus `key`. Its entry becomes inactive and should be deleted along with
+    /// its directory (see `delete_inactive_entry`)
+    pub fn cancel_staging_consensus(&mut self, key: u64) -> StoreResult<()> {
+        let metadata = self.metadata.read()?;
+        if metadata.staging_consensus_key != Some(key) {
+            return Err(StoreError::DataInconsistency(format!(
+                "cannot cancel consensus {key} since it is not the staging consensus (staging: {:?})",
+                metadata.staging_consensus_key
+            )));
+        }
         self.metadata.update(DirectDbWriter::new(&self.db), |mut data| {
             data.staging_consensus_key = None;
             data
@@ -147,8 +282,13 @@ pub struct Factory {
     db_parallelism: usize,
     notification_root: Arc<ConsensusNotificationRoot>,
     counters: Arc<ProcessingCounters>,
+    /// The DB of the currently active consensus instance, updated when a staging consensus becomes active
+    active_consensus_db: ActiveConsensusDb,
 }
 
+/// A shared weak reference to the DB of the active consensus
+pub(crate) type ActiveConsensusDb = Arc<RwLock<Weak<DB>>>;
+
 impl Factory {
     pub fn new(
         management_db: Arc<DB>,
@@ -160,13 +300,64 @@ impl Factory {
     ) -> Self {
         let mut config = config.clone();
         config.process_genesis = false;
+        let management_store = Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db)));
+        Self::purge_inactive_consensus_instances(&management_store, &db_root_dir, config.is_archival);
         Self {
-            management_store: Arc::new(RwLock::new(MultiConsensusManagementStore::new(management_db))),
+            management_store,
             config,
             db_root_dir,
             db_parallelism,
             notification_root,
             counters,
+            active_consensus_db: Default::default(),
+        }
+    }
+
+    /// Returns the DB of the active consensus, if such a consensus is currently alive
+    pub fn active_consensus_db(&self) -> Option<Arc<DB>> {
+        self.active_consensus_db.read().upgrade()
+    }
+
+    /// Deletes the directories and entries of all inactive consensus instances. Archival nodes retain previously
+    /// active instances since the block history they hold cannot be recovered from the network
+    fn purge_inactive_consensus_instances(
+        management_store: &RwLock<MultiConsensusManagementStore>,
+        db_root_dir: &Path,
+        is_archival: bool,
+    ) {
+        let mut management_store = management_store.write();
+        if management_store.set_is_archival_node(is_archival).unwrap() && !is_archival {
+            info!("The node is no longer archival, deleting previously retained consensus instances");
+        }
+        for entry in management_store.inactive_consensus_entries().unwrap() {
+            if is_archival && Some(entry.key) != management_store.interrupted_staging_key() {
+                info!("Archival node: retaining inactive consensus directory {}", entry.directory_name);
+                continue;
+            }
+            let dir = db_root_dir.join(&entry.directory_name);
+            if dir.exists() {
+                info!("Deleting inactive consensus directory {}", dir.display());
+                if let Err(err) = fs::remove_dir_all(&dir) {
+                    // Keep the entry so that deletion is retried on the next startup
+                    warn!("Failed deleting inactive consensus directory {}: {}", dir.display(), err);
+                    continue;
+                }
+            }
+            management_store.delete_inactive_entry(entry.key).unwrap();
+        }
+
+        // Delete consensus directories which are not referenced by any entry. Such directories are created when
+        // the process is interrupted before a new active consensus entry is saved
+        let known_dirs = management_store.entry_directory_names();
+        let Ok(read_dir) = fs::read_dir(db_root_dir) else { return };
+        for dir_entry in read_dir.filter_map(|r| r.ok()) {
+            let name = dir_entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with(CONSENSUS_DIR_PREFIX) && dir_entry.path().is_dir() && !known_dirs.contains(&name) {
+                info!("Deleting orphaned consensus directory {}", dir_entry.path().display());
+                if let Err(err) = fs::remove_dir_all(dir_entry.path()) {
+                    warn!("Failed deleting orphaned consensus directory {}: {}", dir_entry.path().display(), err);
+                }
+            }
         }
     }
 }
@@ -187,6 +378,7 @@ impl ConsensusFactory for Factory {
                 entry
             }
         };
+        let key = entry.key;
         let dir = self.db_root_dir.join(entry.directory_name.clone());
         let db = kaspa_database::prelude::open_db(dir, true, self.db_parallelism);
 
@@ -204,12 +396,17 @@ impl ConsensusFactory for Factory {
         if is_new_consensus {
             self.management_store.write().save_new_active_consensus(entry).unwrap();
         }
+        *self.active_consensus_db.write() = Arc::downgrade(&db);
 
-        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+        (
+            ConsensusInstance::new(session_lock, consensus.clone()),
+            Arc::new(Ctl::new(self.management_store.clone(), self.active_consensus_db.clone(), key, db, consensus)),
+        )
     }
 
     fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
         let entry = self.management_store.write().new_staging_consensus_entry().unwrap();
+        let key = entry.key;
         let dir = self.db_root_dir.join(entry.directory_name);
         let db = kaspa_database::prelude::open_db(dir, true, self.db_parallelism);
 
@@ -222,6 +419,50 @@ impl ConsensusFactory for Factory {
             self.counters.clone(),
         ));
 
-        (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
+        (
+            ConsensusInstance::new(session_lock, consensus.clone()),
+            Arc::new(Ctl::new(self.management_store.clone(), self.active_consensus_db.clone(), key, db, consensus)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::utils::create_temp_db;
+
+    #[test]
+    fn test_staging_consensus_lifecycle() {
+        let (_lifetime, db) = create_temp_db();
+        let mut store = MultiConsensusManagementStore::new(db.clone());
+        let ConsensusEntryType::New(active) = store.active_consensus_entry().unwrap() else { panic!("expected a new entry") };
+        store.save_new_active_consensus(active.clone()).unwrap();
+
+        // Commit and cancel verify they refer to the current staging consensus
+        let staging = store.new_staging_consensus_entry().unwrap();
+        assert!(store.commit_staging_consensus(staging.key + 1).is_err());
+        assert!(store.cancel_staging_consensus(active.key).is_err());
+        assert!(store.inactive_consensus_entries().unwrap().is_empty());
+
+        // Simulate an interrupted run by reopening the store while staging is in progress
+        let mut store = MultiConsensusManagementStore::new(db.clone());
+        assert_eq!(store.interrupted_staging_key(), Some(staging.key));
+        let inactive = store.inactive_consensus_entries().unwrap();
+        assert_eq!(inactive.iter().map(|e| e.key).collect::<Vec<_>>(), vec![staging.key]);
+        store.delete_inactive_entry(staging.key).unwrap();
+        assert!(matches!(store.active_consensus_entry().unwrap(), ConsensusEntryType::Existing(e) if e.key == active.key));
+
+        // A committed staging consensus turns the previously active entry inactive
+        let staging = store.new_staging_consensus_entry().unwrap();
+        store.commit_staging_consensus(staging.key).unwrap();
+        assert!(matches!(store.active_consensus_entry().unwrap(), ConsensusEntryType::Existing(e) if e.key == staging.key));
+        assert_eq!(store.inactive_consensus_entries().unwrap().iter().map(|e| e.key).collect::<Vec<_>>(), vec![active.key]);
+        assert!(store.commit_staging_consensus(staging.key).is_err());
+
+        // The archival flag of the previous run is memorized
+        assert!(!store.set_is_archival_node(true).unwrap());
+        let mut store = MultiConsensusManagementStore::new(db);
+        assert_eq!(store.interrupted_staging_key(), None);
+        assert!(store.set_is_archival_node(false).unwrap());
     }
 }
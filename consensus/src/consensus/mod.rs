@@ -185,6 +185,7 @@ impl Consensus {
         let header_processor = Arc::new(HeaderProcessor::new(
             receiver,
             body_sender,
+            config.is_light.then(|| (pruning_sender.clone(), pruning_receiver.clone())),
             block_processors_pool.clone(),
             params,
             db.clone(),
@@ -401,8 +402,14 @@ impl ConsensusApi for Consensus {
     }
 
     fn is_nearly_synced(&self) -> bool {
+        // Light nodes do not advance virtual, so we use the headers selected tip instead of the sink
+        let timestamp = if self.config.is_light {
+            self.headers_store.get_timestamp(self.get_headers_selected_tip()).unwrap()
+        } else {
+            self.get_sink_timestamp()
+        };
         // See comment within `config.is_nearly_synced`
-        self.config.is_nearly_synced(self.get_sink_timestamp())
+        self.config.is_nearly_synced(timestamp)
     }
 
     fn get_virtual_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
@@ -414,6 +421,11 @@ impl ConsensusApi for Consensus {
         Ok(self.services.dag_traversal_manager.calculate_chain_path(hash, self.get_sink()))
     }
 
+    fn get_headers_selected_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
+        self.validate_block_exists(hash)?;
+        Ok(self.services.dag_traversal_manager.calculate_chain_path(hash, self.get_headers_selected_tip()))
+    }
+
     fn get_virtual_parents(&self) -> BlockHashSet {
         self.virtual_stores.read().state.get().unwrap().parents.iter().copied().collect()
     }
@@ -184,6 +184,7 @@ impl ConsensusServices {
             params.anticone_finalization_depth(),
             params.difficulty_window_size,
             params.ghostdag_k,
+            config.is_light,
         ));
 
         let sync_manager = SyncManager::new(
@@ -20,7 +20,7 @@ use crate::{
         services::reachability::MTReachabilityService,
         stores::{
             ghostdag::DbGhostdagStore, headers::HeaderStoreReader, pruning::PruningStoreReader, reachability::DbReachabilityStore,
-            virtual_state::VirtualStores, DB,
+            utxo_diffs::UtxoDiffsStoreReader, utxo_multisets::UtxoMultisetsStoreReader, virtual_state::VirtualStores, DB,
         },
     },
     params::Params,
@@ -162,6 +162,14 @@ impl TestConsensus {
         self.consensus.headers_store.clone()
     }
 
+    pub fn utxo_diffs_store(&self) -> Arc<impl UtxoDiffsStoreReader> {
+        self.consensus.utxo_diffs_store.clone()
+    }
+
+    pub fn utxo_multisets_store(&self) -> Arc<impl UtxoMultisetsStoreReader> {
+        self.consensus.utxo_multisets_store.clone()
+    }
+
     pub fn virtual_stores(&self) -> Arc<RwLock<VirtualStores>> {
         self.consensus.virtual_stores.clone()
     }
@@ -25,7 +25,10 @@ use crate::{
         },
     },
     params::Params,
-    pipeline::deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+    pipeline::{
+        deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
+        pruning_processor::processor::PruningProcessingMessage,
+    },
     processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations::RelationsStoreExtensions},
 };
 use crossbeam_channel::{Receiver, Sender};
@@ -109,6 +112,10 @@ pub struct HeaderProcessor {
     receiver: Receiver<BlockProcessingMessage>,
     body_sender: Sender<BlockProcessingMessage>,
 
+    // Pruning channel of light nodes. Light nodes do not advance virtual, hence pruning is driven
+    // by the headers selected tip instead of by the virtual processor
+    light_pruning_channel: Option<(Sender<PruningProcessingMessage>, Receiver<PruningProcessingMessage>)>,
+
     // Thread pool
     pub(super) thread_pool: Arc<ThreadPool>,
 
@@ -164,6 +171,7 @@ impl HeaderProcessor {
     pub fn new(
         receiver: Receiver<BlockProcessingMessage>,
         body_sender: Sender<BlockProcessingMessage>,
+        light_pruning_channel: Option<(Sender<PruningProcessingMessage>, Receiver<PruningProcessingMessage>)>,
         thread_pool: Arc<ThreadPool>,
         params: &Params,
         db: Arc<DB>,
@@ -175,6 +183,7 @@ impl HeaderProcessor {
         Self {
             receiver,
             body_sender,
+            light_pruning_channel,
             thread_pool,
             genesis: params.genesis.clone(),
             difficulty_window_size: params.difficulty_window_size,
@@ -240,6 +249,9 @@ impl HeaderProcessor {
     fn queue_block(self: &Arc<HeaderProcessor>, task_id: TaskId) {
         if let Some(task) = self.task_manager.try_begin(task_id) {
             let res = self.process_header(&task);
+            if res.is_ok() {
+                self.notify_light_pruning();
+            }
 
             let dependent_tasks = self.task_manager.end(task, |task, result_transmitter| {
                 if res.is_err() || task.block().is_header_only() {
@@ -257,6 +269,18 @@ impl HeaderProcessor {
         }
     }
 
+    /// Updates the pruning processor about the headers selected tip (light nodes only). Note this is called
+    /// only after the pruning lock was released by header processing, since pruning acquires it for write
+    fn notify_light_pruning(&self) {
+        let Some((pruning_sender, pruning_receiver)) = self.light_pruning_channel.as_ref() else { return; };
+        let hst = self.headers_selected_tip_store.read().get().unwrap().hash;
+        let sink_ghostdag_data = self.ghostdag_stores[0].get_compact_data(hst).unwrap();
+        // Empty the channel before sending the new message, since we only care about the most recent one (see virtual processor).
+        // Header processing is concurrent, so we do not block if the channel was meanwhile refilled by another worker
+        let _consume = pruning_receiver.try_iter().count();
+        let _ = pruning_sender.try_send(PruningProcessingMessage::Process { sink_ghostdag_data });
+    }
+
     fn process_header(&self, task: &BlockTask) -> BlockProcessResult<BlockStatus> {
         let _prune_guard = self.pruning_lock.blocking_read();
         let header = &task.block().header;
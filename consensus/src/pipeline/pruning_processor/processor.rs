@@ -17,7 +17,6 @@ use crate::{
             selected_chain::SelectedChainStore,
             tips::{TipsStore, TipsStoreReader},
             utxo_diffs::UtxoDiffsStoreReader,
-            virtual_state::VirtualStateStoreReader,
         },
     },
     processes::{pruning_proof::PruningProofManager, reachability::inquirer as reachability, relations},
@@ -34,7 +33,7 @@ use kaspa_consensus_core::{
     BlockHashSet,
 };
 use kaspa_consensusmanager::SessionLock;
-use kaspa_core::{debug, info, warn};
+use kaspa_core::{debug, info};
 use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, DB};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
@@ -50,6 +49,8 @@ use std::{
 
 pub enum PruningProcessingMessage {
     Exit,
+    /// Sent by the virtual processor on sink changes, or by the header processor of light nodes with
+    /// the GHOSTDAG data of the headers selected tip (which takes the role of the sink for such nodes)
     Process { sink_ghostdag_data: CompactGhostdagData },
 }
 
@@ -133,8 +134,9 @@ impl PruningProcessor {
         );
 
         if let Some(pruning_utxoset_position) = pruning_utxoset_position {
-            // This indicates the node crashed during a former pruning point move and we need to recover
-            if pruning_utxoset_position != pruning_point {
+            // This indicates the node crashed during a former pruning point move and we need to recover.
+            // Light nodes keep no UTXO state, so their pruning utxoset is never advanced
+            if pruning_utxoset_position != pruning_point && !self.config.is_light {
                 info!("Recovering pruning utxo-set from {} to the pruning point {}", pruning_utxoset_position, pruning_point);
                 self.advance_pruning_utxoset(pruning_utxoset_position, pruning_point);
             }
@@ -142,8 +144,8 @@ impl PruningProcessor {
 
         if let Some(history_root) = history_root {
             // This indicates the node crashed or was forced to stop during a former data prune operation hence
-            // we need to complete it
-            if history_root != pruning_point {
+            // we need to complete it. Archival nodes keep their history, so there is no such operation to complete
+            if history_root != pruning_point && !self.config.is_archival {
                 self.prune(pruning_point);
             }
         }
@@ -177,11 +179,18 @@ impl PruningProcessor {
             // Inform the user
             info!("Daily pruning point movement: advancing from {} to {}", current_pruning_info.pruning_point, new_pruning_point);
 
-            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs
-            self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point);
+            // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs.
+            // Light nodes keep no UTXO state, so for them pruning only deletes old headers and relations
+            if !self.config.is_light {
+                self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point);
+            }
 
             // Finally, prune data in the new pruning point past
-            self.prune(new_pruning_point);
+            if self.config.is_archival {
+                self.prune_archival(current_pruning_info.pruning_point, new_pruning_point);
+            } else {
+                self.prune(new_pruning_point);
+            }
         } else if new_candidate != current_pruning_info.candidate {
             let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
             pruning_point_write.set(current_pruning_info.pruning_point, new_candidate, current_pruning_info.index).unwrap();
@@ -214,12 +223,63 @@ impl PruningProcessor {
         assert_eq!(multiset.finalize(), commitment, "Updated pruning point utxo set does not match the header utxo commitment");
     }
 
-    fn prune(&self, new_pruning_point: Hash) {
-        if self.config.is_archival {
-            warn!("The node is configured as an archival node -- avoiding data pruning. Note this might lead to heavy disk usage.");
-            return;
+    /// Archival nodes keep the full block history below the pruning point, i.e., headers, bodies, acceptance data and
+    /// all DAG relations. Only UTXO state data of blocks in the past of the new pruning point is pruned, since it is
+    /// not part of the history and is no longer required once the pruning point UTXO set has advanced
+    fn prune_archival(&self, prev_pruning_point: Hash, new_pruning_point: Hash) {
+        info!("Archival node: pruning UTXO state data while keeping the full block history. Note this might lead to heavy disk usage.");
+
+        let mut prune_guard = self.pruning_lock.blocking_write();
+        let mut lock_acquire_time = Instant::now();
+
+        {
+            // Prune tips which can no longer be merged by virtual (see `prune`)
+            let mut batch = WriteBatch::default();
+            let mut tips_write = self.body_tips_store.write();
+            let pruned_tips = tips_write
+                .get()
+                .unwrap()
+                .iter()
+                .copied()
+                .filter(|&h| !self.reachability_service.is_dag_ancestor_of(new_pruning_point, h))
+                .collect_vec();
+            tips_write.prune_tips_with_writer(BatchDbWriter::new(&mut batch), &pruned_tips).unwrap();
+            self.db.write(batch).unwrap();
+            drop(tips_write);
         }
 
+        // The blocks in past(new pruning point) \ past(prev pruning point) are exactly the chain blocks between the
+        // two pruning points along with the mergesets of all chain blocks above the previous pruning point
+        let mut counter = 0;
+        for chain_block in self.reachability_service.forward_chain_iterator(prev_pruning_point, new_pruning_point, true) {
+            let mut blocks = Vec::new();
+            if chain_block != new_pruning_point {
+                blocks.push(chain_block);
+            }
+            if chain_block != prev_pruning_point {
+                blocks.extend(self.ghostdag_primary_store.get_data(chain_block).unwrap().unordered_mergeset_without_selected_parent());
+            }
+
+            let mut batch = WriteBatch::default();
+            for block in blocks {
+                self.utxo_multisets_store.delete_batch(&mut batch, block).unwrap();
+                self.utxo_diffs_store.delete_batch(&mut batch, block).unwrap();
+                counter += 1;
+            }
+            self.db.write(batch).unwrap();
+
+            // If we have the lock for more than a few milliseconds, release and recapture to allow consensus progress during pruning
+            if lock_acquire_time.elapsed() > Duration::from_millis(5) {
+                prune_guard.blocking_yield();
+                lock_acquire_time = Instant::now();
+            }
+        }
+
+        drop(prune_guard);
+        info!("Archival node: pruned UTXO state data of {} blocks", counter);
+    }
+
+    fn prune(&self, new_pruning_point: Hash) {
         let proof = self.pruning_proof_manager.get_pruning_point_proof();
         let data = self
             .pruning_proof_manager
@@ -443,10 +503,8 @@ impl PruningProcessor {
 
     fn assert_data_rebuilding(&self, ref_data: Arc<PruningPointTrustedData>, new_pruning_point: Hash) {
         info!("Rebuilding pruning point trusted data (sanity test)");
-        let virtual_state = self.virtual_stores.read().state.get().unwrap();
-        let built_data = self
-            .pruning_proof_manager
-            .calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, virtual_state.parents.iter().copied());
+        let (tips, _) = self.pruning_proof_manager.anticone_reference_tips();
+        let built_data = self.pruning_proof_manager.calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, tips.into_iter());
         assert_eq!(
             ref_data.anticone.iter().copied().collect::<BlockHashSet>(),
             built_data.anticone.iter().copied().collect::<BlockHashSet>()
@@ -39,7 +39,7 @@ use crate::{
             depth::DbDepthStore,
             ghostdag::{DbGhostdagStore, GhostdagData, GhostdagStore, GhostdagStoreReader},
             headers::{DbHeadersStore, HeaderStore, HeaderStoreReader},
-            headers_selected_tip::DbHeadersSelectedTipStore,
+            headers_selected_tip::{DbHeadersSelectedTipStore, HeadersSelectedTipStoreReader},
             past_pruning_points::{DbPastPruningPointsStore, PastPruningPointsStore},
             pruning::{DbPruningStore, PruningStoreReader},
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
@@ -96,6 +96,7 @@ pub struct PruningProofManager {
     anticone_finalization_depth: u64,
     difficulty_adjustment_window_size: usize,
     ghostdag_k: KType,
+    is_light: bool,
 }
 
 impl PruningProofManager {
@@ -113,6 +114,7 @@ impl PruningProofManager {
         anticone_finalization_depth: u64,
         difficulty_adjustment_window_size: usize,
         ghostdag_k: KType,
+        is_light: bool,
     ) -> Self {
         Self {
             db,
@@ -143,6 +145,7 @@ impl PruningProofManager {
             anticone_finalization_depth,
             difficulty_adjustment_window_size,
             ghostdag_k,
+            is_light,
         }
     }
 
@@ -727,6 +730,18 @@ impl PruningProofManager {
         proof
     }
 
+    /// Returns the tips from which the pruning point anticone is calculated, along with their blue score. These are the
+    /// virtual parents, or the headers selected tip for light nodes, which do not advance virtual
+    pub(crate) fn anticone_reference_tips(&self) -> (Vec<Hash>, u64) {
+        if self.is_light {
+            let hst = self.headers_selected_tip_store.read().get().unwrap().hash;
+            (vec![hst], self.ghostdag_stores[0].get_blue_score(hst).unwrap())
+        } else {
+            let virtual_state = self.virtual_stores.read().state.get().unwrap();
+            (virtual_state.parents.clone(), virtual_state.ghostdag_data.blue_score)
+        }
+    }
+
     pub fn get_pruning_point_anticone_and_trusted_data(&self) -> ConsensusResult<Arc<PruningPointTrustedData>> {
         let pp = self.pruning_point_store.read().pruning_point().unwrap();
         if let Some(cache) = self.cached_anticone.read().clone() {
@@ -735,12 +750,12 @@ impl PruningProofManager {
             }
         }
 
-        let virtual_state = self.virtual_stores.read().state.get().unwrap();
+        let (tips, tips_blue_score) = self.anticone_reference_tips();
         let pp_bs = self.headers_store.get_blue_score(pp).unwrap();
 
         // The anticone is considered final only if the pruning point is at sufficient depth from virtual
-        if virtual_state.ghostdag_data.blue_score >= pp_bs + self.anticone_finalization_depth {
-            let anticone = Arc::new(self.calculate_pruning_point_anticone_and_trusted_data(pp, virtual_state.parents.iter().copied()));
+        if tips_blue_score >= pp_bs + self.anticone_finalization_depth {
+            let anticone = Arc::new(self.calculate_pruning_point_anticone_and_trusted_data(pp, tips.into_iter()));
             self.cached_anticone.write().replace(CachedPruningPointData { pruning_point: pp, data: anticone.clone() });
             Ok(anticone)
         } else {
@@ -39,6 +39,13 @@ impl Loggers {
     pub fn items(&self) -> impl IntoIterator<Item = Logger> + '_ {
         self.loggers.iter().map(|x| x.logger())
     }
+
+    /// Returns the names and levels of the configured loggers, sorted by name
+    pub fn levels(&self) -> Vec<(String, LevelFilter)> {
+        let mut levels = self.loggers.iter().map(|x| (x.name.clone(), x.level)).collect::<Vec<_>>();
+        levels.sort();
+        levels
+    }
 }
 
 pub(super) struct Builder {
@@ -52,6 +59,12 @@ impl Builder {
         Builder { appenders: vec![], loggers: HashMap::new(), root_level: None }
     }
 
+    /// Initializes the builder with the loggers and root level of a previous build.
+    pub fn from_loggers(loggers: &Loggers) -> Self {
+        let loggers_map = loggers.loggers.iter().map(|x| (x.name.clone(), (x.appenders.clone(), x.level))).collect();
+        Builder { appenders: vec![], loggers: loggers_map, root_level: Some(loggers.root_level) }
+    }
+
     /// Initializes the builder from an environment variable.
     #[allow(dead_code)]
     pub fn from_env(env: &str) -> Self {
@@ -77,45 +90,33 @@ impl Builder {
     }
 
     fn parse_specs(&mut self, expression: &str) -> &mut Self {
-        for spec in expression.split(',').map(|x| x.trim()) {
-            if spec.is_empty() {
-                continue;
-            }
-            let mut parts = spec.split('=');
-            let (log_level, name) = match (parts.next(), parts.next().map(|x| x.trim()), parts.next()) {
-                (Some(part0), None, None) => {
-                    // if the single argument is a log-level string or number,
-                    // it defines the root level
-                    match part0.parse() {
-                        Ok(lvl) => (lvl, None),
-                        Err(_) => (LevelFilter::max(), Some(part0)),
-                    }
-                }
-                (Some(part0), Some(""), None) => (LevelFilter::max(), Some(part0)),
-                (Some(part0), Some(part1), None) => match part1.parse() {
-                    Ok(lvl) => (lvl, Some(part0)),
-                    _ => {
-                        println!("Ignoring invalid logging spec '{}'", LogError::ParseLoggerSpecError(part1.to_string()));
-                        continue;
-                    }
-                },
-                _ => {
-                    println!("Ignoring invalid logging spec '{}'", LogError::ParseLoggerSpecError(spec.to_string()));
-                    continue;
-                }
-            };
-            match name {
-                Some(name) => {
-                    self.logger(name.to_string(), log_level);
-                }
-                None => {
-                    self.root_level(log_level);
-                }
+        for spec in split_specs(expression) {
+            match parse_spec(spec) {
+                Ok((name, log_level)) => self.apply_spec(name, log_level),
+                Err(err) => println!("Ignoring invalid logging spec '{}'", err),
             }
         }
         self
     }
 
+    /// Parses a specs expression, failing on the first invalid spec instead of ignoring it.
+    pub fn try_parse_expression(&mut self, expression: &str) -> Result<&mut Self, LogError> {
+        let specs = split_specs(expression).map(parse_spec).collect::<Result<Vec<_>, _>>()?;
+        specs.into_iter().for_each(|(name, log_level)| self.apply_spec(name, log_level));
+        Ok(self)
+    }
+
+    fn apply_spec(&mut self, name: Option<&str>, log_level: LevelFilter) {
+        match name {
+            Some(name) => {
+                self.logger(name.to_string(), log_level);
+            }
+            None => {
+                self.root_level(log_level);
+            }
+        }
+    }
+
     #[allow(dead_code)]
     pub fn appenders(&mut self, appenders: impl Iterator<Item = &'static str>) -> &mut Self {
         self.appenders = appenders.collect();
@@ -140,6 +141,31 @@ impl Builder {
     }
 }
 
+fn split_specs(expression: &str) -> impl Iterator<Item = &str> {
+    expression.split(',').map(|x| x.trim()).filter(|x| !x.is_empty())
+}
+
+/// Parses a single `[name=]level` spec, where a missing name refers to the root logger
+fn parse_spec(spec: &str) -> Result<(Option<&str>, LevelFilter), LogError> {
+    let mut parts = spec.split('=');
+    match (parts.next(), parts.next().map(|x| x.trim()), parts.next()) {
+        (Some(part0), None, None) => {
+            // if the single argument is a log-level string or number,
+            // it defines the root level
+            match part0.parse() {
+                Ok(lvl) => Ok((None, lvl)),
+                Err(_) => Ok((Some(part0), LevelFilter::max())),
+            }
+        }
+        (Some(part0), Some(""), None) => Ok((Some(part0), LevelFilter::max())),
+        (Some(part0), Some(part1), None) => match part1.parse() {
+            Ok(lvl) => Ok((Some(part0), lvl)),
+            _ => Err(LogError::ParseLoggerSpecError(part1.to_string())),
+        },
+        _ => Err(LogError::ParseLoggerSpecError(spec.to_string())),
+    }
+}
+
 impl FromStr for Builder {
     type Err = LogError;
 
@@ -147,3 +173,22 @@ impl FromStr for Builder {
         Ok(Self::from_expression(s))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_parse_expression() {
+        let loggers = Builder::new().root_level(LevelFilter::Info).parse_expression("kaspa_core=debug").build();
+        let loggers = Builder::from_loggers(&loggers).try_parse_expression("warn, kaspa_p2p_flows=trace").unwrap().build();
+        assert_eq!(loggers.root_level(), LevelFilter::Warn);
+        assert_eq!(
+            loggers.levels(),
+            vec![("kaspa_core".to_string(), LevelFilter::Debug), ("kaspa_p2p_flows".to_string(), LevelFilter::Trace)]
+        );
+
+        assert!(Builder::from_loggers(&loggers).try_parse_expression("kaspa_core=loud").is_err());
+        assert!(Builder::new().try_parse_expression("a=b=c").is_err());
+    }
+}
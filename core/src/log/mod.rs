@@ -30,7 +30,29 @@ cfg_if::cfg_if! {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn init_logger(log_dir: Option<&str>, filters: &str) {
+pub use logger::LogError;
+
+/// The levels of the root logger and of the subsystem loggers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogLevels {
+    pub root: LevelFilter,
+    /// Subsystem (module path prefix) loggers, sorted by name
+    pub subsystems: Vec<(String, LevelFilter)>,
+}
+
+/// The state required for reconfiguring the global logger at runtime
+#[cfg(not(target_arch = "wasm32"))]
+struct LoggerState {
+    handle: log4rs::Handle,
+    log_dir: Option<String>,
+    loggers: logger::Loggers,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static LOGGER_STATE: std::sync::Mutex<Option<LoggerState>> = std::sync::Mutex::new(None);
+
+#[cfg(not(target_arch = "wasm32"))]
+fn build_config(log_dir: Option<&str>, loggers: &logger::Loggers) -> log4rs::Config {
     use crate::log::appender::AppenderSpec;
     use log4rs::{config::Root, Config};
     use std::iter::once;
@@ -39,16 +61,13 @@ pub fn init_logger(log_dir: Option<&str>, filters: &str) {
     const LOG_FILE_APPENDER: &str = "log_file";
     const ERR_LOG_FILE_APPENDER: &str = "err_log_file";
 
-    let level = LevelFilter::Info;
-    let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
-
     let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None);
     let mut file_appender = log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME));
     let mut err_file_appender =
         log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME));
     let appenders = once(&mut stdout_appender).chain(&mut file_appender).chain(&mut err_file_appender).map(|x| x.appender());
 
-    let config = Config::builder()
+    Config::builder()
         .appenders(appenders)
         .loggers(loggers.items())
         .build(
@@ -56,13 +75,43 @@ pub fn init_logger(log_dir: Option<&str>, filters: &str) {
                 .appenders(once(&stdout_appender).chain(&file_appender).chain(&err_file_appender).map(|x| x.name))
                 .build(loggers.root_level()),
         )
-        .unwrap();
+        .unwrap()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_logger(log_dir: Option<&str>, filters: &str) {
+    let level = LevelFilter::Info;
+    let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
 
-    let _handle = log4rs::init_config(config).unwrap();
+    let handle = log4rs::init_config(build_config(log_dir, &loggers)).unwrap();
+    *LOGGER_STATE.lock().unwrap() = Some(LoggerState { handle, log_dir: log_dir.map(|x| x.to_owned()), loggers });
 
     workflow_log::set_log_level(level);
 }
 
+/// Returns the current log levels, or `None` if the logger was not initialized with [`init_logger`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn log_levels() -> Option<LogLevels> {
+    LOGGER_STATE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|state| LogLevels { root: state.loggers.root_level(), subsystems: state.loggers.levels() })
+}
+
+/// Changes the log levels at runtime. `filters` uses the same syntax as the filters provided to [`init_logger`]
+/// and is applied on top of the current levels, so subsystems which are not mentioned keep their level.
+///
+/// Returns the resulting log levels, or `None` if the logger was not initialized with [`init_logger`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_log_levels(filters: &str) -> Result<Option<LogLevels>, LogError> {
+    let mut state = LOGGER_STATE.lock().unwrap();
+    let Some(state) = state.as_mut() else { return Ok(None) };
+    state.loggers = logger::Builder::from_loggers(&state.loggers).try_parse_expression(filters)?.build();
+    state.handle.set_config(build_config(state.log_dir.as_deref(), &state.loggers));
+    Ok(Some(LogLevels { root: state.loggers.root_level(), subsystems: state.loggers.levels() }))
+}
+
 /// Tries to init the global logger, but does not panic if it was already setup.
 /// Should be used for tests.
 #[cfg(not(target_arch = "wasm32"))]
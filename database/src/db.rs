@@ -15,6 +15,13 @@ pub fn open_db(db_path: PathBuf, create_if_missing: bool, parallelism: usize) ->
     db
 }
 
+/// Loads an existing DB in read-only mode. A read-only DB can be opened while another process holds the DB open
+/// for writing, in which case it reflects the DB state as of opening time.
+pub fn open_db_read_only(db_path: PathBuf) -> Arc<DB> {
+    let opts = rocksdb::Options::default();
+    Arc::new(DB::open_for_read_only(&opts, db_path.to_str().unwrap(), false).unwrap())
+}
+
 /// Deletes an existing DB if it exists
 pub fn delete_db(db_dir: PathBuf) {
     if !db_dir.exists() {
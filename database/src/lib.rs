@@ -16,6 +16,6 @@ pub mod prelude {
     pub use super::item::CachedDbItem;
     pub use super::key::{DbKey, SEP, SEP_SIZE};
     pub use super::writer::{BatchDbWriter, DbWriter, DirectDbWriter, MemoryWriter};
-    pub use db::{delete_db, open_db, DB};
+    pub use db::{delete_db, open_db, open_db_read_only, DB};
     pub use errors::{StoreError, StoreResult, StoreResultEmptyTuple, StoreResultExtensions};
 }
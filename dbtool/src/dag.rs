@@ -0,0 +1,174 @@
+//!
+//! DAG export/import -- a compressed file containing the blocks of a node in topological order, which can be
+//! replayed into a fresh consensus for reproducing issues locally or for building test fixtures from real data.
+//!
+//! The file is a gzip stream starting with a magic prefix followed by a sequence of bincode encoded records:
+//! 1. A header identifying the network and the genesis block
+//! 2. The blocks (excluding genesis) in chunks, terminated by an empty chunk
+//!
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use futures::future::try_join_all;
+use itertools::Itertools;
+use kaspa_consensus::{
+    model::stores::{
+        block_transactions::{BlockTransactionsStoreReader, DbBlockTransactionsStore},
+        ghostdag::{DbGhostdagStore, GhostdagStoreReader},
+        headers::{DbHeadersStore, HeaderStoreReader},
+        relations::{DbRelationsStore, RelationsStoreReader},
+        statuses::{DbStatusesStore, StatusesStoreReader},
+    },
+    processes::ghostdag::ordering::SortableBlock,
+};
+use kaspa_consensus_core::{api::ConsensusApi, block::Block, header::Header, tx::Transaction, BlockHashSet, HashMapCustomHasher};
+use kaspa_database::prelude::DB;
+use kaspa_hashes::Hash;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    io::{BufReader, BufWriter, Read, Write},
+    sync::Arc,
+};
+
+use crate::{ToolResult, CACHE_SIZE};
+
+const DAG_MAGIC: [u8; 8] = *b"KASPADAG";
+
+/// The current DAG file format version
+const DAG_VERSION: u32 = 1;
+
+const BLOCK_CHUNK_SIZE: usize = 1000;
+
+type BlockChunk = Vec<(Arc<Header>, Arc<Vec<Transaction>>)>;
+
+#[derive(Serialize, Deserialize)]
+struct DagFileHeader {
+    version: u32,
+    network: String,
+    genesis: Hash,
+}
+
+/// Writes all blocks with a body in the future of `genesis` to `writer` in topological order. Returns the number of exported blocks
+pub fn export_dag(db: Arc<DB>, network: &str, genesis: Hash, writer: impl Write) -> ToolResult<u64> {
+    let headers_store = DbHeadersStore::new(db.clone(), CACHE_SIZE);
+    let block_transactions_store = DbBlockTransactionsStore::new(db.clone(), CACHE_SIZE);
+    let hashes = topologically_ordered_hashes(db, genesis)?;
+
+    let mut writer = BufWriter::new(GzEncoder::new(writer, Compression::default()));
+    writer.write_all(&DAG_MAGIC)?;
+    bincode::serialize_into(&mut writer, &DagFileHeader { version: DAG_VERSION, network: network.to_owned(), genesis })?;
+    for chunk in hashes.chunks(BLOCK_CHUNK_SIZE) {
+        let blocks = chunk
+            .iter()
+            .map(|&hash| Ok((headers_store.get_header(hash)?, block_transactions_store.get(hash)?)))
+            .collect::<ToolResult<BlockChunk>>()?;
+        bincode::serialize_into(&mut writer, &blocks)?;
+    }
+    bincode::serialize_into(&mut writer, &BlockChunk::new())?;
+    writer.into_inner().map_err(|err| err.into_error())?.finish()?;
+    Ok(hashes.len() as u64)
+}
+
+/// Collects the blocks with a body in the future of `genesis`, sorted by blue work. Since the blue work of a block
+/// is strictly greater than the blue work of each of its parents, this order is topological
+fn topologically_ordered_hashes(db: Arc<DB>, genesis: Hash) -> ToolResult<Vec<Hash>> {
+    let relations_store = DbRelationsStore::new(db.clone(), 0, CACHE_SIZE);
+    let statuses_store = DbStatusesStore::new(db.clone(), CACHE_SIZE);
+    let ghostdag_store = DbGhostdagStore::new(db, 0, CACHE_SIZE);
+
+    let mut queue: VecDeque<Hash> = std::iter::once(genesis).collect();
+    let mut visited = BlockHashSet::new();
+    let mut blocks = Vec::new();
+    while let Some(current) = queue.pop_front() {
+        for &child in relations_store.get_children(current)?.iter() {
+            // Children of blocks without a body cannot have a body either
+            if visited.insert(child) && statuses_store.get(child)?.has_block_body() {
+                queue.push_back(child);
+                blocks.push(SortableBlock::new(child, ghostdag_store.get_blue_work(child)?));
+            }
+        }
+    }
+    blocks.sort();
+    Ok(blocks.into_iter().map(|block| block.hash).collect())
+}
+
+/// Inserts the blocks read from `reader` into `consensus` through the regular block processing pipeline.
+/// Returns the number of imported blocks
+pub async fn import_dag(consensus: &dyn ConsensusApi, network: &str, genesis: Hash, reader: impl Read) -> ToolResult<u64> {
+    let mut reader = BufReader::new(GzDecoder::new(reader));
+    read_header(&mut reader, network, genesis)?;
+
+    let mut count = 0;
+    let mut prev_futures = Vec::new();
+    loop {
+        let chunk: BlockChunk = bincode::deserialize_from(&mut reader)?;
+        if chunk.is_empty() {
+            break;
+        }
+        count += chunk.len() as u64;
+        // Submit the current chunk before waiting for the previous one in order to keep the pipeline busy
+        let futures = chunk
+            .into_iter()
+            .map(|(header, transactions)| consensus.validate_and_insert_block(Block::from_arcs(header, transactions)))
+            .collect_vec();
+        try_join_all(std::mem::replace(&mut prev_futures, futures)).await?;
+    }
+    try_join_all(prev_futures).await?;
+    Ok(count)
+}
+
+fn read_header(reader: &mut impl Read, network: &str, genesis: Hash) -> ToolResult<DagFileHeader> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if magic != DAG_MAGIC {
+        return Err("The file is not a DAG export file".into());
+    }
+    let header: DagFileHeader = bincode::deserialize_from(reader)?;
+    if header.version != DAG_VERSION {
+        return Err(format!("Unsupported DAG file version {}, expected version {}", header.version, DAG_VERSION).into());
+    }
+    if header.network != network || header.genesis != genesis {
+        return Err(format!("DAG file network mismatch - local: {}, file: {}", network, header.network).into());
+    }
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+    use kaspa_database::utils::create_temp_db;
+
+    #[test]
+    fn test_export_import_dag() {
+        let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().build();
+        let network = config.network_name();
+        let genesis = config.genesis.hash;
+
+        let (_lifetime, db) = create_temp_db();
+        let (dummy_notification_sender, _) = async_channel::unbounded();
+        let consensus = TestConsensus::with_db(db.clone(), &config, dummy_notification_sender);
+        let wait_handles = consensus.init();
+        // Build a small DAG with some merging. Blocks are built with a coinbase transaction so that they have a body
+        block_on(async {
+            for (hash, parents) in [(1, vec![0]), (2, vec![0]), (3, vec![1, 2]), (4, vec![3]), (5, vec![2]), (6, vec![4, 5])] {
+                let parents = parents.into_iter().map(|p| if p == 0 { genesis } else { p.into() }).collect();
+                let block = consensus.build_block_with_parents_and_transactions(hash.into(), parents, vec![]);
+                consensus.validate_and_insert_block(block.to_immutable()).await.unwrap();
+            }
+        });
+        consensus.shutdown(wait_handles);
+
+        let mut file = Vec::new();
+        assert_eq!(export_dag(db, &network, genesis, &mut file).unwrap(), 6);
+
+        let consensus2 = TestConsensus::new(&config);
+        let wait_handles = consensus2.init();
+        assert!(block_on(import_dag(consensus2.consensus_clone().as_ref(), "kaspa-other", genesis, file.as_slice())).is_err());
+        assert_eq!(block_on(import_dag(consensus2.consensus_clone().as_ref(), &network, genesis, file.as_slice())).unwrap(), 6);
+        assert_eq!(BlockHashSet::from_iter(consensus2.get_tips()), BlockHashSet::from_iter(consensus.get_tips()));
+        assert_eq!(consensus2.get_virtual_parents(), consensus.get_virtual_parents());
+        consensus2.shutdown(wait_handles);
+    }
+}
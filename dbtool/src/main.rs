@@ -0,0 +1,459 @@
+use async_channel::unbounded;
+use clap::{Parser, Subcommand};
+use futures::executor::block_on;
+use kaspa_consensus::{
+    config::Config,
+    consensus::factory::{Factory as ConsensusFactory, MultiConsensusManagementStore},
+    model::stores::{
+        block_transactions::{BlockTransactionsStoreReader, DbBlockTransactionsStore},
+        ghostdag::{DbGhostdagStore, GhostdagStoreReader},
+        headers::{DbHeadersStore, HeaderStoreReader},
+        headers_selected_tip::{DbHeadersSelectedTipStore, HeadersSelectedTipStoreReader},
+        pruning::{DbPruningStore, PruningStoreReader},
+        pruning_utxoset::PruningUtxosetStores,
+        statuses::{DbStatusesStore, StatusesStoreReader},
+        tips::{DbTipsStore, TipsStoreReader},
+        utxo_set::DbUtxoSetStore,
+        virtual_state::{VirtualStateStoreReader, VirtualStores},
+    },
+    params::Params,
+};
+use kaspa_consensus_core::{muhash::MuHashExtensions, networktype::NetworkType, BlockLevel};
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensusmanager::ConsensusFactory as _;
+use kaspa_database::prelude::{open_db, open_db_read_only, DB};
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use std::{
+    error::Error,
+    fs::{self, File},
+    ops::Deref,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::Arc,
+    time::Instant,
+};
+
+mod dag;
+
+const DEFAULT_DATA_DIR: &str = "datadir";
+const CONSENSUS_DB: &str = "consensus";
+const UTXOINDEX_DB: &str = "utxoindex";
+const META_DB: &str = "meta";
+
+/// Stores are used for point lookups or a single sequential scan, so there is no point in large caches
+const CACHE_SIZE: u64 = 16;
+
+type ToolResult<T> = Result<T, Box<dyn Error>>;
+
+/// Kaspa Database Inspection Tool
+///
+/// Inspects and maintains the databases of a kaspad node. The node must not be running while
+/// `compact` or `import-dag` are executed, other commands open the databases in read-only mode.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Application directory of the node (defaults to the kaspad application directory)
+    #[arg(short = 'b', long = "appdir")]
+    appdir: Option<String>,
+
+    /// The network whose databases should be inspected {mainnet, testnet, devnet, simnet}
+    #[arg(long, default_value = "mainnet")]
+    network: NetworkType,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the multi-consensus metadata and the list of consensus instances
+    Metadata,
+    /// Print the header and status of a block
+    Header { hash: Hash },
+    /// Print the header and transactions of a block
+    Block { hash: Hash },
+    /// Print the GHOSTDAG data of a block
+    Ghostdag {
+        hash: Hash,
+        /// The block level of the GHOSTDAG data (0 for the regular DAG)
+        #[arg(long, default_value_t = 0)]
+        level: BlockLevel,
+    },
+    /// Print the DAG tips, the virtual parents, the headers selected tip and the pruning point
+    Tips,
+    /// Count the entries of the virtual and pruning point UTXO sets
+    CountUtxos,
+    /// Verify the virtual UTXO set against the virtual multiset and the pruning point UTXO set against the pruning point UTXO commitment
+    VerifyUtxos,
+    /// Run a full manual compaction of all databases
+    Compact,
+    /// Export the blocks of the DAG in topological order to a compressed file. Requires the full block history,
+    /// i.e., an archival node or a node which did not prune yet
+    ExportDag { file: PathBuf },
+    /// Import the blocks of a DAG export file into a new node through the regular block processing pipeline
+    ImportDag {
+        file: PathBuf,
+        /// Import as an archival node, keeping the full block history of the imported DAG
+        #[arg(long)]
+        archival: bool,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(err) = run(args) {
+        eprintln!("{err}");
+        exit(1);
+    }
+}
+
+fn run(args: Args) -> ToolResult<()> {
+    let db_dir = db_dir(&args);
+    if let Command::ImportDag { file, archival } = &args.command {
+        let mut config = Config::new(Params::from(args.network));
+        config.is_archival = *archival;
+        return import_dag(&db_dir, &config, file).map(|_| ());
+    }
+
+    let meta_db_dir = db_dir.join(META_DB);
+    if !meta_db_dir.exists() {
+        return Err(format!("Meta database not found at {}", meta_db_dir.display()).into());
+    }
+
+    if let Command::Compact = args.command {
+        return compact(&db_dir);
+    }
+
+    let management_store = MultiConsensusManagementStore::new_read_only(open_db_read_only(meta_db_dir));
+    if let Command::Metadata = args.command {
+        return print_metadata(&management_store);
+    }
+
+    let entry = management_store.current_consensus_entry()?.ok_or("No active consensus instance was found")?;
+    let db = open_db_read_only(db_dir.join(CONSENSUS_DB).join(entry.directory_name()));
+    match args.command {
+        Command::Header { hash } => print_header(db, hash),
+        Command::Block { hash } => print_block(db, hash),
+        Command::Ghostdag { hash, level } => print_ghostdag(db, hash, level),
+        Command::Tips => print_tips(db),
+        Command::CountUtxos => count_utxos(db),
+        Command::VerifyUtxos => verify_utxos(db),
+        Command::ExportDag { file } => export_dag(db, args.network, &file, management_store.metadata()?.is_archival_node()),
+        Command::Metadata | Command::Compact | Command::ImportDag { .. } => unreachable!(),
+    }
+}
+
+fn get_home_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    return dirs::data_local_dir().unwrap();
+    #[cfg(not(target_os = "windows"))]
+    return dirs::home_dir().unwrap();
+}
+
+fn get_app_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    return get_home_dir().join("rusty-kaspa");
+    #[cfg(not(target_os = "windows"))]
+    return get_home_dir().join(".rusty-kaspa");
+}
+
+/// Resolves the data directory the same way kaspad does
+fn db_dir(args: &Args) -> PathBuf {
+    let app_dir = match &args.appdir {
+        Some(app_dir) if !app_dir.is_empty() => PathBuf::from(app_dir.replace('~', get_home_dir().to_str().unwrap())),
+        _ => get_app_dir(),
+    };
+    app_dir.join(Params::from(args.network).network_name()).join(DEFAULT_DATA_DIR)
+}
+
+fn print_metadata(management_store: &MultiConsensusManagementStore) -> ToolResult<()> {
+    let metadata = management_store.metadata()?;
+    println!("Current consensus key: {:?}", metadata.current_consensus_key());
+    println!("Staging consensus key: {:?}", metadata.staging_consensus_key());
+    println!("Max key used: {}", metadata.max_key_used());
+    println!("Archival node: {}", metadata.is_archival_node());
+    println!("Consensus entries:");
+    for entry in management_store.consensus_entries() {
+        println!("  key: {}, directory: {}, created: {}", entry.key(), entry.directory_name(), entry.creation_timestamp());
+    }
+    Ok(())
+}
+
+fn print_header(db: Arc<DB>, hash: Hash) -> ToolResult<()> {
+    let headers_store = DbHeadersStore::new(db.clone(), CACHE_SIZE);
+    let statuses_store = DbStatusesStore::new(db, CACHE_SIZE);
+    let header = headers_store.get_header_with_block_level(hash)?;
+    println!("{:#?}", header.header);
+    println!("Block level: {}", header.block_level);
+    println!("Status: {:?}", statuses_store.get(hash)?);
+    Ok(())
+}
+
+fn print_block(db: Arc<DB>, hash: Hash) -> ToolResult<()> {
+    let block_transactions_store = DbBlockTransactionsStore::new(db.clone(), CACHE_SIZE);
+    print_header(db, hash)?;
+    let transactions = block_transactions_store.get(hash)?;
+    println!("Transactions ({}):", transactions.len());
+    for tx in transactions.iter() {
+        println!("  {} (inputs: {}, outputs: {})", tx.id(), tx.inputs.len(), tx.outputs.len());
+    }
+    Ok(())
+}
+
+fn print_ghostdag(db: Arc<DB>, hash: Hash, level: BlockLevel) -> ToolResult<()> {
+    let ghostdag_store = DbGhostdagStore::new(db, level, CACHE_SIZE);
+    let data = ghostdag_store.get_data(hash)?;
+    println!("Blue score: {}", data.blue_score);
+    println!("Blue work: {}", data.blue_work);
+    println!("Selected parent: {}", data.selected_parent);
+    println!("Mergeset blues ({}):", data.mergeset_blues.len());
+    for blue in data.mergeset_blues.iter() {
+        println!("  {} (anticone size: {:?})", blue, data.blues_anticone_sizes.get(blue));
+    }
+    println!("Mergeset reds ({}):", data.mergeset_reds.len());
+    for red in data.mergeset_reds.iter() {
+        println!("  {}", red);
+    }
+    Ok(())
+}
+
+fn print_tips(db: Arc<DB>) -> ToolResult<()> {
+    let tips = DbTipsStore::new(db.clone()).get()?;
+    let virtual_state = VirtualStores::new(db.clone(), CACHE_SIZE).state.get()?;
+    let headers_selected_tip = DbHeadersSelectedTipStore::new(db.clone()).get()?;
+    let pruning_info = DbPruningStore::new(db).get()?;
+
+    println!("Tips ({}):", tips.len());
+    for tip in tips.iter() {
+        println!("  {}", tip);
+    }
+    println!("Virtual parents ({}):", virtual_state.parents.len());
+    for parent in virtual_state.parents.iter() {
+        println!("  {}", parent);
+    }
+    println!("Virtual DAA score: {}", virtual_state.daa_score);
+    println!("Headers selected tip: {} (blue work: {})", headers_selected_tip.hash, headers_selected_tip.blue_work);
+    println!("Pruning point: {} (index: {})", pruning_info.pruning_point, pruning_info.index);
+    println!("Pruning point candidate: {}", pruning_info.candidate);
+    Ok(())
+}
+
+fn count_utxos(db: Arc<DB>) -> ToolResult<()> {
+    let virtual_utxo_set = VirtualStores::new(db.clone(), CACHE_SIZE).utxo_set;
+    let pruning_utxo_set = PruningUtxosetStores::new(db, CACHE_SIZE).utxo_set;
+    println!("Virtual UTXO set entries: {}", count_entries(&virtual_utxo_set)?);
+    println!("Pruning point UTXO set entries: {}", count_entries(&pruning_utxo_set)?);
+    Ok(())
+}
+
+fn count_entries(utxo_set: &DbUtxoSetStore) -> ToolResult<u64> {
+    let mut count = 0;
+    for item in utxo_set.iterator() {
+        item?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn verify_utxos(db: Arc<DB>) -> ToolResult<()> {
+    let virtual_stores = VirtualStores::new(db.clone(), CACHE_SIZE);
+    let virtual_state = virtual_stores.state.get()?;
+    let (count, mut multiset) = utxo_set_multiset(&virtual_stores.utxo_set)?;
+    let virtual_ok = multiset.finalize() == virtual_state.multiset.clone().finalize();
+    println!("Virtual UTXO set ({} entries): {}", count, if virtual_ok { "OK" } else { "MISMATCH with the virtual multiset" });
+
+    let pruning_utxoset_stores = PruningUtxosetStores::new(db.clone(), CACHE_SIZE);
+    let position = pruning_utxoset_stores.utxoset_position()?;
+    let commitment = DbHeadersStore::new(db, CACHE_SIZE).get_header(position)?.utxo_commitment;
+    let (count, mut multiset) = utxo_set_multiset(&pruning_utxoset_stores.utxo_set)?;
+    let pruning_ok = multiset.finalize() == commitment;
+    println!(
+        "Pruning point UTXO set at {} ({} entries): {}",
+        position,
+        count,
+        if pruning_ok { "OK" } else { "MISMATCH with the header UTXO commitment" }
+    );
+
+    if !virtual_ok || !pruning_ok {
+        return Err("UTXO set verification failed".into());
+    }
+    Ok(())
+}
+
+fn utxo_set_multiset(utxo_set: &DbUtxoSetStore) -> ToolResult<(u64, MuHash)> {
+    let mut multiset = MuHash::new();
+    let mut count = 0;
+    for item in utxo_set.iterator() {
+        let (outpoint, entry) = item?;
+        multiset.add_utxo(&outpoint, &entry);
+        count += 1;
+    }
+    Ok((count, multiset))
+}
+
+fn compact(db_dir: &Path) -> ToolResult<()> {
+    let mut db_paths = vec![db_dir.join(META_DB)];
+    if db_dir.join(UTXOINDEX_DB).exists() {
+        db_paths.push(db_dir.join(UTXOINDEX_DB));
+    }
+    for dir_entry in std::fs::read_dir(db_dir.join(CONSENSUS_DB))? {
+        let path = dir_entry?.path();
+        if path.is_dir() {
+            db_paths.push(path);
+        }
+    }
+    for path in db_paths {
+        println!("Compacting {}...", path.display());
+        let start = Instant::now();
+        let db = open_db(path, false, 1);
+        db.compact_range::<&[u8], &[u8]>(None, None);
+        println!("Compacted in {:.1} seconds", start.elapsed().as_secs_f64());
+    }
+    Ok(())
+}
+
+fn export_dag(db: Arc<DB>, network: NetworkType, file: &Path, is_archival: bool) -> ToolResult<()> {
+    let params = Params::from(network);
+    let pruning_point = DbPruningStore::new(db.clone()).get()?.pruning_point;
+    if pruning_point != params.genesis.hash && !is_archival {
+        return Err("The block history below the pruning point was pruned, only archival nodes can export the full DAG".into());
+    }
+
+    let start = Instant::now();
+    let result = dag::export_dag(db, &params.network_name(), params.genesis.hash, File::create(file)?);
+    match result {
+        Ok(count) => {
+            println!("Exported {} blocks to {} in {:.1} seconds", count, file.display(), start.elapsed().as_secs_f64());
+            Ok(())
+        }
+        Err(err) => {
+            // Do not leave a partial export behind
+            let _ = fs::remove_file(file);
+            Err(err)
+        }
+    }
+}
+
+fn import_dag(db_dir: &Path, config: &Config, file: &Path) -> ToolResult<u64> {
+    let genesis = config.genesis.hash;
+    let network_name = config.network_name();
+    let reader = File::open(file)?;
+
+    let consensus_db_dir = db_dir.join(CONSENSUS_DB);
+    let meta_db_dir = db_dir.join(META_DB);
+    fs::create_dir_all(&consensus_db_dir)?;
+    fs::create_dir_all(&meta_db_dir)?;
+
+    let (dummy_notification_sender, _) = unbounded();
+    let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
+    let factory = ConsensusFactory::new(
+        open_db(meta_db_dir, true, 1),
+        config,
+        consensus_db_dir,
+        num_cpus::get(),
+        notification_root,
+        Default::default(),
+    );
+    let (consensus, ctl) = factory.new_active_consensus();
+    let handles = ctl.start();
+    let start = Instant::now();
+    let result = block_on(async {
+        let session = consensus.session().await;
+        if session.get_headers_selected_tip() != genesis {
+            return Err("The node already has blocks, DAG files can only be imported into a new node".into());
+        }
+        dag::import_dag(session.deref(), &network_name, genesis, reader).await
+    });
+    ctl.stop();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let count = result?;
+    println!("Imported {} blocks in {:.1} seconds", count, start.elapsed().as_secs_f64());
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::{
+        config::ConfigBuilder, consensus::test_consensus::TestConsensus, model::stores::tips::DbTipsStore, params::MAINNET_PARAMS,
+    };
+    use kaspa_consensus_core::{api::ConsensusApi, BlockHashSet};
+    use kaspa_database::utils::{create_temp_db, get_kaspa_tempdir};
+
+    #[test]
+    fn test_parse_args() {
+        let hash = Hash::from_u64_word(1);
+        let args = Args::try_parse_from([
+            "dbtool",
+            "-b",
+            "/tmp/kaspa",
+            "--network",
+            "testnet",
+            "ghostdag",
+            &hash.to_string(),
+            "--level",
+            "2",
+        ])
+        .unwrap();
+        assert_eq!(args.appdir.as_deref(), Some("/tmp/kaspa"));
+        assert_eq!(args.network, NetworkType::Testnet);
+        assert!(matches!(args.command, Command::Ghostdag { hash: h, level: 2 } if h == hash));
+
+        let args = Args::try_parse_from(["dbtool", "--appdir", "/tmp/kaspa", "import-dag", "dag.bin", "--archival"]).unwrap();
+        assert_eq!(args.network, NetworkType::Mainnet);
+        assert!(matches!(&args.command, Command::ImportDag { file, archival: true } if file == Path::new("dag.bin")));
+        assert_eq!(db_dir(&args), Path::new("/tmp/kaspa").join("kaspa-mainnet").join(DEFAULT_DATA_DIR));
+
+        assert!(matches!(Args::try_parse_from(["dbtool", "count-utxos"]).unwrap().command, Command::CountUtxos));
+        assert!(Args::try_parse_from(["dbtool"]).is_err(), "a command is required");
+        assert!(Args::try_parse_from(["dbtool", "--network", "othernet", "tips"]).is_err());
+        assert!(Args::try_parse_from(["dbtool", "header", "not-a-hash"]).is_err());
+        assert!(Args::try_parse_from(["dbtool", "export-dag"]).is_err(), "export-dag requires a file");
+    }
+
+    #[test]
+    fn test_maintenance_commands() {
+        let config = ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().set_archival().build();
+        let tempdir = get_kaspa_tempdir();
+        let args =
+            |command| Args { appdir: Some(tempdir.path().to_str().unwrap().to_owned()), network: NetworkType::Mainnet, command };
+        let db_dir = db_dir(&args(Command::Tips));
+
+        // Commands other than import-dag require an existing node
+        assert!(run(args(Command::Compact)).is_err());
+
+        // Export a small DAG with some merging
+        let (_lifetime, db) = create_temp_db();
+        let (dummy_notification_sender, _) = unbounded();
+        let consensus = TestConsensus::with_db(db.clone(), &config, dummy_notification_sender);
+        let wait_handles = consensus.init();
+        block_on(async {
+            for (hash, parents) in [(1, vec![0]), (2, vec![0]), (3, vec![1, 2]), (4, vec![3]), (5, vec![2])] {
+                let parents = parents.into_iter().map(|p| if p == 0 { config.genesis.hash } else { p.into() }).collect();
+                let block = consensus.build_block_with_parents_and_transactions(hash.into(), parents, vec![]);
+                consensus.validate_and_insert_block(block.to_immutable()).await.unwrap();
+            }
+        });
+        let tips = BlockHashSet::from_iter(consensus.get_tips());
+        consensus.shutdown(wait_handles);
+        let file = tempdir.path().join("dag.bin");
+        dag::export_dag(db, &config.network_name(), config.genesis.hash, File::create(&file).unwrap()).unwrap();
+
+        // Import it into a new node, which can then be compacted and inspected
+        assert_eq!(import_dag(&db_dir, &config, &file).unwrap(), 5);
+        assert!(import_dag(&db_dir, &config, &file).is_err(), "only a new node can import a DAG");
+        run(args(Command::Compact)).unwrap();
+        run(args(Command::Tips)).unwrap();
+        run(args(Command::CountUtxos)).unwrap();
+
+        let management_store = MultiConsensusManagementStore::new_read_only(open_db_read_only(db_dir.join(META_DB)));
+        assert!(management_store.metadata().unwrap().is_archival_node());
+        let entry = management_store.current_consensus_entry().unwrap().unwrap();
+        let consensus_db = open_db_read_only(db_dir.join(CONSENSUS_DB).join(entry.directory_name()));
+        assert_eq!(*DbTipsStore::new(consensus_db).get().unwrap(), tips);
+    }
+}
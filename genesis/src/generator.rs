@@ -0,0 +1,102 @@
+use kaspa_consensus::{params::Params, processes::coinbase::CoinbaseManager};
+use kaspa_consensus_core::{
+    coinbase::{CoinbaseData, MinerData},
+    config::genesis::GenesisBlock,
+    header::Header,
+    merkle::calc_hash_merkle_root,
+    muhash::MuHashExtensions,
+    tx::{PopulatedTransaction, ScriptPublicKey},
+};
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_pow::State;
+use kaspa_txscript::opcodes::codes::OpFalse;
+use rayon::prelude::*;
+
+/// The subsidy written to the coinbase payload of the genesis blocks of all predefined networks
+pub const DEFAULT_GENESIS_SUBSIDY: u64 = 100_000_000;
+
+/// The header fields and coinbase payload of a genesis block to be mined
+pub struct GenesisTemplate {
+    pub timestamp: u64,
+    pub bits: u32,
+    pub daa_score: u64,
+    /// The UTXO commitment of a genesis checkpointing an existing UTXO set. If `None`, the commitment
+    /// of the UTXO set created by the genesis coinbase transaction is used
+    pub utxo_commitment: Option<Hash>,
+    pub coinbase_payload: Vec<u8>,
+}
+
+/// Builds a genesis coinbase payload paying `subsidy` to an unspendable (OP-FALSE) script, followed by `extra_data`
+pub fn genesis_coinbase_payload(params: &Params, subsidy: u64, extra_data: &[u8]) -> Vec<u8> {
+    let coinbase_manager = CoinbaseManager::new(
+        params.coinbase_payload_script_public_key_max_len,
+        params.max_coinbase_payload_len,
+        params.deflationary_phase_daa_score,
+        params.pre_deflationary_phase_base_subsidy,
+    );
+    let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![OpFalse]), extra_data);
+    coinbase_manager.serialize_coinbase_payload(&CoinbaseData { blue_score: 0, subsidy, miner_data }).unwrap()
+}
+
+/// Builds the genesis block of `template`: computes the hash merkle root and the UTXO commitment and mines the
+/// lowest nonce starting at `start_nonce` which satisfies the proof of work. Returns `None` if no such nonce exists.
+///
+/// The coinbase payload is leaked since [`GenesisBlock`] only holds a static reference.
+pub fn generate_genesis(template: GenesisTemplate, start_nonce: u64) -> Option<GenesisBlock> {
+    let mut genesis = GenesisBlock {
+        hash: Default::default(),
+        version: 0,
+        hash_merkle_root: Default::default(),
+        utxo_commitment: Default::default(),
+        timestamp: template.timestamp,
+        bits: template.bits,
+        nonce: 0,
+        daa_score: template.daa_score,
+        coinbase_payload: Box::leak(template.coinbase_payload.into_boxed_slice()),
+    };
+
+    let transactions = genesis.build_genesis_transactions();
+    genesis.hash_merkle_root = calc_hash_merkle_root(transactions.iter());
+    genesis.utxo_commitment = template.utxo_commitment.unwrap_or_else(|| {
+        let mut multiset = MuHash::new();
+        for tx in transactions.iter() {
+            multiset.add_transaction(&PopulatedTransaction::new(tx, vec![]), genesis.daa_score);
+        }
+        multiset.finalize()
+    });
+
+    // The proof of work state does not depend on the header nonce
+    let state = State::new(&Header::from(&genesis));
+    genesis.nonce = (start_nonce..=u64::MAX).into_par_iter().find_first(|&nonce| state.check_pow(nonce).0)?;
+    genesis.hash = Header::from(&genesis).hash;
+    Some(genesis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::params::SIMNET_PARAMS;
+    use kaspa_consensus_core::config::genesis::SIMNET_GENESIS;
+    use kaspa_muhash::EMPTY_MUHASH;
+
+    #[test]
+    fn test_generate_simnet_genesis() {
+        let coinbase_payload = genesis_coinbase_payload(&SIMNET_PARAMS, DEFAULT_GENESIS_SUBSIDY, b"kaspa-simnet");
+        assert_eq!(coinbase_payload, SIMNET_GENESIS.coinbase_payload);
+
+        let template = GenesisTemplate {
+            timestamp: SIMNET_GENESIS.timestamp,
+            bits: SIMNET_GENESIS.bits,
+            daa_score: SIMNET_GENESIS.daa_score,
+            utxo_commitment: None,
+            coinbase_payload,
+        };
+        let genesis = generate_genesis(template, 0).unwrap();
+        assert_eq!(genesis.nonce, SIMNET_GENESIS.nonce);
+        assert_eq!(genesis.hash_merkle_root, SIMNET_GENESIS.hash_merkle_root);
+        assert_eq!(genesis.utxo_commitment, EMPTY_MUHASH);
+        assert_eq!(genesis.hash, SIMNET_GENESIS.hash);
+        assert!(State::new(&Header::from(&genesis)).check_pow(genesis.nonce).0);
+    }
+}
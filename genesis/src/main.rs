@@ -0,0 +1,127 @@
+use clap::{Parser, ValueEnum};
+use generator::{generate_genesis, genesis_coinbase_payload, GenesisTemplate, DEFAULT_GENESIS_SUBSIDY};
+use kaspa_consensus::params::Params;
+use kaspa_consensus_core::networktype::NetworkType;
+use kaspa_hashes::Hash;
+use std::{
+    error::Error,
+    num::ParseIntError,
+    process::exit,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+mod generator;
+mod output;
+
+type ToolResult<T> = Result<T, Box<dyn Error>>;
+
+/// Kaspa Genesis Block Generator
+///
+/// Builds the genesis block of a new network: serializes the coinbase payload, computes the hash merkle root and
+/// the UTXO commitment and mines a nonce satisfying the proof of work of the chosen difficulty bits. The genesis is
+/// printed as the `[genesis]` table of a custom network file, or as a Rust `GenesisBlock` constant.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The network the new network is based on {testnet, devnet, simnet}
+    #[arg(long, default_value = "devnet")]
+    network: NetworkType,
+
+    /// Suffix of the new network. If specified, the TOML output is a complete network file
+    #[arg(long)]
+    suffix: Option<u32>,
+
+    /// Genesis timestamp in milliseconds (defaults to the current time)
+    #[arg(long)]
+    timestamp: Option<u64>,
+
+    /// Compact difficulty target bits, in decimal or 0x-prefixed hex (defaults to the bits of the base network genesis)
+    #[arg(long, value_parser = parse_bits)]
+    bits: Option<u32>,
+
+    /// DAA score of the genesis
+    #[arg(long, default_value_t = 0)]
+    daa_score: u64,
+
+    /// Subsidy written to the coinbase payload
+    #[arg(long, default_value_t = DEFAULT_GENESIS_SUBSIDY, conflicts_with = "payload")]
+    subsidy: u64,
+
+    /// Extra data of the coinbase payload (defaults to the network name)
+    #[arg(long, conflicts_with = "payload")]
+    message: Option<String>,
+
+    /// Hex encoded coinbase payload, used as is instead of the payload built from --subsidy and --message
+    #[arg(long)]
+    payload: Option<String>,
+
+    /// UTXO commitment of a genesis checkpointing an existing UTXO set (defaults to the commitment of the genesis UTXO set)
+    #[arg(long)]
+    utxo_commitment: Option<Hash>,
+
+    /// Nonce from which mining starts
+    #[arg(long, default_value_t = 0)]
+    start_nonce: u64,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Toml)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    /// The `[genesis]` table of a custom network file
+    Toml,
+    /// A `GenesisBlock` constant
+    Rust,
+}
+
+fn parse_bits(bits: &str) -> Result<u32, ParseIntError> {
+    match bits.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => bits.parse(),
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(err) = run(args) {
+        eprintln!("{err}");
+        exit(1);
+    }
+}
+
+fn run(args: Args) -> ToolResult<()> {
+    let params = Params::from(args.network);
+    let network_name = params.net.name(args.suffix.or(params.net_suffix));
+
+    let coinbase_payload = match args.payload {
+        Some(payload) => {
+            let mut bytes = vec![0u8; payload.len() / 2];
+            faster_hex::hex_decode(payload.as_bytes(), &mut bytes).map_err(|err| format!("Invalid coinbase payload: {err}"))?;
+            bytes
+        }
+        None => genesis_coinbase_payload(&params, args.subsidy, args.message.as_deref().unwrap_or(&network_name).as_bytes()),
+    };
+    let template = GenesisTemplate {
+        timestamp: args.timestamp.unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64),
+        bits: args.bits.unwrap_or(params.genesis.bits),
+        daa_score: args.daa_score,
+        utxo_commitment: args.utxo_commitment,
+        coinbase_payload,
+    };
+
+    eprintln!("Mining the {} genesis with bits {:#x}...", network_name, template.bits);
+    let start = Instant::now();
+    let genesis = generate_genesis(template, args.start_nonce).ok_or("No nonce satisfies the proof of work")?;
+    eprintln!("Found nonce {} in {:.2?}", genesis.nonce, start.elapsed());
+
+    match args.format {
+        Format::Toml => print!("{}", output::to_toml(&genesis, args.suffix.map(|suffix| (args.network, suffix)))),
+        Format::Rust => {
+            let name = format!("{}_GENESIS", network_name.trim_start_matches("kaspa-").replace('-', "_").to_uppercase());
+            print!("{}", output::to_rust(&genesis, &name))
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use kaspa_consensus_core::{config::genesis::GenesisBlock, networktype::NetworkType};
+use kaspa_hashes::Hash;
+use kaspa_muhash::EMPTY_MUHASH;
+use std::fmt::Write;
+
+/// Formats the genesis as the `[genesis]` table of a custom network file. If `network` is specified,
+/// the network type and suffix are written as well, so that the output is a complete network file.
+pub fn to_toml(genesis: &GenesisBlock, network: Option<(NetworkType, u32)>) -> String {
+    let mut output = String::new();
+    if let Some((network_type, suffix)) = network {
+        writeln!(output, "network = \"{network_type}\"").unwrap();
+        writeln!(output, "suffix = {suffix}\n").unwrap();
+    }
+    writeln!(output, "[genesis]").unwrap();
+    writeln!(output, "hash = \"{}\"", genesis.hash).unwrap();
+    writeln!(output, "version = {}", genesis.version).unwrap();
+    writeln!(output, "hash_merkle_root = \"{}\"", genesis.hash_merkle_root).unwrap();
+    writeln!(output, "utxo_commitment = \"{}\"", genesis.utxo_commitment).unwrap();
+    writeln!(output, "timestamp = {}", genesis.timestamp).unwrap();
+    writeln!(output, "bits = {:#x}", genesis.bits).unwrap();
+    writeln!(output, "nonce = {}", genesis.nonce).unwrap();
+    writeln!(output, "daa_score = {}", genesis.daa_score).unwrap();
+    writeln!(output, "coinbase_payload = \"{}\"", faster_hex::hex_string(genesis.coinbase_payload)).unwrap();
+    output
+}
+
+/// Formats the genesis as a Rust `GenesisBlock` constant named `name`, in the style of `consensus/core/src/config/genesis.rs`
+pub fn to_rust(genesis: &GenesisBlock, name: &str) -> String {
+    let mut output = String::new();
+    writeln!(output, "pub const {name}: GenesisBlock = GenesisBlock {{").unwrap();
+    writeln!(output, "    hash: {},", rust_hash(genesis.hash)).unwrap();
+    writeln!(output, "    version: {},", genesis.version).unwrap();
+    writeln!(output, "    hash_merkle_root: {},", rust_hash(genesis.hash_merkle_root)).unwrap();
+    if genesis.utxo_commitment == EMPTY_MUHASH {
+        writeln!(output, "    utxo_commitment: EMPTY_MUHASH,").unwrap();
+    } else {
+        writeln!(output, "    utxo_commitment: {},", rust_hash(genesis.utxo_commitment)).unwrap();
+    }
+    writeln!(output, "    timestamp: {:#x},", genesis.timestamp).unwrap();
+    writeln!(output, "    bits: {:#x},", genesis.bits).unwrap();
+    writeln!(output, "    nonce: {:#x},", genesis.nonce).unwrap();
+    writeln!(output, "    daa_score: {},", genesis.daa_score).unwrap();
+    writeln!(output, "    #[rustfmt::skip]").unwrap();
+    writeln!(output, "    coinbase_payload: &[").unwrap();
+    for chunk in genesis.coinbase_payload.chunks(8) {
+        writeln!(output, "        {}", rust_bytes(chunk)).unwrap();
+    }
+    writeln!(output, "    ],").unwrap();
+    writeln!(output, "}};").unwrap();
+    output
+}
+
+fn rust_hash(hash: Hash) -> String {
+    let lines = hash.as_bytes().chunks(21).map(|chunk| format!("        {}\n", rust_bytes(chunk))).collect::<String>();
+    format!("Hash::from_bytes([\n{lines}    ])")
+}
+
+fn rust_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:#04x},")).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::config::{genesis::DEVNET_GENESIS, network::NetworkDefinition};
+    use std::path::Path;
+
+    #[test]
+    fn test_toml_network_file() {
+        let toml = to_toml(&DEVNET_GENESIS, Some((NetworkType::Devnet, 3)));
+        let params = NetworkDefinition::parse(Path::new("devnet-3.toml"), &toml).unwrap().into_params().unwrap();
+        assert_eq!(params.network_name(), "kaspa-devnet-3");
+        assert_eq!(params.genesis.hash, DEVNET_GENESIS.hash);
+        assert_eq!(params.genesis.hash_merkle_root, DEVNET_GENESIS.hash_merkle_root);
+        assert_eq!(params.genesis.bits, DEVNET_GENESIS.bits);
+        assert_eq!(params.genesis.coinbase_payload, DEVNET_GENESIS.coinbase_payload);
+    }
+
+    #[test]
+    fn test_rust_const() {
+        let rust = to_rust(&DEVNET_GENESIS, "DEVNET_GENESIS");
+        assert!(rust.starts_with(
+            "pub const DEVNET_GENESIS: GenesisBlock = GenesisBlock {\n    hash: Hash::from_bytes([\n        0x4c, 0xb4,"
+        ));
+        assert!(rust.contains("    utxo_commitment: EMPTY_MUHASH,\n"));
+        assert!(rust.contains("    bits: 0x1e21bc1c,\n    nonce: 0x48e5e,\n"));
+        assert!(rust.contains("        0x61, 0x2d, 0x64, 0x65, 0x76, 0x6e, 0x65, 0x74,\n"));
+        assert!(rust.ends_with("    ],\n};\n"));
+    }
+}
@@ -1,15 +1,19 @@
-use clap::ArgAction;
+use crate::config_file::{ConfigFile, ConfigFileError, MempoolOverrides, PerfOverrides, DEFAULT_CONFIG_FILE};
 #[allow(unused)]
 use clap::{arg, command, Arg, Command};
+use clap::{parser::ValueSource, ArgAction, ArgMatches};
 use kaspa_consensus::config::Config;
+use kaspa_consensus_core::constants::{MAX_SOMPI, SOMPI_PER_KASPA};
 use kaspa_core::kaspad_env::version;
-use kaspa_utils::networking::ContextualNetAddress;
+use kaspa_utils::networking::{ContextualNetAddress, IpNetwork};
+use std::path::PathBuf;
 
 pub struct Defaults {
     pub appdir: &'static str,
     pub no_log_files: bool,
     pub rpclisten_borsh: &'static str,
     pub rpclisten_json: &'static str,
+    pub metricslisten: &'static str,
     pub unsafe_rpc: bool,
     pub async_threads: usize,
     pub utxoindex: bool,
@@ -21,7 +25,9 @@ pub struct Defaults {
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    pub light: bool,
     pub sanity: bool,
+    pub no_mempool_persist: bool,
 }
 
 impl Default for Defaults {
@@ -31,6 +37,7 @@ impl Default for Defaults {
             no_log_files: false,
             rpclisten_borsh: "127.0.0.1:17110",
             rpclisten_json: "127.0.0.1:18110",
+            metricslisten: "127.0.0.1:19110",
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
@@ -42,7 +49,9 @@ impl Default for Defaults {
             devnet: false,
             simnet: false,
             archival: false,
+            light: false,
             sanity: false,
+            no_mempool_persist: false,
         }
     }
 }
@@ -56,6 +65,7 @@ pub struct Args {
     pub rpclisten: Option<ContextualNetAddress>,
     pub rpclisten_borsh: Option<ContextualNetAddress>,
     pub rpclisten_json: Option<ContextualNetAddress>,
+    pub metricslisten: Option<ContextualNetAddress>,
     pub unsafe_rpc: bool,
     pub wrpc_verbose: bool,
     pub log_level: String,
@@ -72,14 +82,33 @@ pub struct Args {
     pub testnet: bool,
     pub devnet: bool,
     pub simnet: bool,
+    pub network_file: Option<String>,
     pub archival: bool,
+    pub light: bool,
     pub sanity: bool,
+    pub max_ibd_upload_rate: Option<u64>,
+    pub whitelist: Vec<IpNetwork>,
+    pub import_utxo_snapshot: Option<String>,
+    pub export_utxo_snapshot: Option<String>,
+    pub no_mempool_persist: bool,
+    /// Performance params overrides, settable only from the config file
+    pub perf: PerfOverrides,
+    /// Mempool config overrides, the main policies of which are also settable from the command line
+    pub mempool: MempoolOverrides,
 }
 
 pub fn cli(defaults: &Defaults) -> Command {
     Command::new("kaspad")
         .about(format!("{} (rusty-kaspa) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
         .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("configfile")
+                .short('C')
+                .long("configfile")
+                .value_name("CONFIG_FILE")
+                .require_equals(true)
+                .help(format!("Path to a TOML configuration file (default: <appdir>/{DEFAULT_CONFIG_FILE}). Command line arguments override file values.")),
+        )
         .arg(arg!(-b --appdir <DATA_DIR> "Directory to store data."))
         .arg(arg!(--logdir <LOG_DIR> "Directory to log output."))
         .arg(arg!(--nologfiles "Disable logging to files."))
@@ -130,6 +159,18 @@ pub fn cli(defaults: &Defaults) -> Command {
                 .value_parser(clap::value_parser!(ContextualNetAddress))
                 .help(format!("Interface:port to listen for wRPC JSON connections (default: {}).", defaults.rpclisten_json)),
         )
+        .arg(
+            Arg::new("metricslisten")
+                .long("metricslisten")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .default_missing_value(defaults.metricslisten)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help(format!(
+                    "Interface:port to serve Prometheus metrics over HTTP at the `/metrics` path (default: {}).",
+                    defaults.metricslisten
+                )),
+        )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
         .arg(
             Arg::new("connect-peers")
@@ -173,14 +214,111 @@ pub fn cli(defaults: &Defaults) -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Max number of inbound peers (default: 128)."),
         )
+        .arg(
+            Arg::new("maxibduploadrate")
+                .long("maxibduploadrate")
+                .value_name("KB/s")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Max aggregated upload rate in KB/s for serving IBD data (blocks and UTXO set chunks) to syncing peers (default: unlimited)."),
+        )
+        .arg(
+            Arg::new("whitelist")
+                .long("whitelist")
+                .value_name("IP[/PREFIX]")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(IpNetwork))
+                .help("Add an IP network or IP of trusted peers which are exempt from the inbound limit and from banning, receive transactions immediately and are preferred for IBD (eg. 192.168.1.0/24 or ::1)."),
+        )
+        .arg(
+            Arg::new("import-utxo-snapshot")
+                .long("import-utxo-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Bootstrap a new node from a UTXO set snapshot file instead of performing IBD with a headers proof. Ignored if the node already progressed beyond genesis."),
+        )
+        .arg(
+            Arg::new("export-utxo-snapshot")
+                .long("export-utxo-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Export a UTXO set snapshot of the current pruning point to the specified file and exit."),
+        )
         .arg(arg!(--"reset-db" "Reset database before starting node. It's needed when switching between subnetworks."))
         .arg(arg!(--"enable-unsynced-mining" "Allow the node to accept blocks from RPC while not synced (this flag is mainly used for testing)"))
         .arg(arg!(--utxoindex "Enable the UTXO index"))
         .arg(arg!(--testnet "Use the test network"))
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
-        .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(
+            Arg::new("networkfile")
+                .long("networkfile")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Use the custom network defined in the specified TOML file (genesis, consensus params, address prefix, ports and DNS seeders)."),
+        )
+        .arg(arg!(--archival "Run as an archival node: keeps the full block history (headers, bodies and acceptance data) below the pruning point (Warning: heavy disk usage)"))
+        .arg(arg!(--light "Run as a light node: keeps only headers and the pruning proof, without block bodies or UTXO state"))
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
+        .arg(arg!(--nomempoolpersist "Do not save the mempool to disk on shutdown nor restore it on startup"))
+        .arg(
+            Arg::new("maxmempooltxs")
+                .long("maxmempooltxs")
+                .value_name("COUNT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Max number of transactions to keep in the mempool (default: 1000000)."),
+        )
+        .arg(
+            Arg::new("mempoolexpiry")
+                .long("mempoolexpiry")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Time after which a low priority mempool transaction not included in a block expires (default: 60)."),
+        )
+        .arg(
+            Arg::new("localtxexpiry")
+                .long("localtxexpiry")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Time after which a transaction submitted through RPC and not included in a block expires (default: 86400)."),
+        )
+        .arg(
+            Arg::new("maxorphantx")
+                .long("maxorphantx")
+                .value_name("COUNT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Max number of orphan transactions to keep in memory (default: 50)."),
+        )
+        .arg(
+            Arg::new("maxorphantxmass")
+                .long("maxorphantxmass")
+                .value_name("MASS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Max mass of an orphan transaction kept in memory (default: 100000)."),
+        )
+        .arg(
+            Arg::new("orphanexpiry")
+                .long("orphanexpiry")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .help("Time after which a low priority orphan transaction expires (default: 60)."),
+        )
+        .arg(
+            Arg::new("minrelaytxfee")
+                .long("minrelaytxfee")
+                .value_name("KAS_PER_KB")
+                .require_equals(true)
+                .value_parser(parse_kas_per_kb)
+                .help("Min fee, in KAS per 1000 grams of mass, for a transaction to be accepted to the mempool and relayed (default: 1e-05)."),
+        )
+        .arg(arg!(--relaynonstd "Accept and relay non-standard transactions"))
         .arg(
             Arg::new("user_agent_comments")
                 .long("uacomment")
@@ -190,34 +328,105 @@ pub fn cli(defaults: &Defaults) -> Command {
         )
 }
 
+/// Returns the value of `id` only if it was explicitly specified on the command line
+fn cli_value<T: Clone + Send + Sync + 'static>(m: &ArgMatches, id: &str) -> Option<T> {
+    match m.value_source(id) {
+        Some(ValueSource::CommandLine) => m.get_one::<T>(id).cloned(),
+        _ => None,
+    }
+}
+
+/// Parses a fee rate in KAS per 1000 grams of mass, as taken by go-kaspad, into sompi per 1000 grams of mass
+fn parse_kas_per_kb(value: &str) -> Result<u64, String> {
+    let kas_per_kb = value.parse::<f64>().map_err(|err| err.to_string())?;
+    let sompi_per_kb = (kas_per_kb * SOMPI_PER_KASPA as f64).round();
+    if !(0.0..=MAX_SOMPI as f64).contains(&sompi_per_kb) {
+        return Err(format!("{value} is not a valid amount of KAS"));
+    }
+    Ok(sompi_per_kb as u64)
+}
+
+/// Returns the values of `id` if specified on the command line, or `file_values` otherwise
+fn cli_values<T: Clone + Send + Sync + 'static>(m: &ArgMatches, id: &str, file_values: Vec<T>) -> Vec<T> {
+    match m.value_source(id) {
+        Some(ValueSource::CommandLine) => m.get_many::<T>(id).unwrap_or_default().cloned().collect(),
+        _ => file_values,
+    }
+}
+
 impl Args {
-    pub fn parse(defaults: &Defaults) -> Args {
+    /// Parses the command line arguments on top of the config file, which is read from `--configfile` if specified,
+    /// or from the default location under the application directory if such a file exists
+    pub fn parse(defaults: &Defaults) -> Result<Args, ConfigFileError> {
         let m = cli(defaults).get_matches();
+        let file = match m.get_one::<String>("configfile") {
+            Some(path) => ConfigFile::load(&expand_home(path))?,
+            None => {
+                let app_dir = m.get_one::<String>("appdir").map(|dir| expand_home(dir)).unwrap_or_else(crate::get_app_dir);
+                let path = app_dir.join(DEFAULT_CONFIG_FILE);
+                if path.exists() {
+                    ConfigFile::load(&path)?
+                } else {
+                    ConfigFile::default()
+                }
+            }
+        };
+        Ok(Self::from_matches(defaults, &m, file))
+    }
+
+    pub(crate) fn from_matches(defaults: &Defaults, m: &ArgMatches, file: ConfigFile) -> Args {
         Args {
-            appdir: m.get_one::<String>("appdir").cloned(),
-            logdir: m.get_one::<String>("logdir").cloned(),
-            no_log_files: m.get_one::<bool>("nologfiles").cloned().unwrap_or(defaults.no_log_files),
-            rpclisten: m.get_one::<ContextualNetAddress>("rpclisten").cloned(),
-            rpclisten_borsh: m.get_one::<ContextualNetAddress>("rpclisten-borsh").cloned(),
-            rpclisten_json: m.get_one::<ContextualNetAddress>("rpclisten-json").cloned(),
-            unsafe_rpc: m.get_one::<bool>("unsaferpc").cloned().unwrap_or(defaults.unsafe_rpc),
-            wrpc_verbose: false,
-            log_level: m.get_one::<String>("log_level").cloned().unwrap(),
-            async_threads: m.get_one::<usize>("async_threads").cloned().unwrap_or(defaults.async_threads),
-            connect_peers: m.get_many::<ContextualNetAddress>("connect-peers").unwrap_or_default().copied().collect(),
-            add_peers: m.get_many::<ContextualNetAddress>("add-peers").unwrap_or_default().copied().collect(),
-            listen: m.get_one::<ContextualNetAddress>("listen").cloned(),
-            outbound_target: m.get_one::<usize>("outpeers").cloned().unwrap_or(defaults.outbound_target),
-            inbound_limit: m.get_one::<usize>("maxinpeers").cloned().unwrap_or(defaults.inbound_limit),
+            appdir: cli_value(m, "appdir").or(file.appdir),
+            logdir: cli_value(m, "logdir").or(file.logdir),
+            no_log_files: cli_value(m, "nologfiles").or(file.no_log_files).unwrap_or(defaults.no_log_files),
+            rpclisten: cli_value(m, "rpclisten").or(file.rpclisten),
+            rpclisten_borsh: cli_value(m, "rpclisten-borsh").or(file.rpclisten_borsh),
+            rpclisten_json: cli_value(m, "rpclisten-json").or(file.rpclisten_json),
+            metricslisten: cli_value(m, "metricslisten").or(file.metricslisten),
+            unsafe_rpc: cli_value(m, "unsaferpc").or(file.unsafe_rpc).unwrap_or(defaults.unsafe_rpc),
+            wrpc_verbose: file.wrpc_verbose.unwrap_or(false),
+            log_level: cli_value(m, "log_level")
+                .or(file.log_level)
+                .unwrap_or_else(|| m.get_one::<String>("log_level").cloned().unwrap()),
+            async_threads: cli_value(m, "async_threads").or(file.async_threads).unwrap_or(defaults.async_threads),
+            connect_peers: cli_values(m, "connect-peers", file.connect_peers),
+            add_peers: cli_values(m, "add-peers", file.add_peers),
+            listen: cli_value(m, "listen").or(file.listen),
+            outbound_target: cli_value(m, "outpeers").or(file.outbound_target).unwrap_or(defaults.outbound_target),
+            inbound_limit: cli_value(m, "maxinpeers").or(file.inbound_limit).unwrap_or(defaults.inbound_limit),
             reset_db: m.get_one::<bool>("reset-db").cloned().unwrap_or(defaults.reset_db),
-            enable_unsynced_mining: m.get_one::<bool>("enable-unsynced-mining").cloned().unwrap_or(defaults.enable_unsynced_mining),
-            utxoindex: m.get_one::<bool>("utxoindex").cloned().unwrap_or(defaults.utxoindex),
-            testnet: m.get_one::<bool>("testnet").cloned().unwrap_or(defaults.testnet),
-            devnet: m.get_one::<bool>("devnet").cloned().unwrap_or(defaults.devnet),
-            simnet: m.get_one::<bool>("simnet").cloned().unwrap_or(defaults.simnet),
-            archival: m.get_one::<bool>("archival").cloned().unwrap_or(defaults.archival),
-            sanity: m.get_one::<bool>("sanity").cloned().unwrap_or(defaults.sanity),
-            user_agent_comments: m.get_many::<String>("user_agent_comments").unwrap_or_default().cloned().collect(),
+            enable_unsynced_mining: cli_value(m, "enable-unsynced-mining")
+                .or(file.enable_unsynced_mining)
+                .unwrap_or(defaults.enable_unsynced_mining),
+            utxoindex: cli_value(m, "utxoindex").or(file.utxoindex).unwrap_or(defaults.utxoindex),
+            testnet: cli_value(m, "testnet").or(file.testnet).unwrap_or(defaults.testnet),
+            devnet: cli_value(m, "devnet").or(file.devnet).unwrap_or(defaults.devnet),
+            simnet: cli_value(m, "simnet").or(file.simnet).unwrap_or(defaults.simnet),
+            network_file: cli_value(m, "networkfile").or(file.network_file),
+            archival: cli_value(m, "archival").or(file.archival).unwrap_or(defaults.archival),
+            light: cli_value(m, "light").or(file.light).unwrap_or(defaults.light),
+            sanity: cli_value(m, "sanity").or(file.sanity).unwrap_or(defaults.sanity),
+            // A zero rate in the config file means no limit
+            max_ibd_upload_rate: cli_value(m, "maxibduploadrate").or(file.max_ibd_upload_rate.filter(|&rate| rate > 0)),
+            whitelist: cli_values(m, "whitelist", file.whitelist),
+            import_utxo_snapshot: cli_value(m, "import-utxo-snapshot").or(file.import_utxo_snapshot),
+            export_utxo_snapshot: m.get_one::<String>("export-utxo-snapshot").cloned(),
+            no_mempool_persist: cli_value(m, "nomempoolpersist").or(file.no_mempool_persist).unwrap_or(defaults.no_mempool_persist),
+            user_agent_comments: cli_values(m, "user_agent_comments", file.user_agent_comments),
+            perf: file.perf,
+            mempool: MempoolOverrides {
+                maximum_transaction_count: cli_value(m, "maxmempooltxs").or(file.mempool.maximum_transaction_count),
+                transaction_expire_interval_seconds: cli_value(m, "mempoolexpiry")
+                    .or(file.mempool.transaction_expire_interval_seconds),
+                local_transaction_expire_interval_seconds: cli_value(m, "localtxexpiry")
+                    .or(file.mempool.local_transaction_expire_interval_seconds),
+                orphan_expire_interval_seconds: cli_value(m, "orphanexpiry").or(file.mempool.orphan_expire_interval_seconds),
+                maximum_orphan_transaction_mass: cli_value(m, "maxorphantxmass").or(file.mempool.maximum_orphan_transaction_mass),
+                maximum_orphan_transaction_count: cli_value(m, "maxorphantx").or(file.mempool.maximum_orphan_transaction_count),
+                accept_non_standard: cli_value(m, "relaynonstd").or(file.mempool.accept_non_standard),
+                minimum_relay_transaction_fee: cli_value(m, "minrelaytxfee").or(file.mempool.minimum_relay_transaction_fee),
+                ..file.mempool
+            },
         }
     }
 
@@ -226,12 +435,20 @@ impl Args {
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.is_archival = self.archival;
+        config.is_light = self.light;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments = self.user_agent_comments.clone();
+        config.ibd_upload_rate_limit = self.max_ibd_upload_rate.map(|kb| kb.saturating_mul(1000));
+        config.whitelist = self.whitelist.clone().into();
+        self.perf.apply(&mut config.perf);
     }
 }
 
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    PathBuf::from(path.replace('~', crate::get_home_dir().as_path().to_str().unwrap()))
+}
+
 /*
 
   -V, --version                             Display version information and exit
@@ -0,0 +1,338 @@
+use kaspa_consensus_core::config::constants::perf::PerfParams;
+use kaspa_mining::mempool::config::Config as MempoolConfig;
+use kaspa_utils::networking::{ContextualNetAddress, IpNetwork};
+use serde::{de::Error as _, Deserialize, Deserializer};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// The name of the config file looked up under the application directory when `--configfile` is not specified
+pub const DEFAULT_CONFIG_FILE: &str = "kaspad.toml";
+
+#[derive(Error, Debug)]
+pub enum ConfigFileError {
+    #[error("Configuration: cannot read config file {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+
+    #[error("Configuration: invalid config file {0}: {1}")]
+    Parse(PathBuf, toml::de::Error),
+}
+
+/// Node settings loaded from a TOML config file.
+///
+/// Top-level keys mirror the fields of [`crate::args::Args`] and any value specified on the command line
+/// overrides the value in the file. `reset_db` and `export_utxo_snapshot` are deliberately not supported, since
+/// a config file is applied on every launch. Additionally, the `[perf]` and `[mempool]` tables override consensus
+/// performance params and mempool config values respectively. Unknown keys are rejected so that typos do not go
+/// unnoticed.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub appdir: Option<String>,
+    pub logdir: Option<String>,
+    pub no_log_files: Option<bool>,
+    #[serde(deserialize_with = "deserialize_option_from_str")]
+    pub rpclisten: Option<ContextualNetAddress>,
+    #[serde(deserialize_with = "deserialize_option_from_str")]
+    pub rpclisten_borsh: Option<ContextualNetAddress>,
+    #[serde(deserialize_with = "deserialize_option_from_str")]
+    pub rpclisten_json: Option<ContextualNetAddress>,
+    #[serde(deserialize_with = "deserialize_option_from_str")]
+    pub metricslisten: Option<ContextualNetAddress>,
+    pub unsafe_rpc: Option<bool>,
+    pub wrpc_verbose: Option<bool>,
+    pub log_level: Option<String>,
+    pub async_threads: Option<usize>,
+    #[serde(deserialize_with = "deserialize_vec_from_str")]
+    pub connect_peers: Vec<ContextualNetAddress>,
+    #[serde(deserialize_with = "deserialize_vec_from_str")]
+    pub add_peers: Vec<ContextualNetAddress>,
+    #[serde(deserialize_with = "deserialize_option_from_str")]
+    pub listen: Option<ContextualNetAddress>,
+    pub user_agent_comments: Vec<String>,
+    pub utxoindex: Option<bool>,
+    pub outbound_target: Option<usize>,
+    pub inbound_limit: Option<usize>,
+    pub enable_unsynced_mining: Option<bool>,
+    pub testnet: Option<bool>,
+    pub devnet: Option<bool>,
+    pub simnet: Option<bool>,
+    pub network_file: Option<String>,
+    pub archival: Option<bool>,
+    pub light: Option<bool>,
+    pub sanity: Option<bool>,
+    pub max_ibd_upload_rate: Option<u64>,
+    #[serde(deserialize_with = "deserialize_vec_from_str")]
+    pub whitelist: Vec<IpNetwork>,
+    pub import_utxo_snapshot: Option<String>,
+    pub no_mempool_persist: Option<bool>,
+
+    pub perf: PerfOverrides,
+    pub mempool: MempoolOverrides,
+}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self, ConfigFileError> {
+        let content = std::fs::read_to_string(path).map_err(|err| ConfigFileError::Read(path.to_owned(), err))?;
+        Self::parse(path, &content)
+    }
+
+    pub fn parse(path: &Path, content: &str) -> Result<Self, ConfigFileError> {
+        toml::from_str(content).map_err(|err| ConfigFileError::Parse(path.to_owned(), err))
+    }
+}
+
+/// Overrides of [`PerfParams`] cache and thread-pool sizes
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct PerfOverrides {
+    pub header_data_cache_size: Option<u64>,
+    pub block_data_cache_size: Option<u64>,
+    pub utxo_set_cache_size: Option<u64>,
+    pub block_window_cache_size: Option<u64>,
+    pub block_processors_num_threads: Option<usize>,
+    pub virtual_processor_num_threads: Option<usize>,
+}
+
+impl PerfOverrides {
+    pub fn apply(&self, perf: &mut PerfParams) {
+        override_value(&mut perf.header_data_cache_size, self.header_data_cache_size);
+        override_value(&mut perf.block_data_cache_size, self.block_data_cache_size);
+        override_value(&mut perf.utxo_set_cache_size, self.utxo_set_cache_size);
+        override_value(&mut perf.block_window_cache_size, self.block_window_cache_size);
+        override_value(&mut perf.block_processors_num_threads, self.block_processors_num_threads);
+        override_value(&mut perf.virtual_processor_num_threads, self.virtual_processor_num_threads);
+    }
+}
+
+/// Overrides of mempool [`MempoolConfig`] values. The resulting config is validated by the node on startup.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct MempoolOverrides {
+    pub maximum_transaction_count: Option<u64>,
+    /// Transaction expiry in seconds, overriding `transaction_expire_interval_daa_score` if both are specified
+    pub transaction_expire_interval_seconds: Option<u64>,
+    pub transaction_expire_interval_daa_score: Option<u64>,
+    /// Local transaction expiry in seconds, overriding `local_transaction_expire_interval_daa_score` if both are specified
+    pub local_transaction_expire_interval_seconds: Option<u64>,
+    pub local_transaction_expire_interval_daa_score: Option<u64>,
+    pub transaction_expire_scan_interval_daa_score: Option<u64>,
+    pub transaction_expire_scan_interval_milliseconds: Option<u64>,
+    /// Orphan expiry in seconds, overriding `orphan_expire_interval_daa_score` if both are specified
+    pub orphan_expire_interval_seconds: Option<u64>,
+    pub orphan_expire_interval_daa_score: Option<u64>,
+    pub orphan_expire_scan_interval_daa_score: Option<u64>,
+    pub maximum_orphan_transaction_mass: Option<u64>,
+    pub maximum_orphan_transaction_count: Option<u64>,
+    pub maximum_replacement_evictions: Option<u64>,
+    pub accept_non_standard: Option<bool>,
+    pub maximum_mass_per_block: Option<u64>,
+    /// In sompi per 1000 grams of mass, unlike `--minrelaytxfee` which takes KAS per 1000 grams of mass
+    pub minimum_relay_transaction_fee: Option<u64>,
+    pub minimum_standard_transaction_version: Option<u16>,
+    pub maximum_standard_transaction_version: Option<u16>,
+}
+
+impl MempoolOverrides {
+    pub fn apply(&self, config: &mut MempoolConfig) {
+        override_value(&mut config.maximum_transaction_count, self.maximum_transaction_count);
+        override_value(&mut config.transaction_expire_interval_daa_score, self.transaction_expire_interval_daa_score);
+        override_value(&mut config.local_transaction_expire_interval_daa_score, self.local_transaction_expire_interval_daa_score);
+        override_value(&mut config.transaction_expire_scan_interval_daa_score, self.transaction_expire_scan_interval_daa_score);
+        override_value(&mut config.transaction_expire_scan_interval_milliseconds, self.transaction_expire_scan_interval_milliseconds);
+        override_value(&mut config.orphan_expire_interval_daa_score, self.orphan_expire_interval_daa_score);
+        override_value(&mut config.orphan_expire_scan_interval_daa_score, self.orphan_expire_scan_interval_daa_score);
+        override_value(&mut config.maximum_orphan_transaction_mass, self.maximum_orphan_transaction_mass);
+        override_value(&mut config.maximum_orphan_transaction_count, self.maximum_orphan_transaction_count);
+        override_value(&mut config.maximum_replacement_evictions, self.maximum_replacement_evictions);
+        override_value(&mut config.accept_non_standard, self.accept_non_standard);
+        override_value(&mut config.maximum_mass_per_block, self.maximum_mass_per_block);
+        override_value(&mut config.minimum_relay_transaction_fee, self.minimum_relay_transaction_fee);
+        override_value(&mut config.minimum_standard_transaction_version, self.minimum_standard_transaction_version);
+        override_value(&mut config.maximum_standard_transaction_version, self.maximum_standard_transaction_version);
+
+        // Intervals given in seconds are converted to DAA score using the network block rate, saturating on overflow
+        let target_milliseconds_per_block = config.target_milliseconds_per_block.max(1);
+        let to_daa_score = |seconds: u64| seconds.saturating_mul(1000) / target_milliseconds_per_block;
+        override_value(&mut config.transaction_expire_interval_daa_score, self.transaction_expire_interval_seconds.map(to_daa_score));
+        override_value(
+            &mut config.local_transaction_expire_interval_daa_score,
+            self.local_transaction_expire_interval_seconds.map(to_daa_score),
+        );
+        override_value(&mut config.orphan_expire_interval_daa_score, self.orphan_expire_interval_seconds.map(to_daa_score));
+    }
+}
+
+fn override_value<T>(target: &mut T, value: Option<T>) {
+    if let Some(value) = value {
+        *target = value;
+    }
+}
+
+/// Deserializes a value from its string representation (as accepted on the command line)
+fn deserialize_option_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Option::<String>::deserialize(deserializer)?.map(|s| T::from_str(&s).map_err(D::Error::custom)).transpose()
+}
+
+/// Deserializes a list of values from their string representations (as accepted on the command line)
+fn deserialize_vec_from_str<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    Vec::<String>::deserialize(deserializer)?.iter().map(|s| T::from_str(s).map_err(D::Error::custom)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{cli, Args, Defaults};
+    use kaspa_consensus_core::config::{params::MAINNET_PARAMS, Config};
+    use kaspa_mining::mempool::errors::ConfigError as MempoolConfigError;
+
+    const CONFIG: &str = r#"
+        utxoindex = true
+        log_level = "debug"
+        outbound_target = 16
+        rpclisten = "0.0.0.0:16110"
+        add_peers = ["10.0.0.1", "10.0.0.2:16111"]
+        whitelist = ["10.0.0.0/8"]
+
+        [perf]
+        utxo_set_cache_size = 50000
+        virtual_processor_num_threads = 2
+
+        [mempool]
+        maximum_transaction_count = 5000
+        minimum_relay_transaction_fee = 2000
+    "#;
+
+    #[test]
+    fn test_config_file_with_cli_overrides() {
+        let defaults = Defaults::default();
+        let file = ConfigFile::parse(Path::new(DEFAULT_CONFIG_FILE), CONFIG).unwrap();
+
+        // Values set only in the file are applied
+        let m = cli(&defaults).try_get_matches_from(["kaspad"]).unwrap();
+        let args = Args::from_matches(&defaults, &m, file);
+        assert!(args.utxoindex);
+        assert_eq!(args.log_level, "debug");
+        assert_eq!(args.outbound_target, 16);
+        assert_eq!(args.rpclisten, Some("0.0.0.0:16110".parse().unwrap()));
+        assert_eq!(args.add_peers.len(), 2);
+        assert_eq!(args.whitelist, vec!["10.0.0.0/8".parse().unwrap()]);
+        assert_eq!(args.inbound_limit, defaults.inbound_limit);
+
+        let mut perf = kaspa_consensus_core::config::constants::perf::PERF_PARAMS;
+        args.perf.apply(&mut perf);
+        assert_eq!(perf.utxo_set_cache_size, 50000);
+        assert_eq!(perf.virtual_processor_num_threads, 2);
+        assert_eq!(perf.header_data_cache_size, kaspa_consensus_core::config::constants::perf::PERF_PARAMS.header_data_cache_size);
+
+        let mut mempool_config = MempoolConfig::build_default(1000, false, 500_000);
+        args.mempool.apply(&mut mempool_config);
+        assert_eq!(mempool_config.maximum_transaction_count, 5000);
+        assert_eq!(mempool_config.minimum_relay_transaction_fee, 2000);
+
+        // Command line values override the file
+        let file = ConfigFile::parse(Path::new(DEFAULT_CONFIG_FILE), CONFIG).unwrap();
+        let m = cli(&defaults).try_get_matches_from(["kaspad", "--loglevel=trace", "--outpeers=4", "--addpeer=10.0.0.3"]).unwrap();
+        let args = Args::from_matches(&defaults, &m, file);
+        assert_eq!(args.log_level, "trace");
+        assert_eq!(args.outbound_target, 4);
+        assert_eq!(args.add_peers, vec!["10.0.0.3".parse().unwrap()]);
+        assert!(args.utxoindex);
+
+        // The IBD upload rate is taken in KB/s and saturates rather than overflows
+        let ibd_upload_rate_limit = |file: &str, argv: &[&str]| {
+            let file = ConfigFile::parse(Path::new(DEFAULT_CONFIG_FILE), file).unwrap();
+            let m = cli(&defaults).try_get_matches_from(argv).unwrap();
+            let mut config = Config::new(MAINNET_PARAMS);
+            Args::from_matches(&defaults, &m, file).apply_to_config(&mut config);
+            config.ibd_upload_rate_limit
+        };
+        assert_eq!(ibd_upload_rate_limit("", &["kaspad"]), None);
+        assert_eq!(ibd_upload_rate_limit("max_ibd_upload_rate = 0", &["kaspad"]), None);
+        assert_eq!(ibd_upload_rate_limit("max_ibd_upload_rate = 500", &["kaspad"]), Some(500_000));
+        assert_eq!(ibd_upload_rate_limit("max_ibd_upload_rate = 500", &["kaspad", "--maxibduploadrate=20"]), Some(20_000));
+        assert_eq!(ibd_upload_rate_limit(&format!("max_ibd_upload_rate = {}", i64::MAX), &["kaspad"]), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_mempool_policy_overrides() {
+        let defaults = Defaults::default();
+        let file = ConfigFile::parse(
+            Path::new(DEFAULT_CONFIG_FILE),
+            "[mempool]\nmaximum_transaction_count = 5000\norphan_expire_interval_seconds = 120\nmaximum_replacement_evictions = 10",
+        )
+        .unwrap();
+        let m = cli(&defaults)
+            .try_get_matches_from([
+                "kaspad",
+                "--maxmempooltxs=20000",
+                "--mempoolexpiry=30",
+                "--localtxexpiry=3600",
+                "--minrelaytxfee=0.00005",
+                "--relaynonstd",
+            ])
+            .unwrap();
+        let args = Args::from_matches(&defaults, &m, file);
+
+        let mut mempool_config = MempoolConfig::build_default(1000, false, 500_000);
+        args.mempool.apply(&mut mempool_config);
+        assert_eq!(mempool_config.maximum_transaction_count, 20000);
+        assert_eq!(mempool_config.transaction_expire_interval_daa_score, 30);
+        assert_eq!(mempool_config.local_transaction_expire_interval_daa_score, 3600);
+        assert_eq!(mempool_config.orphan_expire_interval_daa_score, 120);
+        assert_eq!(mempool_config.maximum_replacement_evictions, 10);
+        assert_eq!(mempool_config.minimum_relay_transaction_fee, 5000);
+        assert!(mempool_config.accept_non_standard);
+        assert!(mempool_config.validate(500_000).is_ok());
+
+        // The min relay fee is taken in KAS/kB like in go-kaspad, and intervals in seconds saturate rather than overflow
+        let fee = |value: &str| cli(&defaults).try_get_matches_from(["kaspad", &format!("--minrelaytxfee={value}")]);
+        assert_eq!(fee("1e-05").unwrap().get_one::<u64>("minrelaytxfee"), Some(&1000));
+        assert_eq!(fee("0").unwrap().get_one::<u64>("minrelaytxfee"), Some(&0));
+        assert!(fee("-0.00001").is_err());
+        assert!(fee("1000 sompi").is_err());
+        assert!(fee("NaN").is_err());
+        let file = ConfigFile::parse(
+            Path::new(DEFAULT_CONFIG_FILE),
+            &format!("[mempool]\nlocal_transaction_expire_interval_seconds = {}", i64::MAX),
+        )
+        .unwrap();
+        let m = cli(&defaults).try_get_matches_from(["kaspad"]).unwrap();
+        let mut mempool_config = MempoolConfig::build_default(1000, false, 500_000);
+        Args::from_matches(&defaults, &m, file).mempool.apply(&mut mempool_config);
+        assert_eq!(mempool_config.local_transaction_expire_interval_daa_score, u64::MAX / 1000);
+
+        // Inconsistent values are rejected on validation
+        assert!(cli(&defaults).try_get_matches_from(["kaspad", "--maxmempooltxs=0"]).is_err());
+        let file = ConfigFile::parse(Path::new(DEFAULT_CONFIG_FILE), "[mempool]\nmaximum_mass_per_block = 50000").unwrap();
+        let m = cli(&defaults).try_get_matches_from(["kaspad"]).unwrap();
+        let mut mempool_config = MempoolConfig::build_default(1000, false, 500_000);
+        Args::from_matches(&defaults, &m, file).mempool.apply(&mut mempool_config);
+        assert!(matches!(mempool_config.validate(500_000), Err(MempoolConfigError::OrphanMassExceedsBlockMass(100_000, 50_000))));
+        mempool_config.maximum_mass_per_block = 600_000;
+        assert!(matches!(mempool_config.validate(500_000), Err(MempoolConfigError::BlockMassExceedsConsensus(600_000, 500_000))));
+    }
+
+    #[test]
+    fn test_config_file_errors() {
+        let path = Path::new(DEFAULT_CONFIG_FILE);
+        assert!(matches!(ConfigFile::parse(path, "utxoindx = true"), Err(ConfigFileError::Parse(..))));
+        assert!(matches!(ConfigFile::parse(path, "[perf]\nutxo_cache = 1"), Err(ConfigFileError::Parse(..))));
+        assert!(matches!(ConfigFile::parse(path, "listen = \"not an address\""), Err(ConfigFileError::Parse(..))));
+        assert!(matches!(ConfigFile::parse(path, "outbound_target = \"8\""), Err(ConfigFileError::Parse(..))));
+        assert!(ConfigFile::parse(path, "").is_ok());
+    }
+}
@@ -6,7 +6,7 @@ use kaspa_addressmanager::AddressManager;
 use kaspa_consensus::consensus::factory::Factory as ConsensusFactory;
 use kaspa_consensus::pipeline::monitor::ConsensusMonitor;
 use kaspa_consensus::pipeline::ProcessingCounters;
-use kaspa_consensus_core::config::Config;
+use kaspa_consensus_core::config::{network::load_network_params, Config};
 use kaspa_consensus_core::errors::config::{ConfigError, ConfigResult};
 use kaspa_consensus_core::networktype::NetworkType;
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
@@ -15,7 +15,10 @@ use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::kaspad_env::version;
 use kaspa_core::{core::Core, signals::Signals, task::runtime::AsyncRuntime};
 use kaspa_index_processor::service::IndexService;
+use kaspa_metrics::{MetricsCollector, MetricsServer, DEFAULT_METRICS_PORT};
 use kaspa_mining::manager::MiningManager;
+use kaspa_mining::mempool::config::Config as MempoolConfig;
+use kaspa_notify::notifier::DynNotifierStatsProvider;
 use kaspa_p2p_flows::flow_context::FlowContext;
 use kaspa_rpc_service::RpcCoreServer;
 use kaspa_utils::networking::ContextualNetAddress;
@@ -27,7 +30,7 @@ use std::sync::Arc;
 
 // ~~~
 // TODO - discuss handling
-use args::{Args, Defaults};
+use args::{expand_home, Args, Defaults};
 // use clap::Parser;
 // ~~~
 
@@ -39,13 +42,18 @@ use kaspa_core::{info, trace};
 use kaspa_grpc_server::GrpcServer;
 use kaspa_p2p_flows::service::P2pService;
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WrpcEncoding, WrpcService};
+use mempool_persistence::MempoolPersistenceService;
 
 mod args;
+mod config_file;
+mod mempool_persistence;
+mod snapshot;
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
 const META_DB: &str = "meta";
+const MEMPOOL_FILE: &str = "mempool.dat";
 const DEFAULT_LOG_DIR: &str = "logs";
 
 // TODO: refactor the shutdown sequence into a predefined controlled sequence
@@ -132,11 +140,26 @@ fn validate_config_and_args(_config: &Arc<Config>, args: &Args) -> ConfigResult<
     if args.logdir.is_some() && args.no_log_files {
         return Err(ConfigError::MixedLogDirAndNoLogFiles);
     }
+    if args.network_file.is_some() && (args.testnet || args.devnet || args.simnet) {
+        return Err(ConfigError::MixedNetworkFileAndNetworkType);
+    }
+    if args.light && args.archival {
+        return Err(ConfigError::MixedLightAndArchival);
+    }
+    if args.light && args.utxoindex {
+        return Err(ConfigError::MixedLightAndUtxoIndex);
+    }
     Ok(())
 }
 
 pub fn main() {
-    let args = Args::parse(&Defaults::default());
+    let args = match Args::parse(&Defaults::default()) {
+        Ok(args) => args,
+        Err(err) => {
+            println!("{}", err);
+            exit(1);
+        }
+    };
 
     // Configure the panic behavior
     kaspa_core::panic::configure_panic();
@@ -149,7 +172,18 @@ pub fn main() {
         _ => panic!("only a single net should be activated"),
     };
 
-    let config = Arc::new(ConfigBuilder::new(network_type.into()).apply_args(|config| args.apply_to_config(config)).build());
+    let params = match args.network_file.as_deref() {
+        Some(path) => match load_network_params(&expand_home(path)) {
+            Ok(params) => params,
+            Err(err) => {
+                println!("Failed loading the custom network definition: {}", err);
+                exit(1);
+            }
+        },
+        None => network_type.into(),
+    };
+
+    let config = Arc::new(ConfigBuilder::new(params).apply_args(|config| args.apply_to_config(config)).build());
 
     // Make sure config and args form a valid set of properties
     if let Err(err) = validate_config_and_args(&config, &args) {
@@ -157,6 +191,13 @@ pub fn main() {
         exit(1);
     }
 
+    let mut mempool_config = MempoolConfig::build_default(config.target_time_per_block, false, config.max_block_mass);
+    args.mempool.apply(&mut mempool_config);
+    if let Err(err) = mempool_config.validate(config.max_block_mass) {
+        println!("Configuration: invalid mempool policy: {}", err);
+        exit(1);
+    }
+
     // TODO: Refactor all this quick-and-dirty code
     let app_dir = args
         .appdir
@@ -177,6 +218,9 @@ pub fn main() {
     info!("{} v{}", env!("CARGO_PKG_NAME"), version());
 
     assert!(!db_dir.to_str().unwrap().is_empty());
+    if let Some(path) = args.network_file.as_deref() {
+        info!("Custom network {} defined in: {}", config.network_name(), path);
+    }
     info!("Application directory: {}", app_dir.display());
     info!("Data directory: {}", db_dir.display());
     match log_dir {
@@ -191,6 +235,7 @@ pub fn main() {
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
+    let mempool_file = db_dir.join(MEMPOOL_FILE);
 
     if args.reset_db && db_dir.exists() {
         // TODO: add prompt that validates the choice (unless you pass -y)
@@ -198,6 +243,16 @@ pub fn main() {
         fs::remove_dir_all(db_dir).unwrap();
     }
 
+    if let Some(path) = args.export_utxo_snapshot.as_deref() {
+        match snapshot::export_utxo_snapshot(&meta_db_dir, &consensus_db_dir, &config, &expand_home(path)) {
+            Ok(()) => exit(0),
+            Err(err) => {
+                println!("Failed exporting the UTXO set snapshot: {}", err);
+                exit(1);
+            }
+        }
+    }
+
     fs::create_dir_all(consensus_db_dir.as_path()).unwrap();
     fs::create_dir_all(meta_db_dir.as_path()).unwrap();
     if args.utxoindex {
@@ -235,13 +290,24 @@ pub fn main() {
         notification_root.clone(),
         counters.clone(),
     ));
-    let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
-    let monitor = Arc::new(ConsensusMonitor::new(counters));
+
+    if let Some(path) = args.import_utxo_snapshot.as_deref() {
+        if let Err(err) = snapshot::import_utxo_snapshot(consensus_factory.as_ref(), &config, &expand_home(path)) {
+            println!("Failed importing the UTXO set snapshot: {}", err);
+            exit(1);
+        }
+    }
+
+    let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory.clone()));
+    let monitor = Arc::new(ConsensusMonitor::new(counters.clone()));
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv));
+    // Non-consensus databases, reported by the metrics server
+    let mut databases = vec![(META_DB, meta_db.clone())];
     let index_service: Option<Arc<IndexService>> = if args.utxoindex {
         // Use only a single thread for none-consensus databases
         let utxoindex_db = kaspa_database::prelude::open_db(utxoindex_db_dir, true, 1);
+        databases.push((UTXOINDEX_DB, utxoindex_db.clone()));
         let utxoindex = UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap();
         let index_service = Arc::new(IndexService::new(&notify_service.notifier(), Some(utxoindex)));
         Some(index_service)
@@ -250,7 +316,9 @@ pub fn main() {
     };
 
     let address_manager = AddressManager::new(meta_db);
-    let mining_manager = Arc::new(MiningManager::new(config.target_time_per_block, false, config.max_block_mass, None));
+    let mining_manager = Arc::new(MiningManager::with_notification_root(mempool_config, None, Some(notification_root.clone())));
+    let mempool_persistence_service = (!args.no_mempool_persist && !config.is_light)
+        .then(|| Arc::new(MempoolPersistenceService::new(consensus_manager.clone(), mining_manager.clone(), mempool_file)));
 
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
@@ -275,12 +343,21 @@ pub fn main() {
         notify_service.notifier(),
         index_service.as_ref().map(|x| x.notifier()),
         mining_manager,
-        flow_context,
+        flow_context.clone(),
         index_service.as_ref().map(|x| x.utxoindex().unwrap()),
         config,
         core.clone(),
     ));
     let grpc_server = Arc::new(GrpcServer::new(grpc_server_addr, rpc_core_server.service()));
+    let metrics_server = args.metricslisten.map(|address| {
+        let mut notifiers: Vec<DynNotifierStatsProvider> = vec![notify_service.notifier(), rpc_core_server.service().notifier()];
+        if let Some(index_service) = index_service.as_ref() {
+            notifiers.push(index_service.notifier());
+        }
+        let collector =
+            MetricsCollector::new(counters, consensus_manager.clone(), consensus_factory, flow_context, databases, notifiers);
+        Arc::new(MetricsServer::new(address.normalize(DEFAULT_METRICS_PORT), Arc::new(collector)))
+    });
 
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
@@ -292,6 +369,12 @@ pub fn main() {
     async_runtime.register(grpc_server);
     async_runtime.register(p2p_service);
     async_runtime.register(monitor);
+    if let Some(mempool_persistence_service) = mempool_persistence_service {
+        async_runtime.register(mempool_persistence_service);
+    }
+    if let Some(metrics_server) = metrics_server {
+        async_runtime.register(metrics_server);
+    }
 
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
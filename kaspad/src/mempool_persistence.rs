@@ -0,0 +1,86 @@
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+use kaspa_mining::manager::MiningManager;
+use kaspa_utils::triggers::SingleTrigger;
+use std::{ops::Deref, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{select, time::sleep};
+
+const MEMPOOL_PERSISTENCE_SERVICE: &str = "mempool-persistence-service";
+
+/// Interval between two periodic saves of the mempool, limiting the loss in case of a crash
+const SAVE_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Restores the mempool saved in a file when the node starts, then saves the mempool to this file
+/// periodically and when the node shuts down gracefully
+pub struct MempoolPersistenceService {
+    consensus_manager: Arc<ConsensusManager>,
+    mining_manager: Arc<MiningManager>,
+    path: PathBuf,
+    shutdown: SingleTrigger,
+}
+
+impl MempoolPersistenceService {
+    pub fn new(consensus_manager: Arc<ConsensusManager>, mining_manager: Arc<MiningManager>, path: PathBuf) -> Self {
+        Self { consensus_manager, mining_manager, path, shutdown: SingleTrigger::default() }
+    }
+
+    async fn load(&self) {
+        if !self.path.exists() {
+            return;
+        }
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        match self.mining_manager.load_mempool(session.deref(), &self.path) {
+            Ok((restored, dropped)) => {
+                info!("Restored {} mempool transactions from {}, dropped {} no longer valid", restored, self.path.display(), dropped)
+            }
+            Err(err) => warn!("Failed restoring the mempool from {}: {}", self.path.display(), err),
+        }
+    }
+
+    fn save(&self) {
+        match self.mining_manager.save_mempool(&self.path) {
+            Ok(count) => info!("Saved {} mempool transactions to {}", count, self.path.display()),
+            Err(err) => warn!("Failed saving the mempool to {}: {}", self.path.display(), err),
+        }
+    }
+}
+
+impl AsyncService for MempoolPersistenceService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        MEMPOOL_PERSISTENCE_SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", MEMPOOL_PERSISTENCE_SERVICE);
+        let shutdown_signal = self.shutdown.listener.clone();
+        Box::pin(async move {
+            self.load().await;
+            loop {
+                select! {
+                    _ = sleep(SAVE_INTERVAL) => self.save(),
+                    _ = shutdown_signal.clone() => break,
+                }
+            }
+            self.save();
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", MEMPOOL_PERSISTENCE_SERVICE);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} stopping", MEMPOOL_PERSISTENCE_SERVICE);
+        Box::pin(async move {
+            trace!("{} exiting", MEMPOOL_PERSISTENCE_SERVICE);
+            Ok(())
+        })
+    }
+}
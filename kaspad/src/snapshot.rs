@@ -0,0 +1,129 @@
+use futures::executor::block_on;
+use kaspa_consensus::consensus::{factory::MultiConsensusManagementStore, Consensus};
+use kaspa_consensus_core::{api::ConsensusApi, config::Config};
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensusmanager::{ConsensusFactory, SessionLock};
+use kaspa_core::info;
+use kaspa_database::prelude::open_db_read_only;
+use kaspa_utxosnapshot::{export_snapshot, import_snapshot, SnapshotError, SnapshotResult};
+use std::{fs, fs::File, ops::Deref, path::Path, sync::Arc};
+
+/// Exports a UTXO set snapshot of the active consensus to `path`. The node must not be running
+pub fn export_utxo_snapshot(meta_db_dir: &Path, consensus_db_dir: &Path, config: &Config, path: &Path) -> SnapshotResult<()> {
+    let consensus = open_active_consensus_read_only(meta_db_dir, consensus_db_dir, config)?;
+    if consensus.pruning_point() == Some(config.genesis.hash) {
+        return Err(SnapshotError::MissingPruningPoint);
+    }
+
+    let file = File::create(path)?;
+    match export_snapshot(&consensus, &config.network_name(), file) {
+        Ok(summary) => {
+            info!("Exported the UTXO set snapshot of pruning point {} to {}", summary.pruning_point, path.display());
+            Ok(())
+        }
+        Err(err) => {
+            // Do not leave a partial snapshot behind
+            let _ = fs::remove_file(path);
+            Err(err)
+        }
+    }
+}
+
+/// Opens the existing active consensus over read-only databases (as done by dbtool), so that exporting neither
+/// creates a new consensus entry nor processes genesis. Fails if the node has no active consensus
+fn open_active_consensus_read_only(meta_db_dir: &Path, consensus_db_dir: &Path, config: &Config) -> SnapshotResult<Consensus> {
+    if !meta_db_dir.exists() {
+        return Err(SnapshotError::MissingActiveConsensus);
+    }
+    let management_store = MultiConsensusManagementStore::new_read_only(open_db_read_only(meta_db_dir.to_path_buf()));
+    let entry = management_store.current_consensus_entry().ok().flatten().ok_or(SnapshotError::MissingActiveConsensus)?;
+    let db = open_db_read_only(consensus_db_dir.join(entry.directory_name()));
+
+    let mut config = config.clone();
+    config.process_genesis = false;
+    let (dummy_notification_sender, _) = async_channel::unbounded();
+    let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
+    Ok(Consensus::new(db, Arc::new(config), SessionLock::new(), notification_root, Default::default()))
+}
+
+/// Bootstraps a new node from the UTXO set snapshot at `path`. The snapshot is imported into a staging consensus
+/// which becomes the active consensus once the import completes. The snapshot is ignored if the active consensus
+/// has already progressed beyond genesis, so the setting can safely remain in place across restarts.
+pub fn import_utxo_snapshot(factory: &dyn ConsensusFactory, config: &Config, path: &Path) -> SnapshotResult<()> {
+    {
+        let (consensus, _ctl) = factory.new_active_consensus();
+        if block_on(consensus.session()).get_headers_selected_tip() != config.genesis.hash {
+            info!("The node is already bootstrapped, ignoring the UTXO set snapshot {}", path.display());
+            return Ok(());
+        }
+    }
+
+    let file = File::open(path)?;
+    let (consensus, ctl) = factory.new_staging_consensus();
+    let handles = ctl.start();
+    let result = block_on(async {
+        let session = consensus.session().await;
+        // Light nodes do not maintain a UTXO set
+        import_snapshot(session.deref(), &config.network_name(), file, !config.is_light).await
+    });
+    ctl.stop();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // Release the staging consensus so that its DB can be reopened (or deleted) below
+    drop(consensus);
+
+    match result {
+        Ok(summary) => {
+            ctl.make_active();
+            info!("Bootstrapped from the UTXO set snapshot of pruning point {}", summary.pruning_point);
+            Ok(())
+        }
+        Err(err) => {
+            ctl.delete();
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::{consensus::factory::Factory, params::MAINNET_PARAMS};
+    use kaspa_database::{prelude::open_db, utils::get_kaspa_tempdir};
+
+    #[test]
+    fn test_export_requires_an_active_consensus() {
+        let config = Config::new(MAINNET_PARAMS);
+        let tempdir = get_kaspa_tempdir();
+        let (meta_db_dir, consensus_db_dir) = (tempdir.path().join("meta"), tempdir.path().join("consensus"));
+        let snapshot_path = tempdir.path().join("snapshot.bin");
+        let export = || export_utxo_snapshot(&meta_db_dir, &consensus_db_dir, &config, &snapshot_path);
+
+        // No databases at all
+        assert!(matches!(export(), Err(SnapshotError::MissingActiveConsensus)));
+
+        // Initialized metadata with no consensus entry, which must not be created by the export
+        MultiConsensusManagementStore::new(open_db(meta_db_dir.clone(), true, 1));
+        assert!(matches!(export(), Err(SnapshotError::MissingActiveConsensus)));
+        let management_store = MultiConsensusManagementStore::new_read_only(open_db_read_only(meta_db_dir.clone()));
+        assert!(management_store.current_consensus_entry().unwrap().is_none());
+        drop(management_store);
+
+        // An active consensus which did not advance its pruning point yet is opened but has nothing to export
+        {
+            let (notification_sender, _) = async_channel::unbounded();
+            let factory = Factory::new(
+                open_db(meta_db_dir.clone(), true, 1),
+                &config,
+                consensus_db_dir.clone(),
+                1,
+                Arc::new(ConsensusNotificationRoot::new(notification_sender)),
+                Default::default(),
+            );
+            let _ = factory.new_active_consensus();
+        }
+        assert!(matches!(export(), Err(SnapshotError::MissingPruningPoint)));
+        assert!(!snapshot_path.exists());
+    }
+}
@@ -184,18 +184,32 @@ impl TransactionsSelector {
     /// calc_tx_value calculates a value to be used in transaction selection.
     /// The higher the number the more likely it is that the transaction will be
     /// included in the block.
+    ///
+    /// The value derives from the fee rate of the candidate package rather than
+    /// from the fee rate of the transaction alone, so a low fee transaction having
+    /// a high fee descendant in the mempool (child pays for parent) is valued as
+    /// the package including both. The block mass limit still only accounts for
+    /// the mass of the transaction itself, sig ops being part of the mass.
     fn calc_tx_value(&self, transaction: &CandidateTransaction) -> f64 {
-        let mass_limit = self.policy.max_block_mass as f64;
-        let mass = transaction.calculated_mass as f64;
-        let fee = transaction.calculated_fee as f64;
+        let value = Self::calc_native_tx_value(transaction.package_fee_rate(), self.policy.max_block_mass);
         if transaction.tx.subnetwork_id.is_builtin_or_native() {
-            fee / mass / mass_limit
+            value
         } else {
             // TODO: Replace with real gas once implemented
             let gas_limit = u64::MAX as f64;
-            fee / mass / mass_limit + transaction.tx.gas as f64 / gas_limit
+            value + transaction.tx.gas as f64 / gas_limit
         }
     }
+
+    fn calc_native_tx_value(fee_rate: f64, max_block_mass: u64) -> f64 {
+        fee_rate / max_block_mass as f64
+    }
+
+    /// Returns the weight of a native transaction with `fee_rate` in the random selection. The probability
+    /// of a candidate to be drawn is its weight divided by the sum of the weights of all candidates.
+    pub(crate) fn selection_weight(fee_rate: f64, max_block_mass: u64) -> f64 {
+        SelectableTransaction::new(Self::calc_native_tx_value(fee_rate, max_block_mass), 0, ALPHA).p
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,287 @@
+use crate::block_template::selector::TransactionsSelector;
+use std::collections::VecDeque;
+
+/// Probability of inclusion in the next block targeted by the priority bucket
+const PRIORITY_INCLUSION_PROBABILITY: f64 = 0.9;
+
+/// Inclusion time targeted by the normal bucket, in seconds
+const NORMAL_TARGET_SECONDS: f64 = 60.0;
+
+/// Inclusion time targeted by the low bucket, in seconds
+const LOW_TARGET_SECONDS: f64 = 3600.0;
+
+/// Number of recently included transactions kept in the inclusion statistics
+const INCLUSION_STATS_CAPACITY: usize = 1000;
+
+/// Minimum number of recently included transactions paying at least some fee rate
+/// for their inclusion delays to be accounted for in the estimate of this fee rate
+const MIN_INCLUSION_SAMPLES: usize = 10;
+
+/// Maximum number of doublings of the upper bound when searching a fee rate
+const MAX_FEE_RATE_DOUBLINGS: usize = 64;
+
+/// Number of bisection steps when searching a fee rate
+const FEE_RATE_BISECTION_STEPS: usize = 64;
+
+/// A fee rate, in sompi per gram of transaction mass, along with the expected delay for a
+/// transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeRateBucket {
+    pub fee_rate: f64,
+    pub estimated_seconds: f64,
+}
+
+/// Fee rate estimates for inclusion with priority, with a normal delay and with a low delay
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeEstimate {
+    /// Fee rate giving a transaction a high probability to be included in the next block
+    pub priority_bucket: FeeRateBucket,
+    /// Fee rate for inclusion within about a minute
+    pub normal_bucket: FeeRateBucket,
+    /// Fee rate for inclusion within about an hour
+    pub low_bucket: FeeRateBucket,
+}
+
+/// Fee rates and inclusion delays of the transactions recently accepted in blocks after having waited in the mempool
+#[derive(Default)]
+pub(crate) struct InclusionStats {
+    /// Fee rate and waited DAA score of each transaction, from the oldest to the most recent inclusion
+    samples: VecDeque<(f64, u64)>,
+}
+
+impl InclusionStats {
+    pub(crate) fn record(&mut self, fee_rate: f64, waited_daa_score: u64) {
+        if self.samples.len() == INCLUSION_STATS_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((fee_rate, waited_daa_score));
+    }
+
+    /// Returns the mean delay, in DAA score, with which the recent transactions paying at least `fee_rate`
+    /// were included, if there are enough of them.
+    fn mean_delay(&self, fee_rate: f64) -> Option<f64> {
+        let (count, total) = self
+            .samples
+            .iter()
+            .filter(|(sample_fee_rate, _)| *sample_fee_rate >= fee_rate)
+            .fold((0usize, 0u64), |(count, total), (_, waited)| (count + 1, total + waited));
+        (count >= MIN_INCLUSION_SAMPLES).then(|| total as f64 / count as f64)
+    }
+}
+
+/// Estimates the delay for a transaction paying some fee rate to be included in a block.
+///
+/// The estimate combines:
+///
+/// - the mass of the mempool transactions paying a higher fee rate, which are expected to be included first,
+/// - the probability for the transaction to be drawn by the [`TransactionsSelector`] among the block template
+///   candidates,
+/// - the delays with which recent transactions paying at least the same fee rate were included in blocks.
+pub(crate) struct FeeEstimator<'a> {
+    max_block_mass: u64,
+    /// Target time per block, in seconds
+    block_seconds: f64,
+    minimum_fee_rate: f64,
+    /// Fee rate and mass of all mempool transactions, by decreasing fee rate, along with the cumulative mass
+    mempool: Vec<(f64, u64)>,
+    /// Total selection weight of the block template candidates
+    candidates_weight: f64,
+    candidates_mass: u64,
+    candidates_count: usize,
+    /// Lowest fee rate of the block template candidates
+    candidates_minimum_fee_rate: f64,
+    inclusion_stats: &'a InclusionStats,
+}
+
+impl<'a> FeeEstimator<'a> {
+    /// Builds an estimator for the mempool transactions `mempool` and the block template candidates `candidates`,
+    /// both given as fee rate and mass pairs, `mempool` being ordered by decreasing fee rate.
+    pub(crate) fn new(
+        max_block_mass: u64,
+        target_milliseconds_per_block: u64,
+        minimum_fee_rate: f64,
+        mempool: impl Iterator<Item = (f64, u64)>,
+        candidates: impl Iterator<Item = (f64, u64)>,
+        inclusion_stats: &'a InclusionStats,
+    ) -> Self {
+        let mempool = mempool
+            .scan(0u64, |cumulative_mass, (fee_rate, mass)| {
+                *cumulative_mass += mass;
+                Some((fee_rate, *cumulative_mass))
+            })
+            .collect();
+        let (candidates_weight, candidates_mass, candidates_count, candidates_minimum_fee_rate) =
+            candidates.fold((0.0, 0, 0, f64::MAX), |(weight, total_mass, count, minimum_fee_rate), (fee_rate, mass)| {
+                (
+                    weight + TransactionsSelector::selection_weight(fee_rate, max_block_mass),
+                    total_mass + mass,
+                    count + 1,
+                    minimum_fee_rate.min(fee_rate),
+                )
+            });
+        Self {
+            max_block_mass,
+            block_seconds: target_milliseconds_per_block as f64 / 1000.0,
+            minimum_fee_rate,
+            mempool,
+            candidates_weight,
+            candidates_mass,
+            candidates_count,
+            candidates_minimum_fee_rate,
+            inclusion_stats,
+        }
+    }
+
+    pub(crate) fn estimate(&self) -> FeeEstimate {
+        let priority_bucket = self.bucket(self.block_seconds / PRIORITY_INCLUSION_PROBABILITY);
+        let normal_bucket = self.bucket(NORMAL_TARGET_SECONDS);
+        let low_bucket = self.bucket(LOW_TARGET_SECONDS);
+        // Make sure the buckets are ordered whatever the irregularities of the inclusion statistics
+        let normal_bucket = if normal_bucket.fee_rate > priority_bucket.fee_rate { priority_bucket } else { normal_bucket };
+        let low_bucket = if low_bucket.fee_rate > normal_bucket.fee_rate { normal_bucket } else { low_bucket };
+        FeeEstimate { priority_bucket, normal_bucket, low_bucket }
+    }
+
+    /// Returns the lowest fee rate, no lower than the minimum fee rate, whose expected inclusion delay is at most `target_seconds`
+    fn bucket(&self, target_seconds: f64) -> FeeRateBucket {
+        let mut low = self.minimum_fee_rate;
+        if self.estimated_seconds(low) <= target_seconds {
+            return FeeRateBucket { fee_rate: low, estimated_seconds: self.estimated_seconds(low) };
+        }
+        let mut high = self.mempool.first().map_or(low, |(fee_rate, _)| fee_rate.max(low)) * 2.0;
+        for _ in 0..MAX_FEE_RATE_DOUBLINGS {
+            if self.estimated_seconds(high) <= target_seconds {
+                break;
+            }
+            low = high;
+            high *= 2.0;
+        }
+        for _ in 0..FEE_RATE_BISECTION_STEPS {
+            let middle = (low + high) / 2.0;
+            if self.estimated_seconds(middle) <= target_seconds {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        FeeRateBucket { fee_rate: high, estimated_seconds: self.estimated_seconds(high) }
+    }
+
+    /// Returns the expected delay, in seconds, for a transaction paying `fee_rate` to be included in a block
+    fn estimated_seconds(&self, fee_rate: f64) -> f64 {
+        let mempool_seconds = self.estimated_blocks(fee_rate) * self.block_seconds;
+        match self.inclusion_stats.mean_delay(fee_rate) {
+            Some(delay) => (mempool_seconds + delay.max(1.0) * self.block_seconds) / 2.0,
+            None => mempool_seconds,
+        }
+    }
+
+    /// Returns the expected number of blocks until a transaction paying `fee_rate` gets included, according to the mempool
+    fn estimated_blocks(&self, fee_rate: f64) -> f64 {
+        // The transaction is assumed to have the average mass of the candidates it competes with
+        let mass = (self.candidates_mass / self.candidates_count.max(1) as u64).max(1);
+        if self.candidates_mass + mass <= self.max_block_mass {
+            return 1.0;
+        }
+
+        // Blocks needed for including the transactions paying a higher fee rate
+        let index = self.mempool.partition_point(|(mempool_fee_rate, _)| *mempool_fee_rate > fee_rate);
+        let mass_ahead = if index == 0 { 0 } else { self.mempool[index - 1].1 };
+        let queue_blocks = 1.0 + mass_ahead as f64 / self.max_block_mass as f64;
+
+        if fee_rate >= self.candidates_minimum_fee_rate {
+            (1.0 / self.selection_probability(fee_rate, mass)).max(queue_blocks)
+        } else {
+            // The transaction is not a candidate yet: it waits for the transactions ahead of it to be included
+            // and then competes like the current lowest candidates
+            queue_blocks - 1.0 + 1.0 / self.selection_probability(self.candidates_minimum_fee_rate, mass)
+        }
+    }
+
+    /// Returns the probability for a candidate paying `fee_rate` to be drawn at least once by the selector
+    /// in the number of draws filling a block
+    fn selection_probability(&self, fee_rate: f64, mass: u64) -> f64 {
+        let weight = TransactionsSelector::selection_weight(fee_rate, self.max_block_mass);
+        let draws = self.max_block_mass as f64 / mass as f64;
+        1.0 - (1.0 - weight / (self.candidates_weight + weight)).powf(draws)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_BLOCK_MASS: u64 = 500_000;
+    const TARGET_MILLISECONDS_PER_BLOCK: u64 = 1000;
+    const MINIMUM_FEE_RATE: f64 = 1.0;
+    const TX_MASS: u64 = 2_000;
+
+    #[test]
+    fn test_empty_mempool_estimate() {
+        let stats = InclusionStats::default();
+        let estimator = FeeEstimator::new(
+            MAX_BLOCK_MASS,
+            TARGET_MILLISECONDS_PER_BLOCK,
+            MINIMUM_FEE_RATE,
+            std::iter::empty(),
+            std::iter::empty(),
+            &stats,
+        );
+        let bucket = FeeRateBucket { fee_rate: MINIMUM_FEE_RATE, estimated_seconds: 1.0 };
+        assert_eq!(FeeEstimate { priority_bucket: bucket, normal_bucket: bucket, low_bucket: bucket }, estimator.estimate());
+    }
+
+    #[test]
+    fn test_congested_mempool_estimate() {
+        // Enough transactions for filling 100 blocks, with fee rates decreasing from 100 to 1
+        let tx_count = 100 * MAX_BLOCK_MASS / TX_MASS;
+        let mempool = (0..tx_count).map(|i| (100.0 - 99.0 * i as f64 / tx_count as f64, TX_MASS)).collect::<Vec<_>>();
+        let candidates = mempool.iter().take((4 * MAX_BLOCK_MASS / TX_MASS) as usize).copied().collect::<Vec<_>>();
+        let stats = InclusionStats::default();
+        let estimator = FeeEstimator::new(
+            MAX_BLOCK_MASS,
+            TARGET_MILLISECONDS_PER_BLOCK,
+            MINIMUM_FEE_RATE,
+            mempool.into_iter(),
+            candidates.into_iter(),
+            &stats,
+        );
+        let estimate = estimator.estimate();
+
+        assert!(estimate.priority_bucket.fee_rate > 100.0, "{estimate:?}");
+        assert!(estimate.priority_bucket.estimated_seconds <= 1.0 / PRIORITY_INCLUSION_PROBABILITY + 1e-9, "{estimate:?}");
+        assert!(estimate.normal_bucket.fee_rate < estimate.priority_bucket.fee_rate, "{estimate:?}");
+        assert!(estimate.normal_bucket.fee_rate > MINIMUM_FEE_RATE, "{estimate:?}");
+        assert!(estimate.normal_bucket.estimated_seconds <= NORMAL_TARGET_SECONDS, "{estimate:?}");
+        // About 100 blocks are enough for emptying the mempool
+        assert_eq!(MINIMUM_FEE_RATE, estimate.low_bucket.fee_rate, "{estimate:?}");
+        assert!(estimate.low_bucket.estimated_seconds < 2.0 * 100.0, "{estimate:?}");
+    }
+
+    #[test]
+    fn test_inclusion_stats() {
+        let mut stats = InclusionStats::default();
+        for i in 0..INCLUSION_STATS_CAPACITY as u64 + 10 {
+            stats.record(if i % 2 == 0 { 1.0 } else { 10.0 }, i % 2 * 100);
+        }
+        assert_eq!(INCLUSION_STATS_CAPACITY, stats.samples.len());
+        assert_eq!(Some(100.0), stats.mean_delay(5.0));
+        assert_eq!(Some(50.0), stats.mean_delay(1.0));
+        assert_eq!(None, stats.mean_delay(20.0));
+
+        // Transactions at the minimum fee rate recently waited 200 blocks
+        let mut stats = InclusionStats::default();
+        (0..MIN_INCLUSION_SAMPLES).for_each(|_| stats.record(MINIMUM_FEE_RATE, 200));
+        let estimator = FeeEstimator::new(
+            MAX_BLOCK_MASS,
+            TARGET_MILLISECONDS_PER_BLOCK,
+            MINIMUM_FEE_RATE,
+            std::iter::empty(),
+            std::iter::empty(),
+            &stats,
+        );
+        let estimate = estimator.estimate();
+        assert_eq!(FeeRateBucket { fee_rate: MINIMUM_FEE_RATE, estimated_seconds: 100.5 }, estimate.low_bucket);
+        assert!(estimate.normal_bucket.fee_rate > MINIMUM_FEE_RATE, "{estimate:?}");
+    }
+}
@@ -1,6 +1,7 @@
 mod block_template;
 pub(crate) mod cache;
 pub mod errors;
+pub mod fee_estimate;
 pub mod manager;
 mod manager_tests;
 pub mod mempool;
@@ -1,19 +1,24 @@
 // TODO: add integration tests
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use crate::{
     block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
+    fee_estimate::FeeEstimate,
     mempool::{
         config::Config,
-        tx::{Orphan, Priority},
+        errors::PersistenceResult,
+        persistence::{load_transactions, save_transactions},
+        tx::{Orphan, Priority, RbfPolicy},
         Mempool,
     },
     model::{
         candidate_tx::CandidateTransaction,
+        local_txs::LocalTransactionStatus,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        tx_insert::{TransactionAcceptance, TransactionInsertion},
     },
 };
 use kaspa_consensus_core::{
@@ -23,13 +28,17 @@ use kaspa_consensus_core::{
     errors::block::RuleError,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
-use kaspa_core::error;
+use kaspa_consensus_notify::{notification::MempoolRemovalReason, root::ConsensusNotificationRoot};
+use kaspa_core::{debug, error};
+use kaspa_notify::notifier::Notify;
 use parking_lot::{Mutex, RwLock};
 
 pub struct MiningManager {
     block_template_builder: BlockTemplateBuilder,
     block_template_cache: Mutex<BlockTemplateCache>,
     mempool: RwLock<Mempool>,
+    /// Receives the notifications of the transactions added to and removed from the mempool, if any
+    notification_root: Option<Arc<ConsensusNotificationRoot>>,
 }
 
 impl MiningManager {
@@ -44,10 +53,33 @@ impl MiningManager {
     }
 
     pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>) -> Self {
+        Self::with_notification_root(config, cache_lifetime, None)
+    }
+
+    /// Builds a mining manager sending a notification to `notification_root` for every transaction added to
+    /// or removed from the mempool.
+    pub fn with_notification_root(
+        config: Config,
+        cache_lifetime: Option<u64>,
+        notification_root: Option<Arc<ConsensusNotificationRoot>>,
+    ) -> Self {
         let block_template_builder = BlockTemplateBuilder::new(config.maximum_mass_per_block);
-        let mempool = RwLock::new(Mempool::new(config));
+        let mut mempool = Mempool::new(config);
+        if notification_root.is_some() {
+            mempool.enable_notifications();
+        }
+        let mempool = RwLock::new(mempool);
         let block_template_cache = Mutex::new(BlockTemplateCache::new(cache_lifetime));
-        Self { block_template_builder, block_template_cache, mempool }
+        Self { block_template_builder, block_template_cache, mempool, notification_root }
+    }
+
+    /// Sends the notifications of the mempool changes recorded since the previous call
+    fn notify_mempool_changes(&self, mempool: &mut Mempool) {
+        if let Some(notification_root) = self.notification_root.as_ref() {
+            for notification in mempool.take_notifications() {
+                let _ = notification_root.notify(notification);
+            }
+        }
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -83,7 +115,7 @@ impl MiningManager {
                 Err(BuilderError::ConsensusError(RuleError::InvalidTransactionsInNewBlock(invalid_transactions))) => {
                     let mut mempool_write = self.mempool.write();
                     invalid_transactions.iter().for_each(|(x, _)| {
-                        let removal_result = mempool_write.remove_transaction(x, true);
+                        let removal_result = mempool_write.remove_transaction(x, true, MempoolRemovalReason::Invalid);
                         if let Err(err) = removal_result {
                             // Original golang comment:
                             // mempool.remove_transactions might return errors in situations that are perfectly fine in this context.
@@ -93,6 +125,7 @@ impl MiningManager {
                             error!("Error from mempool.remove_transactions: {:?}", err);
                         }
                     });
+                    self.notify_mempool_changes(&mut mempool_write);
                 }
                 Err(err) => {
                     return Err(err)?;
@@ -119,6 +152,9 @@ impl MiningManager {
     /// adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// If the transaction double spends a mempool transaction, `rbf_policy` defines whether
+    /// it may replace it.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub fn validate_and_insert_transaction(
         &self,
@@ -126,8 +162,12 @@ impl MiningManager {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        Ok(self.mempool.write().validate_and_insert_transaction(consensus, transaction, priority, orphan)?)
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        let mut mempool = self.mempool.write();
+        let result = mempool.validate_and_insert_transaction(consensus, transaction, priority, orphan, rbf_policy);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(result?)
     }
 
     /// Exposed only for tests. Ordinary users should let the mempool create the mutable tx internally
@@ -138,8 +178,44 @@ impl MiningManager {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        let mut mempool = self.mempool.write();
+        let result = mempool.validate_and_insert_mutable_transaction(consensus, transaction, priority, orphan, rbf_policy);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(result?)
+    }
+
+    /// validate_and_insert_transaction_package validates the given package of chained transactions, ordered
+    /// so that every transaction follows its parents, and adds either all or none of them to the mempool.
+    ///
+    /// Returns the accepted transactions, the package transactions first followed by the transactions
+    /// unorphaned by the package.
+    pub fn validate_and_insert_transaction_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        Ok(self.mempool.write().validate_and_insert_mutable_transaction(consensus, transaction, priority, orphan)?)
+        let mut mempool = self.mempool.write();
+        let result = mempool.validate_and_insert_transaction_package(consensus, transactions, priority);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(result?)
+    }
+
+    /// test_accept_transaction validates the given transaction exactly as validate_and_insert_transaction
+    /// would, without inserting it into the mempool.
+    ///
+    /// Returns the populated transaction, along with the id of the mempool transaction it would replace
+    /// according to `rbf_policy`, if any.
+    pub fn test_accept_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: Transaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionAcceptance> {
+        Ok(self.mempool.read().test_accept_transaction(consensus, MutableTransaction::from_tx(transaction), priority, rbf_policy)?)
     }
 
     /// Try to return a mempool transaction by its id.
@@ -184,17 +260,49 @@ impl MiningManager {
         self.mempool.read().transaction_count(include_transaction_pool, include_orphan_pool)
     }
 
+    /// Returns the effective mempool policy
+    pub fn mempool_config(&self) -> Arc<Config> {
+        self.mempool.read().config()
+    }
+
+    /// Returns the fee rates, in sompi per gram of mass, expected to get a transaction included with priority,
+    /// within about a minute and within about an hour
+    pub fn get_fee_estimate(&self) -> FeeEstimate {
+        self.mempool.read().get_fee_estimate()
+    }
+
     pub fn handle_new_block_transactions(
         &self,
         consensus: &dyn ConsensusApi,
         block_transactions: &[Transaction],
     ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
         // TODO: should use tx acceptance data to verify that new block txs are actually accepted into virtual state.
-        Ok(self.mempool.write().handle_new_block_transactions(consensus, block_transactions)?)
+        let mut mempool = self.mempool.write();
+        let result = mempool.handle_new_block_transactions(consensus, block_transactions);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(result?)
     }
 
     pub fn revalidate_high_priority_transactions(&self, consensus: &dyn ConsensusApi) -> MiningManagerResult<Vec<TransactionId>> {
-        Ok(self.mempool.write().revalidate_high_priority_transactions(consensus)?)
+        let mut mempool = self.mempool.write();
+        let result = mempool.revalidate_high_priority_transactions(consensus);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(result?)
+    }
+
+    /// Revalidates the high priority transactions, submitted locally, and returns the valid ones due for a
+    /// rebroadcast. The interval between two rebroadcasts of a transaction doubles every time, up to a maximum.
+    pub fn rebroadcast_local_transactions(&self, consensus: &dyn ConsensusApi) -> MiningManagerResult<Vec<TransactionId>> {
+        let mut mempool = self.mempool.write();
+        let result = mempool.revalidate_high_priority_transactions(consensus);
+        self.notify_mempool_changes(&mut mempool);
+        Ok(mempool.take_local_transactions_to_rebroadcast(result?))
+    }
+
+    /// Returns the status of a transaction submitted locally, while it is in the mempool and for a day
+    /// after it left it.
+    pub fn get_local_transaction_status(&self, transaction_id: &TransactionId) -> Option<LocalTransactionStatus> {
+        self.mempool.read().get_local_transaction_status(transaction_id)
     }
 
     /// is_transaction_output_dust returns whether or not the passed transaction output
@@ -207,4 +315,44 @@ impl MiningManager {
     pub fn is_transaction_output_dust(&self, transaction_output: &TransactionOutput) -> bool {
         self.mempool.read().is_transaction_output_dust(transaction_output)
     }
+
+    /// Saves all the transactions of the mempool, orphans included, to the file at `path`.
+    ///
+    /// Returns the number of saved transactions.
+    pub fn save_mempool(&self, path: &Path) -> PersistenceResult<usize> {
+        let transactions = self.mempool.read().get_persisted_transactions();
+        save_transactions(path, &transactions)?;
+        Ok(transactions.len())
+    }
+
+    /// Loads the transactions saved by [`Self::save_mempool`] in the file at `path` and inserts them in the mempool
+    /// with their original priority, revalidating them against the current virtual UTXO state. Transactions which
+    /// are no longer valid, for instance because they were included in a block or double spent in the meantime,
+    /// are dropped.
+    ///
+    /// Returns the numbers of restored and dropped transactions.
+    pub fn load_mempool(&self, consensus: &dyn ConsensusApi, path: &Path) -> PersistenceResult<(usize, usize)> {
+        let transactions = load_transactions(path)?;
+        let mut restored = 0;
+        let mut dropped = 0;
+        for persisted in transactions {
+            let transaction_id = persisted.transaction.id();
+            // An orphan may remain orphan but a transaction of the pool must still have all its inputs available
+            let orphan = if persisted.is_orphan { Orphan::Allowed } else { Orphan::Forbidden };
+            match self.validate_and_insert_transaction(
+                consensus,
+                persisted.transaction,
+                persisted.priority,
+                orphan,
+                RbfPolicy::Forbidden,
+            ) {
+                Ok(_) => restored += 1,
+                Err(err) => {
+                    debug!("Dropping saved mempool transaction {}: {}", transaction_id, err);
+                    dropped += 1;
+                }
+            }
+        }
+        Ok((restored, dropped))
+    }
 }
@@ -6,10 +6,10 @@ mod tests {
         manager::MiningManager,
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
-            errors::RuleError,
-            tx::{Orphan, Priority},
+            errors::{ReplacementError, RuleError},
+            tx::{Orphan, Priority, RbfPolicy},
         },
-        model::candidate_tx::CandidateTransaction,
+        model::{candidate_tx::CandidateTransaction, tx_insert::TransactionInsertion},
         testutils::consensus_mock::ConsensusMock,
     };
     use kaspa_addresses::{Address, Prefix, Version};
@@ -25,12 +25,17 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use kaspa_consensus_notify::{
+        notification::{MempoolRemovalReason, Notification},
+        root::ConsensusNotificationRoot,
+    };
     use kaspa_hashes::Hash;
+    use kaspa_notify::{events::EventType, scope::Scope, subscriber::SubscriptionManager};
     use kaspa_txscript::{
         pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, op_true_script},
     };
-    use std::sync::Arc;
+    use std::{iter::once, sync::Arc};
 
     const TARGET_TIME_PER_BLOCK: u64 = 1_000;
     const MAX_BLOCK_MASS: u64 = 500_000;
@@ -48,6 +53,7 @@ mod tests {
                 transaction.clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "inserting a valid transaction failed");
         }
@@ -96,6 +102,7 @@ mod tests {
             transaction_not_an_orphan.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "inserting the child transaction {} into the mempool failed", transaction_not_an_orphan.id());
         let (transactions_from_pool, _) = mining_manager.get_all_transactions(true, false);
@@ -127,6 +134,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         ));
 
         assert_eq!(
@@ -152,6 +160,7 @@ mod tests {
             transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "mempool should have accepted a valid transaction but did not");
 
@@ -161,6 +170,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double submit of the same transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDuplicate(transaction_id))) = result {
@@ -193,8 +203,13 @@ mod tests {
             transaction.id()
         );
 
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), transaction.clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the mempool should accept a valid transaction when it is able to populate its UTXO entries");
 
         let mut double_spending_transaction = transaction.clone();
@@ -210,6 +225,7 @@ mod tests {
             double_spending_transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double spend transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, transaction_id))) = result {
@@ -228,6 +244,482 @@ mod tests {
         }
     }
 
+    // test_replace_by_fee verifies that a transaction double-spending another transaction already in the mempool replaces
+    // it, along with its redeemers, only when allowed to and when paying a higher fee rate and a sufficient fee bump.
+    #[test]
+    fn test_replace_by_fee() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        let mining_manager = MiningManager::with_config(config.clone(), None);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KASPA]);
+        let transaction = create_transaction(&funding_tx, 1000);
+        let redeemer = create_transaction(&transaction, 1000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        for tx in [&transaction, &redeemer] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // A replacement is mandatory but the transaction double spends nothing
+        let unrelated = create_transaction_with_utxo_entry(0, 0);
+        let result = mining_manager.validate_and_insert_mutable_transaction(
+            consensus.as_ref(),
+            unrelated.clone(),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Mandatory,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectReplacement(ReplacementError::RejectNoConflict(id)))) if id == unrelated.id()),
+            "a mandatory replacement double spending no mempool transaction should be rejected but got {result:?}"
+        );
+
+        struct Test {
+            name: &'static str,
+            fee: u64,
+            rbf_policy: RbfPolicy,
+            maximum_replacement_evictions: u64,
+            expected: fn(&MiningManagerResult<TransactionInsertion>) -> bool,
+        }
+        let tests = [
+            Test {
+                name: "replacement forbidden",
+                fee: 3000,
+                rbf_policy: RbfPolicy::Forbidden,
+                maximum_replacement_evictions: 100,
+                expected: |result| {
+                    matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, _))))
+                },
+            },
+            Test {
+                name: "lower fee rate",
+                fee: 999,
+                rbf_policy: RbfPolicy::Mandatory,
+                maximum_replacement_evictions: 100,
+                expected: |result| {
+                    matches!(
+                        result,
+                        Err(MiningManagerError::MempoolError(RuleError::RejectReplacement(ReplacementError::RejectLowFeeRate(..))))
+                    )
+                },
+            },
+            Test {
+                name: "fee not covering the evicted redeemer",
+                fee: 1500,
+                rbf_policy: RbfPolicy::Allowed,
+                maximum_replacement_evictions: 100,
+                expected: |result| {
+                    matches!(
+                        result,
+                        Err(MiningManagerError::MempoolError(RuleError::RejectReplacement(ReplacementError::RejectInsufficientFee(
+                            ..
+                        ))))
+                    )
+                },
+            },
+            Test {
+                name: "too many evictions",
+                fee: 3000,
+                rbf_policy: RbfPolicy::Mandatory,
+                maximum_replacement_evictions: 1,
+                expected: |result| {
+                    matches!(
+                        result,
+                        Err(MiningManagerError::MempoolError(RuleError::RejectReplacement(ReplacementError::RejectTooManyEvictions(
+                            ..
+                        ))))
+                    )
+                },
+            },
+            Test {
+                name: "valid replacement",
+                fee: 3000,
+                rbf_policy: RbfPolicy::Mandatory,
+                maximum_replacement_evictions: 100,
+                expected: |result| result.is_ok(),
+            },
+        ];
+
+        for test in tests {
+            config.maximum_replacement_evictions = test.maximum_replacement_evictions;
+            let mining_manager = MiningManager::with_config(config.clone(), None);
+            for tx in [&transaction, &redeemer] {
+                mining_manager
+                    .validate_and_insert_transaction(
+                        consensus.as_ref(),
+                        tx.clone(),
+                        Priority::High,
+                        Orphan::Forbidden,
+                        RbfPolicy::Forbidden,
+                    )
+                    .unwrap();
+            }
+
+            let replacement = create_transaction(&funding_tx, test.fee);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                replacement.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                test.rbf_policy,
+            );
+            assert!((test.expected)(&result), "{}: unexpected result {result:?}", test.name);
+
+            let replaced = result.is_ok();
+            if let Ok(insertion) = result {
+                assert_eq!(insertion.replaced.map(|x| x.id()), Some(transaction.id()), "{}: wrong replaced transaction", test.name);
+            }
+            assert_eq!(replaced, mining_manager.has_transaction(&replacement.id(), true, false), "{}: replacement", test.name);
+            assert_eq!(!replaced, mining_manager.has_transaction(&transaction.id(), true, false), "{}: original", test.name);
+            assert_eq!(!replaced, mining_manager.has_transaction(&redeemer.id(), true, false), "{}: redeemer", test.name);
+        }
+    }
+
+    // test_accept_without_insertion verifies that testing the acceptance of a transaction reports the outcome
+    // validate_and_insert_transaction would have, while leaving the mempool untouched.
+    #[test]
+    fn test_accept_without_insertion() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KASPA]);
+        let transaction = create_transaction(&funding_tx, 1000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+
+        let acceptance = mining_manager
+            .test_accept_transaction(consensus.as_ref(), transaction.clone(), Priority::High, RbfPolicy::Forbidden)
+            .expect("the valid transaction should be accepted");
+        assert_eq!(acceptance.transaction.id(), transaction.id());
+        assert_eq!(acceptance.fee(), 1000);
+        assert_eq!(acceptance.mass(), consensus.calculate_transaction_mass(&transaction));
+        assert_eq!(acceptance.fee_rate(), 1000.0 / acceptance.mass() as f64);
+        assert!(acceptance.replaced.is_none());
+        assert_eq!(mining_manager.transaction_count(true, true), 0, "testing a transaction should not insert it");
+
+        // A transaction spending unknown outpoints would only be an orphan
+        let orphan = create_transaction(&create_transaction_without_input(vec![SOMPI_PER_KASPA]), 1000);
+        let result = mining_manager.test_accept_transaction(consensus.as_ref(), orphan, Priority::High, RbfPolicy::Forbidden);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectMissingOutpoint))),
+            "unexpected result {result:?}"
+        );
+        assert_eq!(mining_manager.transaction_count(true, true), 0, "testing an orphan should not insert it");
+
+        mining_manager
+            .validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::High,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            )
+            .unwrap();
+        let result =
+            mining_manager.test_accept_transaction(consensus.as_ref(), transaction.clone(), Priority::High, RbfPolicy::Forbidden);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectDuplicate(id))) if id == transaction.id()),
+            "unexpected result {result:?}"
+        );
+
+        // A valid replacement is reported along with the transaction it would replace, which stays in the mempool
+        let replacement = create_transaction(&funding_tx, 3000);
+        let result =
+            mining_manager.test_accept_transaction(consensus.as_ref(), replacement.clone(), Priority::High, RbfPolicy::Forbidden);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, _)))),
+            "unexpected result {result:?}"
+        );
+        let acceptance = mining_manager
+            .test_accept_transaction(consensus.as_ref(), replacement.clone(), Priority::High, RbfPolicy::Mandatory)
+            .expect("the valid replacement should be accepted");
+        assert_eq!(acceptance.replaced, Some(transaction.id()));
+        assert!(mining_manager.has_transaction(&transaction.id(), true, false), "the replaced transaction should stay in the mempool");
+        assert!(!mining_manager.has_transaction(&replacement.id(), true, true), "the replacement should not be inserted");
+    }
+
+    // test_transaction_package verifies that a package of chained transactions, longer than what the orphan pool can hold,
+    // is inserted as a whole, and that a package having any invalid transaction is rejected as a whole.
+    #[test]
+    fn test_transaction_package() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None);
+
+        const PACKAGE_LEN: usize = 120;
+        let mut package = vec![create_funded_transaction(&consensus, 0, 1000)];
+        while package.len() < PACKAGE_LEN {
+            package.push(create_transaction(package.last().unwrap(), 1000));
+        }
+        let unknown_parent = create_transaction_without_input(vec![SOMPI_PER_KASPA]);
+        let orphan = create_transaction(&unknown_parent, 1000);
+
+        struct Test {
+            name: &'static str,
+            transactions: Vec<Transaction>,
+            expected: fn(&MiningManagerResult<Vec<Arc<Transaction>>>) -> bool,
+        }
+        let tests = [
+            Test {
+                name: "empty package",
+                transactions: vec![],
+                expected: |result| matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectEmptyPackage))),
+            },
+            Test {
+                name: "duplicate transaction",
+                transactions: vec![package[0].clone(), package[1].clone(), package[0].clone()],
+                expected: |result| matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectPackageDuplicate(_)))),
+            },
+            Test {
+                name: "child before its parent",
+                transactions: package.iter().rev().cloned().collect(),
+                expected: |result| matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectPackageNotTopological))),
+            },
+            Test {
+                name: "orphan in the package",
+                transactions: package.iter().cloned().chain(once(orphan.clone())).collect(),
+                expected: |result| {
+                    matches!(
+                        result,
+                        Err(MiningManagerError::MempoolError(RuleError::RejectPackageTransaction(_, err)))
+                            if matches!(err.as_ref(), RuleError::RejectMissingOutpoint)
+                    )
+                },
+            },
+        ];
+        for test in tests {
+            let result = mining_manager.validate_and_insert_transaction_package(consensus.as_ref(), test.transactions, Priority::High);
+            assert!((test.expected)(&result), "{}: unexpected result {result:?}", test.name);
+            assert_eq!(mining_manager.transaction_count(true, true), 0, "{}: no transaction should be inserted", test.name);
+        }
+
+        let result = mining_manager.validate_and_insert_transaction_package(consensus.as_ref(), package.clone(), Priority::High);
+        let accepted = result.expect("the valid package should be accepted");
+        assert_eq!(accepted.iter().map(|x| x.id()).collect::<Vec<_>>(), package.iter().map(|x| x.id()).collect::<Vec<_>>());
+        assert_eq!(mining_manager.transaction_count(true, false), PACKAGE_LEN);
+
+        // A package transaction already in the mempool makes the whole package a duplicate
+        let extension = create_transaction(package.last().unwrap(), 1000);
+        let result = mining_manager.validate_and_insert_transaction_package(
+            consensus.as_ref(),
+            vec![package[PACKAGE_LEN - 1].clone(), extension.clone()],
+            Priority::High,
+        );
+        assert!(
+            matches!(
+                &result,
+                Err(MiningManagerError::MempoolError(RuleError::RejectPackageTransaction(_, err)))
+                    if matches!(err.as_ref(), RuleError::RejectDuplicate(_))
+            ),
+            "unexpected result {result:?}"
+        );
+        assert!(!mining_manager.has_transaction(&extension.id(), true, true));
+        assert_eq!(mining_manager.transaction_count(true, false), PACKAGE_LEN);
+    }
+
+    // test_evict_low_fee_rate_transactions verifies that a full mempool evicts its lowest fee rate low-priority transactions,
+    // along with their redeemers, for making room to a transaction having a higher fee rate.
+    #[test]
+    fn test_evict_low_fee_rate_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_transaction_count = 3;
+        let mining_manager = MiningManager::with_config(config, None);
+
+        let insert = |transaction: &Transaction, priority: Priority| {
+            mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            )
+        };
+        let in_mempool = |transaction: &Transaction| mining_manager.has_transaction(&transaction.id(), true, false);
+
+        // Fill the mempool, the redeemer having the highest fee rate
+        let high_fee_rate = create_funded_transaction(&consensus, 1, 2000);
+        let low_fee_rate = create_funded_transaction(&consensus, 2, 1000);
+        let redeemer = create_transaction(&low_fee_rate, 3000);
+        for transaction in [&high_fee_rate, &low_fee_rate, &redeemer] {
+            assert!(
+                insert(transaction, Priority::Low).is_ok(),
+                "the mempool should accept the valid transaction {}",
+                transaction.id()
+            );
+        }
+
+        // A transaction paying a fee rate lower than all mempool transactions is rejected
+        let lowest_fee_rate = create_funded_transaction(&consensus, 3, 500);
+        let result = insert(&lowest_fee_rate, Priority::Low);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectMempoolIsFullLowFeeRate(id, 3))) if id == lowest_fee_rate.id()),
+            "a full mempool should reject a transaction with the lowest fee rate but got {result:?}"
+        );
+
+        // A transaction paying a higher fee rate evicts the lowest fee rate transaction and its redeemer
+        let medium_fee_rate = create_funded_transaction(&consensus, 4, 1500);
+        assert!(insert(&medium_fee_rate, Priority::Low).is_ok(), "a full mempool should evict lower fee rate transactions");
+        assert!(in_mempool(&high_fee_rate) && in_mempool(&medium_fee_rate), "the highest fee rate transactions should be kept");
+        assert!(
+            !in_mempool(&low_fee_rate) && !in_mempool(&redeemer),
+            "the lowest fee rate transaction and its redeemer should be evicted"
+        );
+
+        // A high-priority transaction evicts the lowest fee rate low-priority transaction whatever its own fee rate
+        let filler = create_funded_transaction(&consensus, 5, 1800);
+        assert!(insert(&filler, Priority::Low).is_ok());
+        assert!(insert(&lowest_fee_rate, Priority::High).is_ok(), "a high-priority transaction should evict a low-priority one");
+        assert!(!in_mempool(&medium_fee_rate), "the lowest fee rate low-priority transaction should be evicted");
+        assert_eq!(3, mining_manager.transaction_count(true, false));
+    }
+
+    // test_package_evicts_low_fee_rate_transactions verifies that a package entering a full mempool evicts transactions
+    // having a lower fee rate than the whole package, and that a package of a lower fee rate is rejected with no eviction.
+    #[test]
+    fn test_package_evicts_low_fee_rate_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        config.maximum_transaction_count = 3;
+        let mining_manager = MiningManager::with_config(config, None);
+        let in_mempool = |transaction: &Transaction| mining_manager.has_transaction(&transaction.id(), true, false);
+
+        let transactions = [1000, 1200, 2000].map(|fee| create_funded_transaction(&consensus, fee, fee));
+        for transaction in transactions.iter() {
+            mining_manager
+                .validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    transaction.clone(),
+                    Priority::Low,
+                    Orphan::Forbidden,
+                    RbfPolicy::Forbidden,
+                )
+                .unwrap();
+        }
+
+        // A package paying a fee rate lower than all mempool transactions is rejected and evicts nothing
+        let parent = create_funded_transaction(&consensus, 1, 500);
+        let package = vec![parent.clone(), create_transaction(&parent, 600)];
+        let result = mining_manager.validate_and_insert_transaction_package(consensus.as_ref(), package.clone(), Priority::Low);
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectMempoolIsFullLowFeeRate(id, 3))) if id == package[1].id()),
+            "a full mempool should reject a package with the lowest fee rate but got {result:?}"
+        );
+        assert!(package.iter().all(|x| !in_mempool(x)), "no package transaction should be inserted");
+        assert!(transactions.iter().all(in_mempool), "no mempool transaction should be evicted");
+
+        // A package whose parent pays a low fee but with a higher overall fee rate evicts the lowest fee rate transactions
+        let parent = create_funded_transaction(&consensus, 2, 500);
+        let package = vec![parent.clone(), create_transaction(&parent, 3000)];
+        let result = mining_manager.validate_and_insert_transaction_package(consensus.as_ref(), package.clone(), Priority::Low);
+        assert!(result.is_ok(), "a full mempool should evict lower fee rate transactions but got {result:?}");
+        assert!(package.iter().all(in_mempool), "the package should be inserted");
+        assert!(!in_mempool(&transactions[0]) && !in_mempool(&transactions[1]), "the lowest fee rate transactions should be evicted");
+        assert!(in_mempool(&transactions[2]), "the highest fee rate transaction should be kept");
+        assert_eq!(3, mining_manager.transaction_count(true, false));
+    }
+
+    // test_block_candidates_by_fee_rate verifies that the block template candidates are the ready transactions with the
+    // highest fee rates.
+    #[test]
+    fn test_block_candidates_by_fee_rate() {
+        const TX_COUNT: u64 = 10;
+        let consensus = Arc::new(ConsensusMock::new());
+        let transactions = (0..TX_COUNT).map(|i| create_funded_transaction(&consensus, i, 1000 * (i + 1))).collect::<Vec<_>>();
+        let mut config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        // Leave room for 4 transactions of equal mass in the candidates
+        config.maximum_mass_per_block = consensus.calculate_transaction_mass(&transactions[0]);
+        let mining_manager = MiningManager::with_config(config, None);
+        for transaction in transactions.iter() {
+            mining_manager
+                .validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    transaction.clone(),
+                    Priority::Low,
+                    Orphan::Forbidden,
+                    RbfPolicy::Forbidden,
+                )
+                .unwrap();
+        }
+        // A redeemer having the highest fee rate is not ready but pulls its low fee parent in the candidates
+        let redeemer = create_transaction(&transactions[0], 100_000);
+        mining_manager
+            .validate_and_insert_transaction(
+                consensus.as_ref(),
+                redeemer.clone(),
+                Priority::Low,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            )
+            .unwrap();
+
+        let candidates = mining_manager.block_candidate_transactions();
+        let candidate_ids = candidates.iter().map(|x| x.tx.id()).collect::<Vec<_>>();
+        let expected_ids = once(transactions[0].id()).chain(transactions.iter().rev().take(3).map(|x| x.id())).collect::<Vec<_>>();
+        assert_eq!(expected_ids, candidate_ids, "the candidates should be the ready transactions with the highest package fee rates");
+        let parent = &candidates[0];
+        assert_eq!(1000 + 100_000, parent.package_fee);
+        assert_eq!(parent.calculated_mass + consensus.calculate_transaction_mass(&redeemer), parent.package_mass);
+        assert!(!candidate_ids.contains(&redeemer.id()), "a transaction with parents in the mempool should never be a candidate");
+    }
+
+    // test_save_and_load_mempool verifies that a saved mempool is restored with its priorities and orphans,
+    // the transactions which became invalid in the meantime being dropped along with their redeemers.
+    #[test]
+    fn test_save_and_load_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None);
+        let high_priority = create_funded_transaction(&consensus, 0, 1000);
+        let (parent, child) = create_parent_and_children_transactions(&consensus, vec![550 * SOMPI_PER_KASPA]);
+        let (invalidated, invalidated_child) = create_parent_and_children_transactions(&consensus, vec![600 * SOMPI_PER_KASPA]);
+        let orphan = create_transaction(&create_transaction_without_input(vec![700 * SOMPI_PER_KASPA]), 1000);
+        let transactions = [
+            (&high_priority, Priority::High),
+            (&parent, Priority::Low),
+            (&child, Priority::Low),
+            (&invalidated, Priority::Low),
+            (&invalidated_child, Priority::Low),
+            (&orphan, Priority::Low),
+        ];
+        for (transaction, priority) in transactions {
+            mining_manager
+                .validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    transaction.clone(),
+                    priority,
+                    Orphan::Allowed,
+                    RbfPolicy::Forbidden,
+                )
+                .unwrap();
+        }
+        assert_eq!(5, mining_manager.transaction_count(true, false));
+        assert_eq!(1, mining_manager.transaction_count(false, true));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mempool.dat");
+        assert_eq!(6, mining_manager.save_mempool(&path).unwrap());
+
+        // Simulate a double spend of a transaction while the node was down
+        consensus.set_status(invalidated.id(), Err(TxRuleError::MissingTxOutpoints));
+
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None);
+        assert_eq!((4, 2), mining_manager.load_mempool(consensus.as_ref(), &path).unwrap());
+        for transaction in [&high_priority, &parent, &child] {
+            assert!(mining_manager.has_transaction(&transaction.id(), true, false), "a valid transaction should be restored");
+        }
+        assert!(mining_manager.has_transaction(&orphan.id(), false, true), "an orphan should be restored as orphan");
+        for transaction in [&invalidated, &invalidated_child] {
+            assert!(!mining_manager.has_transaction(&transaction.id(), true, true), "an invalid transaction should be dropped");
+        }
+        let high_priority_ids = mining_manager.revalidate_high_priority_transactions(consensus.as_ref()).unwrap();
+        assert_eq!(vec![high_priority.id()], high_priority_ids, "the transaction priorities should be restored");
+    }
+
     // test_handle_new_block_transactions verifies that all the transactions in the block were successfully removed from the mempool.
     #[test]
     fn test_handle_new_block_transactions() {
@@ -242,6 +734,7 @@ mod tests {
                 transaction.tx.as_ref().clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "the insertion of a new valid transaction in the mempool failed");
         }
@@ -299,6 +792,7 @@ mod tests {
             transaction_in_the_mempool.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok());
 
@@ -317,6 +811,61 @@ mod tests {
         );
     }
 
+    // test_mempool_notifications verifies that every transaction entering or leaving the mempool is notified,
+    // along with the reason of its removal.
+    #[test]
+    fn test_mempool_notifications() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let (sender, receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(sender));
+        for event_type in [EventType::MempoolTransactionAdded, EventType::MempoolTransactionRemoved] {
+            let scope = Scope::with_addresses(event_type, vec![]).unwrap();
+            futures::executor::block_on(notification_root.start_notify(0, scope)).unwrap();
+        }
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        let mining_manager = MiningManager::with_notification_root(config, None, Some(notification_root));
+
+        let parent_tx = create_funded_transaction(&consensus, 0, 1000);
+        let child_tx = create_transaction(&parent_tx, 1000);
+        let double_spend_tx = create_transaction(&parent_tx, 2000);
+
+        // The child is first received as an orphan and gets unorphaned by its parent
+        for transaction in [child_tx.clone(), parent_tx.clone()] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction,
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction should succeed but returned {result:?}");
+        }
+
+        // A block accepts the parent and double spends the child
+        let block_transactions = build_block_transactions([parent_tx.clone(), double_spend_tx].iter());
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), &block_transactions);
+        assert!(result.is_ok(), "handling the block transactions should succeed but returned {result:?}");
+
+        let notifications = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|notification| match notification {
+                Notification::MempoolTransactionAdded(added) => (added.transaction.id(), added.is_orphan, None),
+                Notification::MempoolTransactionRemoved(removed) => {
+                    (removed.transaction.id(), removed.is_orphan, Some(removed.reason))
+                }
+                notification => panic!("unexpected notification {notification}"),
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (child_tx.id(), true, None),
+            (parent_tx.id(), false, None),
+            (child_tx.id(), true, Some(MempoolRemovalReason::OrphanResolved)),
+            (child_tx.id(), false, None),
+            (parent_tx.id(), false, Some(MempoolRemovalReason::Accepted)),
+            (child_tx.id(), false, Some(MempoolRemovalReason::DoubleSpent)),
+        ];
+        assert_eq!(expected, notifications);
+    }
+
     // test_orphan_transactions verifies that a transaction could be a part of a new block template only if it's not an orphan.
     #[test]
     fn test_orphan_transactions() {
@@ -330,8 +879,13 @@ mod tests {
         assert_eq!(parent_txs.len(), TX_PAIRS_COUNT);
         assert_eq!(child_txs.len(), TX_PAIRS_COUNT);
         for orphan in child_txs.iter() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), orphan.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                orphan.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid orphan transaction {}", orphan.id());
         }
         let (populated_txs, orphans) = mining_manager.get_all_transactions(true, true);
@@ -475,10 +1029,15 @@ mod tests {
         );
 
         // Add the remaining parent transaction into the mempool
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_txs[0].clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_txs[0].clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion of the remaining parent transaction in the mempool failed");
-        let unorphaned_txs = result.unwrap();
+        let unorphaned_txs = result.unwrap().accepted;
         let (populated_txs, orphans) = mining_manager.get_all_transactions(true, true);
         assert_eq!(
             unorphaned_txs.len(), SKIPPED_TXS,
@@ -581,8 +1140,13 @@ mod tests {
 
         // Try submit children while rejecting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Forbidden);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_err(), "mempool should reject an orphan transaction with {:?} when asked to do so", test.priority);
             if let Err(MiningManagerError::MempoolError(RuleError::RejectDisallowedOrphan(transaction_id))) = result {
                 assert_eq!(
@@ -602,8 +1166,13 @@ mod tests {
 
         // Try submit children while accepting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert_eq!(
                 test.should_enter_orphan_pool,
                 result.is_ok(),
@@ -611,8 +1180,8 @@ mod tests {
                 test.name,
                 test.insert_result()
             );
-            if let Ok(unorphaned_txs) = result {
-                assert!(unorphaned_txs.is_empty(), "mempool should unorphan no transaction since it only contains orphans");
+            if let Ok(insertion) = result {
+                assert!(insertion.accepted.is_empty(), "mempool should unorphan no transaction since it only contains orphans");
             } else if let Err(MiningManagerError::MempoolError(RuleError::RejectOrphanPoolIsFull(pool_len, config_len))) = result {
                 assert_eq!(
                     (config.maximum_orphan_transaction_count as usize, config.maximum_orphan_transaction_count),
@@ -631,10 +1200,15 @@ mod tests {
 
         // Submit all the parents
         for (i, (tx, test)) in parent_txs.iter().zip(tests.iter()).enumerate() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "mempool should accept a valid transaction with {:?} when asked to do so", test.priority,);
-            let unorphaned_txs = result.as_ref().unwrap();
+            let unorphaned_txs = &result.as_ref().unwrap().accepted;
             assert_eq!(
                 test.should_unorphan,
                 !unorphaned_txs.is_empty(),
@@ -670,8 +1244,13 @@ mod tests {
 
         // Add to mempool a transaction that spends child_tx_2 (as high priority)
         let spending_tx = create_transaction(&child_tx_2, 1_000);
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), spending_tx.clone(), Priority::High, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            spending_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion in the mempool of the spending transaction failed");
 
         // Revalidate, to make sure spending_tx is still valid
@@ -703,6 +1282,47 @@ mod tests {
         assert!(orphan_txs.is_empty(), "orphan pool should be empty");
     }
 
+    // test_local_transactions verifies that only high-priority transactions are tracked as local, that they are not
+    // rebroadcast before their first rebroadcast interval elapsed and that their removal from the mempool is recorded.
+    #[test]
+    fn test_local_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None);
+
+        let local_tx = create_funded_transaction(&consensus, 0, 1000);
+        let relayed_tx = create_funded_transaction(&consensus, 1, 1000);
+        for (transaction, priority) in [(&local_tx, Priority::High), (&relayed_tx, Priority::Low)] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction should succeed but returned {result:?}");
+        }
+
+        let status = mining_manager.get_local_transaction_status(&local_tx.id()).expect("a local transaction should be tracked");
+        assert!(status.is_pending());
+        assert_eq!(0, status.rebroadcast_count);
+        assert!(
+            mining_manager.get_local_transaction_status(&relayed_tx.id()).is_none(),
+            "a relayed transaction should not be tracked"
+        );
+
+        let result = mining_manager.rebroadcast_local_transactions(consensus.as_ref());
+        assert!(result.unwrap().is_empty(), "a local transaction should not be rebroadcast right after its submission");
+
+        let block_transactions = build_block_transactions(once(&local_tx));
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), &block_transactions);
+        assert!(result.is_ok(), "handling the block transactions should succeed but returned {result:?}");
+
+        let status =
+            mining_manager.get_local_transaction_status(&local_tx.id()).expect("an accepted local transaction should be tracked");
+        assert!(!status.is_pending());
+        assert_eq!(Some(MempoolRemovalReason::Accepted), status.removal.map(|(reason, _)| reason));
+    }
+
     // test_modify_block_template verifies that modifying a block template changes coinbase data correctly.
     #[test]
     fn test_modify_block_template() {
@@ -714,11 +1334,21 @@ mod tests {
         let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
 
         for (parent_tx, child_tx) in parent_txs.iter().zip(child_txs.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                parent_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid parent transaction {}", parent_tx.id());
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), child_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                child_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid child transaction {}", parent_tx.id());
         }
 
@@ -900,6 +1530,14 @@ mod tests {
         child_tx
     }
 
+    fn create_funded_transaction(consensus: &Arc<ConsensusMock>, i: u64, fee: u64) -> Transaction {
+        // Make the funding amounts always different so that funding txs have different ids
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KASPA + i]);
+        let transaction = create_transaction(&funding_tx, fee);
+        consensus.add_transaction(funding_tx, 1);
+        transaction
+    }
+
     fn create_transaction_without_input(output_values: Vec<u64>) -> Transaction {
         let (script_public_key, _) = op_true_script();
         let outputs = output_values.iter().map(|value| TransactionOutput::new(*value, script_public_key.clone())).collect();
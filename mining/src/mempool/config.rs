@@ -1,9 +1,13 @@
+use super::errors::{ConfigError, ConfigResult};
 use kaspa_consensus_core::constants::TX_VERSION;
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: u64 = 1_000_000;
 
 pub(crate) const DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 60;
 pub(crate) const DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
+/// DEFAULT_LOCAL_TRANSACTION_EXPIRE_INTERVAL_SECONDS specifies how long a high priority transaction, submitted
+/// locally through RPC, is exempt from expiry and rebroadcast while not included in a block.
+pub(crate) const DEFAULT_LOCAL_TRANSACTION_EXPIRE_INTERVAL_SECONDS: u64 = 24 * 60 * 60;
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS: u64 = 60;
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 
@@ -12,6 +16,14 @@ pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 // TODO: when rusty-kaspa nodes run most of the network, consider increasing this value
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 50;
 
+/// DEFAULT_MAXIMUM_REPLACEMENT_EVICTIONS specifies the maximum number of mempool transactions, the replaced
+/// transaction and all its redeemers, a replace-by-fee transaction is allowed to evict.
+pub(crate) const DEFAULT_MAXIMUM_REPLACEMENT_EVICTIONS: u64 = 100;
+
+/// MAXIMUM_PACKAGE_TRANSACTION_COUNT specifies the maximum number of transactions a package submitted
+/// as a whole may contain.
+pub const MAXIMUM_PACKAGE_TRANSACTION_COUNT: usize = 1_000;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
@@ -27,14 +39,17 @@ pub(crate) const DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION: u16 = TX_VERSION;
 pub struct Config {
     pub maximum_transaction_count: u64,
     pub transaction_expire_interval_daa_score: u64,
+    pub local_transaction_expire_interval_daa_score: u64,
     pub transaction_expire_scan_interval_daa_score: u64,
     pub transaction_expire_scan_interval_milliseconds: u64,
     pub orphan_expire_interval_daa_score: u64,
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_replacement_evictions: u64,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
+    pub target_milliseconds_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
@@ -45,14 +60,17 @@ impl Config {
     pub fn new(
         maximum_transaction_count: u64,
         transaction_expire_interval_daa_score: u64,
+        local_transaction_expire_interval_daa_score: u64,
         transaction_expire_scan_interval_daa_score: u64,
         transaction_expire_scan_interval_seconds: u64,
         orphan_expire_interval_daa_score: u64,
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_replacement_evictions: u64,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
+        target_milliseconds_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
@@ -60,14 +78,17 @@ impl Config {
         Self {
             maximum_transaction_count,
             transaction_expire_interval_daa_score,
+            local_transaction_expire_interval_daa_score,
             transaction_expire_scan_interval_daa_score,
             transaction_expire_scan_interval_milliseconds: transaction_expire_scan_interval_seconds,
             orphan_expire_interval_daa_score,
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_replacement_evictions,
             accept_non_standard,
             maximum_mass_per_block,
+            target_milliseconds_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
@@ -80,6 +101,8 @@ impl Config {
         Self {
             maximum_transaction_count: DEFAULT_MAXIMUM_TRANSACTION_COUNT,
             transaction_expire_interval_daa_score: DEFAULT_TRANSACTION_EXPIRE_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
+            local_transaction_expire_interval_daa_score: DEFAULT_LOCAL_TRANSACTION_EXPIRE_INTERVAL_SECONDS * 1000
+                / target_milliseconds_per_block,
             transaction_expire_scan_interval_daa_score: DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS * 1000
                 / target_milliseconds_per_block,
             transaction_expire_scan_interval_milliseconds: DEFAULT_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS * 1000,
@@ -87,11 +110,45 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_replacement_evictions: DEFAULT_MAXIMUM_REPLACEMENT_EVICTIONS,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
+            target_milliseconds_per_block,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
         }
     }
+
+    /// Checks that the config values are consistent, as they may be set individually by the node operator.
+    /// `max_block_mass` is the consensus limit the configured mass per block must respect.
+    pub fn validate(&self, max_block_mass: u64) -> ConfigResult<()> {
+        let positive_values = [
+            ("maximum_transaction_count", self.maximum_transaction_count),
+            ("transaction_expire_interval_daa_score", self.transaction_expire_interval_daa_score),
+            ("local_transaction_expire_interval_daa_score", self.local_transaction_expire_interval_daa_score),
+            ("transaction_expire_scan_interval_daa_score", self.transaction_expire_scan_interval_daa_score),
+            ("transaction_expire_scan_interval_milliseconds", self.transaction_expire_scan_interval_milliseconds),
+            ("orphan_expire_interval_daa_score", self.orphan_expire_interval_daa_score),
+            ("orphan_expire_scan_interval_daa_score", self.orphan_expire_scan_interval_daa_score),
+            ("maximum_mass_per_block", self.maximum_mass_per_block),
+            ("target_milliseconds_per_block", self.target_milliseconds_per_block),
+        ];
+        if let Some((name, _)) = positive_values.iter().find(|(_, value)| *value == 0) {
+            return Err(ConfigError::ZeroValue(name));
+        }
+        if self.minimum_standard_transaction_version > self.maximum_standard_transaction_version {
+            return Err(ConfigError::InvalidStandardVersionRange(
+                self.minimum_standard_transaction_version,
+                self.maximum_standard_transaction_version,
+            ));
+        }
+        if self.maximum_mass_per_block > max_block_mass {
+            return Err(ConfigError::BlockMassExceedsConsensus(self.maximum_mass_per_block, max_block_mass));
+        }
+        if self.maximum_orphan_transaction_mass > self.maximum_mass_per_block {
+            return Err(ConfigError::OrphanMassExceedsBlockMass(self.maximum_orphan_transaction_mass, self.maximum_mass_per_block));
+        }
+        Ok(())
+    }
 }
@@ -30,6 +30,10 @@ pub enum RuleError {
     #[error("number of high-priority transactions in mempool ({0}) has reached the maximum allowed ({1})")]
     RejectMempoolIsFull(usize, u64),
 
+    /// New behavior: a transaction is rejected if the mempool is full of transactions having a higher fee rate
+    #[error("transaction {0} has a fee rate too low for entering the mempool holding the maximum of {1} transactions")]
+    RejectMempoolIsFullLowFeeRate(TransactionId, u64),
+
     /// An error emitted by mining\src\mempool\check_transaction_standard.rs
     #[error("transaction {0} is not standard: {1}")]
     RejectNonStandard(TransactionId, String),
@@ -62,6 +66,26 @@ pub enum RuleError {
     #[error("number of high-priority transactions in orphan pool ({0}) has reached the maximum allowed ({1})")]
     RejectOrphanPoolIsFull(usize, u64),
 
+    /// A transaction double spending some mempool transaction was rejected as a replacement
+    #[error(transparent)]
+    RejectReplacement(ReplacementError),
+
+    #[error("transaction package is empty")]
+    RejectEmptyPackage,
+
+    #[error("transaction package of {0} transactions exceeds the maximum of {1} transactions")]
+    RejectPackageTooLarge(usize, usize),
+
+    #[error("transaction {0} appears more than once in the transaction package")]
+    RejectPackageDuplicate(TransactionId),
+
+    #[error("transaction package is not topologically ordered, some transaction precedes its parent")]
+    RejectPackageNotTopological,
+
+    /// A package transaction was rejected, and so was the whole package along with it
+    #[error("transaction {0} of the package was rejected: {1}")]
+    RejectPackageTransaction(TransactionId, Box<RuleError>),
+
     #[error("transactions in mempool form a cycle")]
     RejectCycleInMempoolTransactions,
 
@@ -70,6 +94,12 @@ pub enum RuleError {
     RejectInvalid(TransactionId),
 }
 
+impl From<ReplacementError> for RuleError {
+    fn from(item: ReplacementError) -> Self {
+        RuleError::RejectReplacement(item)
+    }
+}
+
 impl From<NonStandardError> for RuleError {
     fn from(item: NonStandardError) -> Self {
         RuleError::RejectNonStandard(*item.transaction_id(), item.to_string())
@@ -141,3 +171,62 @@ impl NonStandardError {
 }
 
 pub type NonStandardResult<T> = std::result::Result<T, NonStandardError>;
+
+#[derive(Error, Debug, Clone)]
+pub enum ReplacementError {
+    #[error("transaction {0} is not a replacement since it does not double spend any transaction in the mempool")]
+    RejectNoConflict(TransactionId),
+
+    #[error("transaction {0} double spends more than one transaction in the mempool ({1} and {2})")]
+    RejectMultipleConflicts(TransactionId, TransactionId, TransactionId),
+
+    #[error(
+        "transaction {0} has a fee rate of {1:.2} which is not higher than the fee rate of {2:.2} of the replaced transaction {3}"
+    )]
+    RejectLowFeeRate(TransactionId, f64, f64, TransactionId),
+
+    #[error("transaction {0} has {1} fees which is under the required amount of {2} for replacing transaction {3}")]
+    RejectInsufficientFee(TransactionId, u64, u64, TransactionId),
+
+    #[error("replacing transaction {1} by transaction {0} would evict {2} transactions, exceeding the maximum of {3}")]
+    RejectTooManyEvictions(TransactionId, TransactionId, usize, u64),
+
+    #[error("transaction {0} spends an output of transaction {1} which it would evict from the mempool")]
+    RejectSpendsEvicted(TransactionId, TransactionId),
+}
+
+/// An error while saving or loading the mempool file
+#[derive(Error, Debug)]
+pub enum PersistenceError {
+    #[error("mempool file I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("mempool file encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    #[error("not a mempool file")]
+    InvalidMagic,
+
+    #[error("unsupported mempool file version {0}, expected version {1}")]
+    UnsupportedVersion(u32, u32),
+}
+
+pub type PersistenceResult<T> = std::result::Result<T, PersistenceError>;
+
+/// An invalid mempool [`Config`](super::config::Config) value
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("mempool config value {0} must be greater than zero")]
+    ZeroValue(&'static str),
+
+    #[error("minimum standard transaction version {0} is greater than the maximum standard transaction version {1}")]
+    InvalidStandardVersionRange(u16, u16),
+
+    #[error("maximum mass per block {0} exceeds the consensus maximum block mass {1}")]
+    BlockMassExceedsConsensus(u64, u64),
+
+    #[error("maximum orphan transaction mass {0} exceeds the maximum mass per block {1}")]
+    OrphanMassExceedsBlockMass(u64, u64),
+}
+
+pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
@@ -1,5 +1,6 @@
-use crate::mempool::{errors::RuleResult, Mempool};
+use crate::mempool::{errors::RuleResult, model::pool::Pool, Mempool};
 use kaspa_consensus_core::{api::ConsensusApi, tx::Transaction};
+use kaspa_consensus_notify::notification::MempoolRemovalReason;
 use std::{collections::HashSet, sync::Arc};
 
 impl Mempool {
@@ -8,17 +9,25 @@ impl Mempool {
         consensus: &dyn ConsensusApi,
         block_transactions: &[Transaction],
     ) -> RuleResult<Vec<Arc<Transaction>>> {
+        let virtual_daa_score = consensus.get_virtual_daa_score();
         let mut accepted_orphans = vec![];
         for transaction in block_transactions[1..].iter() {
             let transaction_id = transaction.id();
-            self.remove_transaction(&transaction_id, false)?;
+            if let Some(mempool_transaction) = self.transaction_pool.get(&transaction_id) {
+                self.inclusion_stats
+                    .record(mempool_transaction.fee_rate(), virtual_daa_score.saturating_sub(mempool_transaction.added_at_daa_score));
+            }
+            self.remove_transaction(&transaction_id, false, MempoolRemovalReason::Accepted)?;
             self.remove_double_spends(transaction)?;
-            self.orphan_pool.remove_orphan(&transaction_id, false)?;
+            let removed_orphans = self.orphan_pool.remove_orphan(&transaction_id, false)?;
+            self.record_all_removed(&removed_orphans, true, MempoolRemovalReason::Accepted);
             let mut unorphaned_transactions = self.process_orphans_after_accepted_transaction(consensus, transaction)?;
             accepted_orphans.append(&mut unorphaned_transactions);
         }
-        self.orphan_pool.expire_low_priority_transactions(consensus)?;
-        self.transaction_pool.expire_low_priority_transactions(consensus.get_virtual_daa_score())?;
+        let expired_orphans = self.orphan_pool.expire_low_priority_transactions(consensus)?;
+        self.record_all_removed(&expired_orphans, true, MempoolRemovalReason::Expired);
+        let expired_transactions = self.transaction_pool.expire_transactions(virtual_daa_score)?;
+        self.record_all_removed(&expired_transactions, false, MempoolRemovalReason::Expired);
         Ok(accepted_orphans)
     }
 
@@ -29,6 +38,6 @@ impl Mempool {
                 transactions_to_remove.insert(*redeemer_id);
             }
         }
-        transactions_to_remove.iter().try_for_each(|x| self.remove_transaction(x, true))
+        transactions_to_remove.iter().try_for_each(|x| self.remove_transaction(x, true, MempoolRemovalReason::DoubleSpent))
     }
 }
@@ -1,13 +1,22 @@
-use crate::model::{
-    candidate_tx::CandidateTransaction,
-    owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+use crate::{
+    fee_estimate::{FeeEstimate, FeeEstimator, InclusionStats},
+    model::{
+        candidate_tx::CandidateTransaction,
+        local_txs::{LocalTransactionStatus, LocalTransactions},
+        owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+    },
 };
 
 use self::{
     config::Config,
-    model::{orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    model::{orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool, tx::MempoolTransaction},
+    tx::Priority,
 };
 use kaspa_consensus_core::tx::{MutableTransaction, TransactionId};
+use kaspa_consensus_notify::notification::{
+    MempoolRemovalReason, MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, Notification,
+};
+use kaspa_core::time::unix_now;
 use std::sync::Arc;
 
 pub(crate) mod check_transaction_standard;
@@ -15,8 +24,10 @@ pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 mod model;
+pub(crate) mod persistence;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
+pub(crate) mod replace_by_fee;
 pub(crate) mod revalidate_high_priority_transactions;
 pub(crate) mod validate_and_insert_transaction;
 
@@ -32,14 +43,21 @@ pub(crate) mod validate_and_insert_transaction;
 ///   built or found.
 /// - There are transaction priorities: high and low.
 /// - Transactions submitted to the mempool by a RPC call have **high priority**.
-///   They are owned by the node, they never expire in the mempool and the node
-///   rebroadcasts them once in a while.
+///   They are owned by the node, which tracks their status and rebroadcasts them
+///   with a backoff until they expire, after a configurable and long interval.
 /// - Transactions received through P2P have **low-priority**. They expire after
 ///   60 seconds and are removed if not inserted in a block for mining.
+/// - A transaction double spending a mempool transaction can replace it, along
+///   with its redeemers, if allowed by the [`tx::RbfPolicy`] and paying a higher
+///   fee rate and fee.
 pub(crate) struct Mempool {
     config: Arc<Config>,
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
+    inclusion_stats: InclusionStats,
+    local_transactions: LocalTransactions,
+    /// Notifications of the transactions added to and removed from the mempool, recorded only if enabled
+    pending_notifications: Option<Vec<Notification>>,
 }
 
 impl Mempool {
@@ -47,7 +65,73 @@ impl Mempool {
         let config = Arc::new(config);
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
-        Self { config, transaction_pool, orphan_pool }
+        Self {
+            config,
+            transaction_pool,
+            orphan_pool,
+            inclusion_stats: InclusionStats::default(),
+            local_transactions: LocalTransactions::default(),
+            pending_notifications: None,
+        }
+    }
+
+    /// Starts recording a notification for every transaction added to or removed from the mempool
+    pub(crate) fn enable_notifications(&mut self) {
+        self.pending_notifications.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the notifications recorded since the previous call, in the order of the mempool changes
+    pub(crate) fn take_notifications(&mut self) -> Vec<Notification> {
+        self.pending_notifications.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn record_added(&mut self, transaction_id: &TransactionId, is_orphan: bool) {
+        let transaction = match is_orphan {
+            false => self.transaction_pool.get(transaction_id),
+            true => self.orphan_pool.get(transaction_id),
+        };
+        let Some(transaction) = transaction else {
+            return;
+        };
+        if transaction.priority == Priority::High {
+            self.local_transactions.track(*transaction_id, unix_now());
+        }
+        if let Some(notifications) = self.pending_notifications.as_mut() {
+            notifications.push(Notification::MempoolTransactionAdded(MempoolTransactionAddedNotification::new(
+                Arc::new(transaction.mtx.clone()),
+                is_orphan,
+            )));
+        }
+    }
+
+    fn record_removed(&mut self, transaction: &MempoolTransaction, is_orphan: bool, reason: MempoolRemovalReason) {
+        if transaction.priority == Priority::High {
+            self.local_transactions.on_removed(&transaction.id(), reason, unix_now());
+        }
+        if let Some(notifications) = self.pending_notifications.as_mut() {
+            notifications.push(Notification::MempoolTransactionRemoved(MempoolTransactionRemovedNotification::new(
+                Arc::new(transaction.mtx.clone()),
+                is_orphan,
+                reason,
+            )));
+        }
+    }
+
+    fn record_all_removed(&mut self, transactions: &[MempoolTransaction], is_orphan: bool, reason: MempoolRemovalReason) {
+        transactions.iter().for_each(|x| self.record_removed(x, is_orphan, reason));
+    }
+
+    pub(crate) fn get_local_transaction_status(&self, transaction_id: &TransactionId) -> Option<LocalTransactionStatus> {
+        self.local_transactions.get(transaction_id).copied()
+    }
+
+    /// Returns the local transactions among `transaction_ids` due for a rebroadcast, scheduling their next one
+    pub(crate) fn take_local_transactions_to_rebroadcast(&mut self, transaction_ids: Vec<TransactionId>) -> Vec<TransactionId> {
+        self.local_transactions.take_due_for_rebroadcast(transaction_ids, unix_now())
+    }
+
+    pub(crate) fn config(&self) -> Arc<Config> {
+        self.config.clone()
     }
 
     pub(crate) fn get_transaction(
@@ -122,10 +206,25 @@ impl Mempool {
     pub(crate) fn block_candidate_transactions(&self) -> Vec<CandidateTransaction> {
         self.transaction_pool.all_ready_transactions()
     }
+
+    pub(crate) fn get_fee_estimate(&self) -> FeeEstimate {
+        let candidates = self.block_candidate_transactions();
+        FeeEstimator::new(
+            self.config.maximum_mass_per_block,
+            self.config.target_milliseconds_per_block,
+            self.config.minimum_relay_transaction_fee as f64 / 1000.0,
+            self.transaction_pool.fee_rate_keys().rev().map(|key| (key.fee_rate(), key.mass())),
+            candidates.iter().map(|tx| (tx.package_fee_rate(), tx.calculated_mass)),
+            &self.inclusion_stats,
+        )
+        .estimate()
+    }
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
     pub enum Priority {
         Low,
         High,
@@ -136,4 +235,15 @@ pub mod tx {
         Forbidden,
         Allowed,
     }
+
+    /// Replace-by-fee policy applied to a transaction double spending a transaction in the mempool
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RbfPolicy {
+        /// The transaction is rejected as a double spend
+        Forbidden,
+        /// The transaction replaces the double spent transaction if it satisfies the replacement rules
+        Allowed,
+        /// The transaction must double spend a transaction in the mempool and replace it
+        Mandatory,
+    }
 }
@@ -1,9 +1,12 @@
-use super::tx::MempoolTransaction;
+use super::tx::{FeeRateKey, MempoolTransaction};
 use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 /// MempoolTransactionCollection maps a transaction id to a mempool transaction
 pub(crate) type MempoolTransactionCollection = HashMap<TransactionId, MempoolTransaction>;
 
 /// OutpointIndex maps an outpoint to a transaction id
 pub(crate) type OutpointIndex = HashMap<TransactionOutpoint, TransactionId>;
+
+/// FeeRateIndex orders transaction ids by fee rate
+pub(crate) type FeeRateIndex = BTreeSet<FeeRateKey>;
@@ -1,5 +1,6 @@
 pub(crate) mod map;
 pub(crate) mod orphan_pool;
+pub(crate) mod package;
 pub(crate) mod pool;
 pub(crate) mod transactions_pool;
 pub(crate) mod tx;
@@ -61,30 +61,32 @@ impl OrphanPool {
         self.outpoint_owner_id.get(outpoint).and_then(|id| self.all_orphans.get_mut(id))
     }
 
+    /// Adds the orphan to the pool, if the pool accepts orphans, and returns the orphans evicted to make room for it
     pub(crate) fn try_add_orphan(
         &mut self,
         consensus: &dyn ConsensusApi,
         transaction: MutableTransaction,
         priority: Priority,
-    ) -> RuleResult<()> {
+    ) -> RuleResult<Vec<MempoolTransaction>> {
         // Rust rewrite: original name is maybeAddOrphan
         if self.config.maximum_orphan_transaction_count == 0 {
             // TODO: determine how/why this may happen
-            return Ok(());
+            return Ok(vec![]);
         }
         self.check_orphan_duplicate(&transaction)?;
         self.check_orphan_mass(&transaction)?;
         self.check_orphan_double_spend(&transaction)?;
         // Make sure there is room in the pool for the new transaction
-        self.limit_orphan_pool_size(1)?;
+        let evicted = self.limit_orphan_pool_size(1)?;
         self.add_orphan(consensus, transaction, priority)?;
-        Ok(())
+        Ok(evicted)
     }
 
-    /// Make room in the pool for at least `free_slots` new transactions.
+    /// Make room in the pool for at least `free_slots` new transactions and return the evicted orphans.
     ///
     /// An error is returned if the pool is filled with high priority transactions.
-    fn limit_orphan_pool_size(&mut self, free_slots: usize) -> RuleResult<()> {
+    fn limit_orphan_pool_size(&mut self, free_slots: usize) -> RuleResult<Vec<MempoolTransaction>> {
+        let mut evicted = vec![];
         while self.all_orphans.len() + free_slots > self.config.maximum_orphan_transaction_count as usize {
             let orphan_to_remove = self.get_random_low_priority_orphan();
             if orphan_to_remove.is_none() {
@@ -95,9 +97,9 @@ impl OrphanPool {
             }
             // Don't remove redeemers in the case of a random eviction since the evicted transaction is
             // not invalid, therefore it's redeemers are as good as any orphan that just arrived.
-            self.remove_orphan(&orphan_to_remove.unwrap().id(), false)?;
+            evicted.extend(self.remove_orphan(&orphan_to_remove.unwrap().id(), false)?);
         }
-        Ok(())
+        Ok(evicted)
     }
 
     fn check_orphan_mass(&self, transaction: &MutableTransaction) -> RuleResult<()> {
@@ -214,10 +216,11 @@ impl OrphanPool {
         self.get_redeemer_ids_in_pool(transaction_id).iter().map(|x| self.remove_single_orphan(x)).collect()
     }
 
-    pub(crate) fn expire_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<()> {
+    /// Removes the low priority orphans which stayed in the pool for too long and returns them
+    pub(crate) fn expire_low_priority_transactions(&mut self, consensus: &dyn ConsensusApi) -> RuleResult<Vec<MempoolTransaction>> {
         let virtual_daa_score = consensus.get_virtual_daa_score();
-        if virtual_daa_score - self.last_expire_scan < self.config.orphan_expire_scan_interval_daa_score {
-            return Ok(());
+        if virtual_daa_score.saturating_sub(self.last_expire_scan) < self.config.orphan_expire_scan_interval_daa_score {
+            return Ok(vec![]);
         }
 
         // Never expire high priority transactions
@@ -227,7 +230,7 @@ impl OrphanPool {
             .values()
             .filter_map(|x| {
                 if (x.priority == Priority::Low)
-                    && virtual_daa_score - x.added_at_daa_score > self.config.orphan_expire_interval_daa_score
+                    && virtual_daa_score.saturating_sub(x.added_at_daa_score) > self.config.orphan_expire_interval_daa_score
                 {
                     Some(x.id())
                 } else {
@@ -236,23 +239,25 @@ impl OrphanPool {
             })
             .collect();
 
+        let mut expired = vec![];
         for transaction_id in expired_low_priority_transactions.iter() {
-            self.remove_orphan(transaction_id, false)?;
+            expired.extend(self.remove_orphan(transaction_id, false)?);
         }
 
         self.last_expire_scan = virtual_daa_score;
-        Ok(())
+        Ok(expired)
     }
 
+    /// Updates the orphans spending outputs of a transaction removed from the transactions pool, removing them if
+    /// `remove_redeemers` is set, in which case the removed orphans are returned
     pub(crate) fn update_orphans_after_transaction_removed(
         &mut self,
         removed_transaction: &MempoolTransaction,
         remove_redeemers: bool,
-    ) -> RuleResult<()> {
+    ) -> RuleResult<Vec<MempoolTransaction>> {
         let removed_transaction_id = removed_transaction.id();
         if remove_redeemers {
-            self.remove_redeemers_of(&removed_transaction_id)?;
-            return Ok(());
+            return self.remove_redeemers_of(&removed_transaction_id);
         }
 
         let mut outpoint = TransactionOutpoint::new(removed_transaction_id, 0);
@@ -266,7 +271,7 @@ impl OrphanPool {
                 }
             }
         }
-        Ok(())
+        Ok(vec![])
     }
 
     fn get_random_low_priority_orphan(&self) -> Option<&MempoolTransaction> {
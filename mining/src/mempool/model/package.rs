@@ -0,0 +1,66 @@
+use crate::{
+    mempool::{
+        config::MAXIMUM_PACKAGE_TRANSACTION_COUNT,
+        errors::{RuleError, RuleResult},
+    },
+    model::{
+        topological_index::{TopologicalIndex, TopologicalIndexError},
+        TransactionIdSet,
+    },
+};
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use std::collections::{hash_set::Iter as SetIter, HashMap};
+
+/// The dependency graph of a package of transactions, each transaction having an edge to all the
+/// package transactions spending its outputs.
+pub(crate) struct TransactionPackage {
+    transactions: TransactionIdSet,
+    chained_transactions: HashMap<TransactionId, TransactionIdSet>,
+}
+
+impl TransactionPackage {
+    /// Builds the package of `transactions`, checking they are unique and ordered so that every transaction
+    /// follows its parents.
+    pub(crate) fn build(transactions: &[Transaction]) -> RuleResult<Self> {
+        if transactions.is_empty() {
+            return Err(RuleError::RejectEmptyPackage);
+        }
+        if transactions.len() > MAXIMUM_PACKAGE_TRANSACTION_COUNT {
+            return Err(RuleError::RejectPackageTooLarge(transactions.len(), MAXIMUM_PACKAGE_TRANSACTION_COUNT));
+        }
+
+        let ids = transactions.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let mut package = Self { transactions: TransactionIdSet::with_capacity(ids.len()), chained_transactions: HashMap::new() };
+        for id in ids.iter() {
+            if !package.transactions.insert(*id) {
+                return Err(RuleError::RejectPackageDuplicate(*id));
+            }
+        }
+        for (transaction, id) in transactions.iter().zip(ids.iter()) {
+            for input in transaction.inputs.iter() {
+                let parent_id = input.previous_outpoint.transaction_id;
+                if package.transactions.contains(&parent_id) {
+                    package.chained_transactions.entry(parent_id).or_default().insert(*id);
+                }
+            }
+        }
+
+        package.check_topological_order(&ids).map_err(|err| match err {
+            TopologicalIndexError::IndexIsNotTopological => RuleError::RejectPackageNotTopological,
+            err => err.into(),
+        })?;
+        Ok(package)
+    }
+}
+
+type IterTxId<'a> = SetIter<'a, TransactionId>;
+
+impl<'a> TopologicalIndex<'a, IterTxId<'a>, IterTxId<'a>, TransactionId> for TransactionPackage {
+    fn topology_nodes(&'a self) -> IterTxId<'a> {
+        self.transactions.iter()
+    }
+
+    fn topology_node_edges(&'a self, key: &TransactionId) -> Option<IterTxId<'a>> {
+        self.chained_transactions.get(key).map(|x| x.iter())
+    }
+}
@@ -2,10 +2,15 @@ use crate::{
     mempool::{
         config::Config,
         errors::{RuleError, RuleResult},
-        model::{map::MempoolTransactionCollection, pool::Pool, tx::MempoolTransaction, utxo_set::MempoolUtxoSet},
+        model::{
+            map::{FeeRateIndex, MempoolTransactionCollection},
+            pool::Pool,
+            tx::{FeeRateKey, MempoolTransaction},
+            utxo_set::MempoolUtxoSet,
+        },
         tx::Priority,
     },
-    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex},
+    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex, TransactionIdSet},
 };
 use kaspa_consensus_core::{
     tx::TransactionId,
@@ -13,12 +18,16 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::{debug, time::unix_now, warn};
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter},
+    collections::{hash_map::Keys, hash_set::Iter, HashMap},
     sync::Arc,
 };
 
 use super::pool::TransactionsEdges;
 
+/// The block template candidates are the ready transactions with the highest fee rates, up to a total
+/// mass of this factor times the maximum block mass, leaving the selector some room for randomization.
+const BLOCK_CANDIDATES_MASS_FACTOR: u64 = 4;
+
 /// Pool of transactions to be included in a block template
 ///
 /// ### Rust rewrite notes
@@ -36,10 +45,12 @@ use super::pool::TransactionsEdges;
 ///   introducing a indirection stage when the matching object is required.
 /// - Hash sets are used by parent_transaction_ids_in_pool and chained_transaction_ids_by_parent_id
 ///   instead of vectors to prevent duplicates.
-/// - transactionsOrderedByFeeRate is dropped and replaced by an in-place vector
-///   of low-priority transactions sorted by fee rates. This design might eventually
-///   prove to be sub-optimal, in which case an index should be implemented, probably
-///   requiring smart pointers eventually or an indirection stage too.
+/// - transactionsOrderedByFeeRate is replaced by an ordered index of fee rate keys,
+///   introducing an indirection stage when the matching object is required. The index
+///   covers all transactions and is used both for evicting the lowest fee rate
+///   low-priority transactions when the pool is full and for estimating fees. The
+///   block template candidates are ordered by package fee rate instead, so that a
+///   high fee child pays for its low fee parents.
 pub(crate) struct TransactionsPool {
     /// Mempool config
     config: Arc<Config>,
@@ -50,6 +61,10 @@ pub(crate) struct TransactionsPool {
     parent_transactions: TransactionsEdges,
     /// Transactions dependencies formed by outputs present in pool - successor relations.
     chained_transactions: TransactionsEdges,
+    /// Transactions ordered by fee rate
+    ordered_by_fee_rate: FeeRateIndex,
+    /// The total fee and mass of each transaction along with all its ancestors in the pool, keyed by transaction id
+    ancestor_packages: HashMap<TransactionId, FeeRateKey>,
 
     last_expire_scan_daa_score: u64,
     /// last expire scan time in milliseconds
@@ -66,6 +81,8 @@ impl TransactionsPool {
             all_transactions: MempoolTransactionCollection::default(),
             parent_transactions: TransactionsEdges::default(),
             chained_transactions: TransactionsEdges::default(),
+            ordered_by_fee_rate: FeeRateIndex::default(),
+            ancestor_packages: HashMap::default(),
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
@@ -105,28 +122,33 @@ impl TransactionsPool {
             }
         }
 
+        // The ancestors of a transaction are all in the pool already and cannot change until one of them is removed
+        let (fee, mass) = self
+            .get_ancestor_ids_in_pool(&id)
+            .iter()
+            .map(|id| &self.all_transactions[id].mtx)
+            .fold((transaction.mtx.calculated_fee.unwrap(), transaction.mtx.calculated_mass.unwrap()), |(fee, mass), mtx| {
+                (fee + mtx.calculated_fee.unwrap(), mass + mtx.calculated_mass.unwrap())
+            });
+        self.ancestor_packages.insert(id, FeeRateKey::new(fee, mass, id));
+
         self.utxo_set.add_transaction(&transaction.mtx);
+        self.ordered_by_fee_rate.insert(transaction.fee_rate_key());
         self.all_transactions.insert(id, transaction);
         Ok(())
     }
 
-    pub(crate) fn remove_parent_chained_relation_in_pool(
-        &mut self,
-        transaction_id: &TransactionId,
-        parent_id: &TransactionId,
-    ) -> bool {
-        let mut found = false;
-        // Remove the bijective parent/chained relation
-        if let Some(parents) = self.parent_transactions.get_mut(transaction_id) {
-            found = parents.remove(parent_id);
-        }
-        if let Some(chains) = self.chained_transactions.get_mut(parent_id) {
-            found = chains.remove(transaction_id) || found;
+    pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
+        // The removed transaction is no longer an ancestor of its redeemers remaining in the pool
+        if self.ancestor_packages.remove(transaction_id).is_some() {
+            let mtx = &self.all_transactions[transaction_id].mtx;
+            let (fee, mass) = (mtx.calculated_fee.unwrap(), mtx.calculated_mass.unwrap());
+            for redeemer_id in self.get_redeemer_ids_in_pool(transaction_id) {
+                let redeemer_package = self.ancestor_packages.get_mut(&redeemer_id).unwrap();
+                *redeemer_package = FeeRateKey::new(redeemer_package.fee() - fee, redeemer_package.mass() - mass, redeemer_id);
+            }
         }
-        found
-    }
 
-    pub(crate) fn remove_transaction(&mut self, transaction_id: &TransactionId) -> RuleResult<MempoolTransaction> {
         // Remove all bijective parent/chained relations
         if let Some(parents) = self.parent_transactions.get(transaction_id) {
             for parent in parents.iter() {
@@ -146,31 +168,38 @@ impl TransactionsPool {
         self.chained_transactions.remove(transaction_id);
 
         // Remove the transaction itself
-        self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))
+        let transaction = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
+        self.ordered_by_fee_rate.remove(&transaction.fee_rate_key());
+        Ok(transaction)
     }
 
-    pub(crate) fn expire_low_priority_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<()> {
+    /// Removes the transactions which stayed in the pool for too long and returns them.
+    ///
+    /// High priority transactions, submitted locally, are exempt from expiry for a longer, distinct interval.
+    pub(crate) fn expire_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<Vec<MempoolTransaction>> {
         let now = unix_now();
-        if virtual_daa_score < self.last_expire_scan_daa_score + self.config.transaction_expire_scan_interval_daa_score
-            || now < self.last_expire_scan_time + self.config.transaction_expire_scan_interval_milliseconds
+        // Intervals are configurable and may be as large as u64::MAX (i.e. never scan/expire), hence the saturating additions
+        if virtual_daa_score < self.last_expire_scan_daa_score.saturating_add(self.config.transaction_expire_scan_interval_daa_score)
+            || now < self.last_expire_scan_time.saturating_add(self.config.transaction_expire_scan_interval_milliseconds)
         {
-            return Ok(());
+            return Ok(vec![]);
         }
 
-        // Never expire high priority transactions
-        // Remove all transactions whose added_at_daa_score is older then transaction_expire_interval_daa_score
-        let expired_low_priority_transactions: Vec<TransactionId> = self
+        // Remove all transactions whose added_at_daa_score is older then the expire interval of their priority
+        let expired_transactions: Vec<TransactionId> = self
             .all_transactions
             .values()
             .filter_map(|x| {
-                if (x.priority == Priority::Low)
-                    && virtual_daa_score > x.added_at_daa_score + self.config.transaction_expire_interval_daa_score
-                {
+                let expire_interval = match x.priority {
+                    Priority::Low => self.config.transaction_expire_interval_daa_score,
+                    Priority::High => self.config.local_transaction_expire_interval_daa_score,
+                };
+                if virtual_daa_score > x.added_at_daa_score.saturating_add(expire_interval) {
                     debug!(
                         "Removing transaction {}, because it expired, DAAScore moved by {}, expire interval: {}",
                         x.id(),
                         virtual_daa_score - x.added_at_daa_score,
-                        self.config.transaction_expire_interval_daa_score
+                        expire_interval
                     );
                     Some(x.id())
                 } else {
@@ -179,13 +208,16 @@ impl TransactionsPool {
             })
             .collect();
 
-        for transaction_id in expired_low_priority_transactions.iter() {
-            self.remove_transaction(transaction_id)?;
+        let mut expired = Vec::with_capacity(expired_transactions.len());
+        for transaction_id in expired_transactions.iter() {
+            let transaction = self.remove_transaction(transaction_id)?;
+            self.remove_transaction_utxos(&transaction.mtx);
+            expired.push(transaction);
         }
 
         self.last_expire_scan_daa_score = virtual_daa_score;
         self.last_expire_scan_time = now;
-        Ok(())
+        Ok(expired)
     }
 
     /// Is the mempool transaction identified by `transaction_id` ready for being inserted into a block template?
@@ -199,53 +231,150 @@ impl TransactionsPool {
         false
     }
 
-    /// all_ready_transactions returns all fully populated mempool transactions having no parents in the mempool.
-    /// These transactions are ready for being inserted in a block template.
+    /// all_ready_transactions returns the fully populated mempool transactions having no parents in the mempool
+    /// and the highest package fee rates, up to a total mass of [`BLOCK_CANDIDATES_MASS_FACTOR`] times the maximum
+    /// block mass. These transactions are ready for being inserted in a block template.
+    ///
+    /// See [`Self::ready_transaction_packages`] for the package fee rate of a ready transaction.
     pub(crate) fn all_ready_transactions(&self) -> Vec<CandidateTransaction> {
+        let maximum_mass = self.config.maximum_mass_per_block.saturating_mul(BLOCK_CANDIDATES_MASS_FACTOR);
+        let mut packages = self.ready_transaction_packages().into_values().collect::<Vec<_>>();
+        packages.sort_unstable_by(|a, b| b.cmp(a));
+        let mut total_mass: u64 = 0;
         // The returned transactions are leaving the mempool so they are cloned
-        self.all_transactions
-            .values()
-            .filter_map(|x| if self.is_transaction_ready(&x.id()) { Some(CandidateTransaction::from_mutable(&x.mtx)) } else { None })
+        packages
+            .into_iter()
+            .map(|package| (package, &self.all_transactions[&package.id()].mtx))
+            .take_while(|(_, mtx)| {
+                total_mass = total_mass.saturating_add(mtx.calculated_mass.unwrap());
+                total_mass <= maximum_mass
+            })
+            .map(|(package, mtx)| CandidateTransaction::from_mutable_in_package(mtx, package.fee(), package.mass()))
             .collect()
     }
 
-    /// Returns the exceeding low-priority transactions having the lowest fee rates in order
-    /// to have room for at least `free_slots` new transactions.
+    /// Returns the fee and mass of the package giving its highest fee rate to each ready transaction, keyed by
+    /// the ready transaction id.
+    ///
+    /// The package of a ready transaction is either the transaction alone or one of its descendants in the pool
+    /// along with all the ancestors of this descendant, which must be included in blocks first. A high fee
+    /// descendant thus raises the fee rate of its low fee ready ancestors (child pays for parent).
+    ///
+    /// The best package of every transaction is computed in a single pass in reverse topological order, as the
+    /// best of its own ancestor package and of the best packages of its redeemers.
+    fn ready_transaction_packages(&self) -> HashMap<TransactionId, FeeRateKey> {
+        let order = self.topological_index().unwrap_or_else(|_| self.all_transactions.keys().copied().collect());
+        let mut best_packages: HashMap<TransactionId, FeeRateKey> = HashMap::with_capacity(order.len());
+        for id in order.into_iter().rev() {
+            let mut best = self.ancestor_packages[&id];
+            for redeemer_id in self.chained_transactions.get(&id).into_iter().flatten() {
+                if let Some(package) = best_packages.get(redeemer_id) {
+                    if package.cmp_fee_rate(&best).is_gt() {
+                        best = *package;
+                    }
+                }
+            }
+            best_packages.insert(id, best);
+        }
+        best_packages
+            .into_iter()
+            .filter(|(id, _)| self.is_transaction_ready(id))
+            .map(|(id, package)| (id, FeeRateKey::new(package.fee(), package.mass(), id)))
+            .collect()
+    }
+
+    /// Returns the ids of all transactions being direct and indirect parents of `transaction_id` in the pool.
+    fn get_ancestor_ids_in_pool(&self, transaction_id: &TransactionId) -> TransactionIdSet {
+        let mut ancestors = TransactionIdSet::new();
+        let mut stack = vec![*transaction_id];
+        while let Some(id) = stack.pop() {
+            if let Some(parents) = self.parent_transactions.get(&id) {
+                for parent_id in parents {
+                    // Do no revisit transactions
+                    if ancestors.insert(*parent_id) {
+                        stack.push(*parent_id);
+                    }
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Returns the low-priority transactions to be evicted, along with their redeemers, in order to have room for at least
+    /// `free_slots` new transactions, `transactions` with `priority` being the first of them. The transactions are either
+    /// a single new transaction, or the transactions of a package already inserted in the pool (with no free slot required).
     ///
-    /// An error is returned if the mempool is filled with high priority transactions.
-    pub(crate) fn limit_transaction_count(&self, free_slots: usize) -> RuleResult<Vec<TransactionId>> {
+    /// Transactions are evicted by increasing fee rate. Low-priority `transactions` may only evict transactions having
+    /// a lower fee rate than their overall fee rate. Transactions having high-priority redeemers or any of `transactions`
+    /// as redeemer are never evicted.
+    ///
+    /// An error is returned if not enough transactions can be evicted.
+    pub(crate) fn limit_transaction_count(
+        &self,
+        free_slots: usize,
+        transactions: &[&MutableTransaction],
+        priority: Priority,
+    ) -> RuleResult<Vec<TransactionId>> {
         // Returns a vector of transactions to be removed that the caller has to remove actually.
         // The caller is golang validateAndInsertTransaction equivalent.
         // This behavior differs from golang impl.
         let mut transactions_to_remove = Vec::new();
-        if self.len() + free_slots > self.config.maximum_transaction_count as usize {
-            // TODO: consider introducing an index on all_transactions low-priority items instead.
-            //
-            // Sorting this vector here may be sub-optimal compared with maintaining a sorted
-            // index of all_transactions low-priority items if the proportion of low-priority txs
-            // in all_transactions is important.
-            let mut low_priority_txs = self.all_transactions.values().filter(|x| x.priority == Priority::Low).collect::<Vec<_>>();
-
-            if !low_priority_txs.is_empty() {
-                low_priority_txs.sort_by(|a, b| a.fee_rate().partial_cmp(&b.fee_rate()).unwrap());
-                transactions_to_remove.extend_from_slice(
-                    &low_priority_txs[0..usize::min(
-                        self.len() + free_slots - self.config.maximum_transaction_count as usize,
-                        low_priority_txs.len(),
-                    )],
-                );
+        if self.len() + free_slots <= self.config.maximum_transaction_count as usize {
+            return Ok(transactions_to_remove);
+        }
+
+        let last_id = transactions.last().expect("at least one transaction").id();
+        let (fee, mass) = transactions
+            .iter()
+            .fold((0u64, 0u64), |(fee, mass), x| (fee + x.calculated_fee.unwrap(), mass + x.calculated_mass.unwrap()));
+        let transaction_key = FeeRateKey::new(fee, mass, last_id);
+        let mut kept_ids = TransactionIdSet::new();
+        for transaction in transactions.iter() {
+            kept_ids.insert(transaction.id());
+            kept_ids.extend(self.get_parent_transaction_ids_in_pool(transaction));
+        }
+        let mut evicted = TransactionIdSet::new();
+        let mut low_fee_rate = false;
+        for key in self.ordered_by_fee_rate.iter() {
+            if self.len() + free_slots - evicted.len() <= self.config.maximum_transaction_count as usize {
+                break;
+            }
+            if priority == Priority::Low && key.cmp_fee_rate(&transaction_key).is_ge() {
+                low_fee_rate = true;
+                break;
+            }
+            if evicted.contains(&key.id()) || self.all_transactions[&key.id()].priority == Priority::High {
+                continue;
+            }
+            let mut candidates = vec![key.id()];
+            candidates.extend(self.get_redeemer_ids_in_pool(&key.id()));
+            if candidates.iter().any(|id| self.all_transactions[id].priority == Priority::High || kept_ids.contains(id)) {
+                continue;
+            }
+            for id in candidates {
+                if evicted.insert(id) {
+                    transactions_to_remove.push(id);
+                }
             }
         }
 
-        // An error is returned if the mempool is filled with high priority transactions.
-        let tx_count = self.len() + free_slots - transactions_to_remove.len();
+        // An error is returned if the mempool is filled with transactions which cannot be evicted.
+        let tx_count = self.len() + free_slots - evicted.len();
         if tx_count as u64 > self.config.maximum_transaction_count {
-            let err = RuleError::RejectMempoolIsFull(tx_count - free_slots, self.config.maximum_transaction_count);
+            let err = match low_fee_rate {
+                true => RuleError::RejectMempoolIsFullLowFeeRate(last_id, self.config.maximum_transaction_count),
+                false => RuleError::RejectMempoolIsFull(tx_count - free_slots, self.config.maximum_transaction_count),
+            };
             warn!("{}", err.to_string());
             return Err(err);
         }
 
-        Ok(transactions_to_remove.iter().map(|x| x.id()).collect())
+        Ok(transactions_to_remove)
+    }
+
+    /// Returns the fee rate keys of all transactions, by increasing fee rate
+    pub(crate) fn fee_rate_keys(&self) -> impl DoubleEndedIterator<Item = &FeeRateKey> {
+        self.ordered_by_fee_rate.iter()
     }
 
     pub(crate) fn get_all_transactions(&self) -> Vec<MutableTransaction> {
@@ -260,6 +389,10 @@ impl TransactionsPool {
         self.utxo_set.check_double_spends(transaction)
     }
 
+    pub(crate) fn get_double_spend_transaction_ids(&self, transaction: &MutableTransaction) -> Vec<TransactionId> {
+        self.utxo_set.get_double_spend_transaction_ids(transaction)
+    }
+
     pub(crate) fn remove_transaction_utxos(&mut self, transaction: &MutableTransaction) {
         let parent_ids = self.get_parent_transaction_ids_in_pool(transaction);
         self.utxo_set.remove_transaction(transaction, &parent_ids)
@@ -300,3 +433,111 @@ impl Pool for TransactionsPool {
         &mut self.chained_transactions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        constants::TX_VERSION,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutput, UtxoEntry},
+    };
+    use kaspa_hashes::Hash;
+
+    /// Creates a fully populated transaction of mass 1000 spending `previous_outpoints` into two outputs of `value`
+    fn create_transaction(previous_outpoints: &[TransactionOutpoint], value: u64, fee: u64) -> MutableTransaction {
+        let inputs = previous_outpoints.iter().map(|&outpoint| TransactionInput::new(outpoint, vec![], 0, 1)).collect();
+        let outputs = vec![TransactionOutput::new(value, ScriptPublicKey::default()); 2];
+        let transaction = Transaction::new(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let mut mutable_tx = MutableTransaction::from_tx(transaction);
+        mutable_tx.calculated_fee = Some(fee);
+        mutable_tx.calculated_mass = Some(1_000);
+        let entry_value = (2 * value + fee).div_ceil(previous_outpoints.len() as u64);
+        mutable_tx.entries.fill(Some(UtxoEntry::new(entry_value, ScriptPublicKey::default(), 0, false)));
+        mutable_tx
+    }
+
+    // test_ready_transaction_packages verifies that a ready transaction gets the highest fee rate among the packages formed
+    // by its descendants along with their ancestors, counting every ancestor once, and that the packages are updated
+    // when an ancestor leaves the pool.
+    #[test]
+    fn test_ready_transaction_packages() {
+        let mut pool = TransactionsPool::new(Arc::new(Config::build_default(1_000, false, 500_000)));
+
+        // A diamond: b and c both spend a, and d spends both b and c
+        let a = create_transaction(&[TransactionOutpoint::new(Hash::from_u64_word(1), 0)], 10_000, 100);
+        let b = create_transaction(&[TransactionOutpoint::new(a.id(), 0)], 4_000, 200);
+        let c = create_transaction(&[TransactionOutpoint::new(a.id(), 1)], 4_000, 300);
+        let d = create_transaction(&[TransactionOutpoint::new(b.id(), 0), TransactionOutpoint::new(c.id(), 0)], 1_000, 7_400);
+        let ids = [a.id(), b.id(), c.id(), d.id()];
+        for transaction in [a, b, c, d] {
+            pool.add_transaction(transaction, 0, Priority::Low).unwrap();
+        }
+
+        let packages = pool.ready_transaction_packages();
+        assert_eq!(packages.keys().copied().collect::<Vec<_>>(), vec![ids[0]]);
+        assert_eq!(packages[&ids[0]], FeeRateKey::new(8_000, 4_000, ids[0]), "the package of a should be a, b, c and d");
+
+        // Once a is accepted, b and c become ready, each with the package made of b, c and d
+        pool.remove_transaction(&ids[0]).unwrap();
+        let packages = pool.ready_transaction_packages();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[&ids[1]], FeeRateKey::new(7_900, 3_000, ids[1]));
+        assert_eq!(packages[&ids[2]], FeeRateKey::new(7_900, 3_000, ids[2]));
+
+        // Without d, b and c are packages on their own
+        pool.remove_transaction(&ids[3]).unwrap();
+        let packages = pool.ready_transaction_packages();
+        assert_eq!(packages[&ids[1]], FeeRateKey::new(200, 1_000, ids[1]));
+        assert_eq!(packages[&ids[2]], FeeRateKey::new(300, 1_000, ids[2]));
+    }
+
+    // test_expired_transaction_releases_its_outpoints verifies that the outpoints spent by an expired transaction
+    // are no longer owned by it, so that a transaction spending them can enter the pool again.
+    #[test]
+    fn test_expired_transaction_releases_its_outpoints() {
+        let mut config = Config::build_default(1_000, false, 500_000);
+        config.transaction_expire_interval_daa_score = 10;
+        config.transaction_expire_scan_interval_daa_score = 0;
+        config.transaction_expire_scan_interval_milliseconds = 0;
+        let mut pool = TransactionsPool::new(Arc::new(config));
+
+        let outpoint = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let transaction = create_transaction(&[outpoint], 10_000, 1_000);
+        let transaction_id = transaction.id();
+        pool.add_transaction(transaction, 0, Priority::Low).unwrap();
+        assert_eq!(pool.get_outpoint_owner_id(&outpoint), Some(&transaction_id));
+
+        // Not expired yet
+        assert!(pool.expire_transactions(10).unwrap().is_empty());
+
+        let expired = pool.expire_transactions(11).unwrap();
+        assert_eq!(expired.iter().map(|x| x.id()).collect::<Vec<_>>(), vec![transaction_id]);
+        assert!(pool.get_outpoint_owner_id(&outpoint).is_none(), "the outpoint of an expired transaction should be spendable again");
+
+        let double_spend = create_transaction(&[outpoint], 9_000, 1_000);
+        assert!(pool.check_double_spends(&double_spend).is_ok());
+        pool.add_transaction(double_spend, 11, Priority::Low).unwrap();
+    }
+
+    // test_maximal_expire_intervals verifies that expire and scan intervals of u64::MAX mean transactions never expire.
+    #[test]
+    fn test_maximal_expire_intervals() {
+        let mut config = Config::build_default(1_000, false, 500_000);
+        config.transaction_expire_interval_daa_score = u64::MAX;
+        config.transaction_expire_scan_interval_daa_score = 0;
+        config.transaction_expire_scan_interval_milliseconds = 0;
+        let mut pool = TransactionsPool::new(Arc::new(config.clone()));
+        let transaction = create_transaction(&[TransactionOutpoint::new(Hash::from_u64_word(1), 0)], 10_000, 1_000);
+        pool.add_transaction(transaction, 10, Priority::Low).unwrap();
+        assert!(pool.expire_transactions(u64::MAX).unwrap().is_empty(), "the transaction should never expire");
+
+        config.transaction_expire_interval_daa_score = 0;
+        config.transaction_expire_scan_interval_daa_score = u64::MAX;
+        config.transaction_expire_scan_interval_milliseconds = u64::MAX;
+        let mut pool = TransactionsPool::new(Arc::new(config));
+        let transaction = create_transaction(&[TransactionOutpoint::new(Hash::from_u64_word(1), 0)], 10_000, 1_000);
+        pool.add_transaction(transaction, 10, Priority::Low).unwrap();
+        assert!(pool.expire_transactions(u64::MAX).unwrap().is_empty(), "the pool should never be scanned");
+    }
+}
@@ -21,6 +21,62 @@ impl MempoolTransaction {
     pub(crate) fn fee_rate(&self) -> f64 {
         self.mtx.calculated_fee.unwrap() as f64 / self.mtx.calculated_mass.unwrap() as f64
     }
+
+    pub(crate) fn fee_rate_key(&self) -> FeeRateKey {
+        FeeRateKey::from_mutable(&self.mtx)
+    }
+}
+
+/// A key ordering transactions by fee rate, ties being broken by transaction id
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FeeRateKey {
+    fee: u64,
+    mass: u64,
+    id: TransactionId,
+}
+
+impl FeeRateKey {
+    pub(crate) fn new(fee: u64, mass: u64, id: TransactionId) -> Self {
+        Self { fee, mass, id }
+    }
+
+    /// Builds the key of a fully populated transaction
+    pub(crate) fn from_mutable(mtx: &MutableTransaction) -> Self {
+        Self::new(mtx.calculated_fee.unwrap(), mtx.calculated_mass.unwrap(), mtx.id())
+    }
+
+    pub(crate) fn id(&self) -> TransactionId {
+        self.id
+    }
+
+    pub(crate) fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    pub(crate) fn mass(&self) -> u64 {
+        self.mass
+    }
+
+    pub(crate) fn fee_rate(&self) -> f64 {
+        self.fee as f64 / self.mass as f64
+    }
+
+    /// Compares the fee rates of two keys exactly, by cross multiplying fees and masses
+    pub(crate) fn cmp_fee_rate(&self, other: &Self) -> Ordering {
+        (self.fee as u128 * other.mass as u128).cmp(&(other.fee as u128 * self.mass as u128))
+    }
+}
+
+impl Ord for FeeRateKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_fee_rate(other).then(self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for FeeRateKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Ord for MempoolTransaction {
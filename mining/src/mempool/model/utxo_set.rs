@@ -68,6 +68,21 @@ impl MempoolUtxoSet {
         self.outpoint_owner_id.get(outpoint)
     }
 
+    /// Returns the ids of the mempool transactions, other than `transaction`, already spending an output one of this
+    /// transaction inputs spends
+    pub(crate) fn get_double_spend_transaction_ids(&self, transaction: &MutableTransaction) -> Vec<TransactionId> {
+        let transaction_id = transaction.id();
+        let mut double_spends = Vec::new();
+        for input in transaction.tx.inputs.iter() {
+            if let Some(existing_transaction_id) = self.get_outpoint_owner_id(&input.previous_outpoint) {
+                if *existing_transaction_id != transaction_id && !double_spends.contains(existing_transaction_id) {
+                    double_spends.push(*existing_transaction_id);
+                }
+            }
+        }
+        double_spends
+    }
+
     /// Make sure no other transaction in the mempool is already spending an output one of this transaction inputs spends
     pub(crate) fn check_double_spends(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         let transaction_id = transaction.id();
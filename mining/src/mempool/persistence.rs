@@ -0,0 +1,80 @@
+use crate::{
+    mempool::{
+        errors::{PersistenceError, PersistenceResult},
+        model::pool::Pool,
+        tx::Priority,
+        Mempool,
+    },
+    model::topological_index::TopologicalIndex,
+};
+use kaspa_consensus_core::tx::Transaction;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MEMPOOL_FILE_MAGIC: [u8; 8] = *b"KASMEMPL";
+
+/// The current mempool file format version
+pub const MEMPOOL_FILE_VERSION: u32 = 1;
+
+/// A mempool transaction as saved to disk
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PersistedTransaction {
+    pub(crate) transaction: Transaction,
+    pub(crate) priority: Priority,
+    pub(crate) is_orphan: bool,
+}
+
+impl Mempool {
+    /// Returns all the transactions of the transaction pool in topological order, followed by all the orphans,
+    /// so that reinserting them in this order never turns a transaction into an orphan because of a missing
+    /// parent.
+    pub(crate) fn get_persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        let ids = self.transaction_pool.topological_index().unwrap_or_else(|_| self.transaction_pool.all().keys().copied().collect());
+        let persisted = |transaction: &Transaction, priority, is_orphan| PersistedTransaction {
+            transaction: transaction.clone(),
+            priority,
+            is_orphan,
+        };
+        ids.iter()
+            .map(|id| self.transaction_pool.get(id).unwrap())
+            .map(|x| persisted(&x.mtx.tx, x.priority, false))
+            .chain(self.orphan_pool.all().values().map(|x| persisted(&x.mtx.tx, x.priority, true)))
+            .collect()
+    }
+}
+
+/// Writes `transactions` to the mempool file at `path`.
+///
+/// The file is first written under a temporary name and then renamed, so an interrupted save never
+/// corrupts a previously saved file.
+pub(crate) fn save_transactions(path: &Path, transactions: &[PersistedTransaction]) -> PersistenceResult<()> {
+    let temp_path = path.with_extension("tmp");
+    {
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        writer.write_all(&MEMPOOL_FILE_MAGIC)?;
+        bincode::serialize_into(&mut writer, &MEMPOOL_FILE_VERSION)?;
+        bincode::serialize_into(&mut writer, transactions)?;
+        writer.flush()?;
+    }
+    fs::rename(temp_path, path)?;
+    Ok(())
+}
+
+/// Reads the transactions of the mempool file at `path`
+pub(crate) fn load_transactions(path: &Path) -> PersistenceResult<Vec<PersistedTransaction>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut magic = [0u8; MEMPOOL_FILE_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != MEMPOOL_FILE_MAGIC {
+        return Err(PersistenceError::InvalidMagic);
+    }
+    let version: u32 = bincode::deserialize_from(&mut reader)?;
+    if version != MEMPOOL_FILE_VERSION {
+        return Err(PersistenceError::UnsupportedVersion(version, MEMPOOL_FILE_VERSION));
+    }
+    Ok(bincode::deserialize_from(&mut reader)?)
+}
@@ -0,0 +1,94 @@
+use crate::{
+    mempool::{
+        errors::{ReplacementError, RuleResult},
+        model::pool::Pool,
+        tx::RbfPolicy,
+        Mempool,
+    },
+    model::TransactionIdSet,
+};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
+use kaspa_consensus_notify::notification::MempoolRemovalReason;
+use std::sync::Arc;
+
+impl Mempool {
+    /// Returns the id of the mempool transaction `transaction` double spends and is candidate for replacing
+    /// according to `rbf_policy`.
+    ///
+    /// A replacement transaction is allowed to double spend a single mempool transaction.
+    pub(crate) fn get_replaced_transaction_id(
+        &self,
+        transaction: &MutableTransaction,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<Option<TransactionId>> {
+        if rbf_policy == RbfPolicy::Forbidden {
+            self.transaction_pool.check_double_spends(transaction)?;
+            return Ok(None);
+        }
+        let double_spends = self.transaction_pool.get_double_spend_transaction_ids(transaction);
+        match double_spends.len() {
+            0 if rbf_policy == RbfPolicy::Mandatory => Err(ReplacementError::RejectNoConflict(transaction.id()).into()),
+            0 => Ok(None),
+            1 => Ok(Some(double_spends[0])),
+            _ => Err(ReplacementError::RejectMultipleConflicts(transaction.id(), double_spends[0], double_spends[1]).into()),
+        }
+    }
+
+    /// Validates that the fully populated `transaction` can replace the mempool transaction `replaced_id`
+    /// and, if so, removes the replaced transaction and all its redeemers from the mempool.
+    ///
+    /// The replacement rules are:
+    ///
+    /// - the replaced transaction and its redeemers, all evicted, must not exceed the configured maximum count,
+    /// - `transaction` must not spend any output of an evicted transaction,
+    /// - the fee rate of `transaction` must be strictly higher than the fee rate of the replaced transaction,
+    /// - the fee of `transaction` must cover the fees of all evicted transactions plus its own minimum relay fee.
+    ///
+    /// Returns the replaced transaction.
+    pub(crate) fn replace_transaction(
+        &mut self,
+        transaction: &MutableTransaction,
+        replaced_id: &TransactionId,
+    ) -> RuleResult<Arc<Transaction>> {
+        self.validate_replacement(transaction, replaced_id)?;
+        let replaced_transaction = self.transaction_pool.get(replaced_id).unwrap().mtx.tx.clone();
+        self.remove_transaction(replaced_id, true, MempoolRemovalReason::DoubleSpent)?;
+        Ok(replaced_transaction)
+    }
+
+    pub(crate) fn validate_replacement(&self, transaction: &MutableTransaction, replaced_id: &TransactionId) -> RuleResult<()> {
+        let transaction_id = transaction.id();
+        let replaced = self.transaction_pool.get(replaced_id).unwrap();
+
+        let mut evicted: TransactionIdSet = self.transaction_pool.get_redeemer_ids_in_pool(replaced_id);
+        evicted.insert(*replaced_id);
+        if evicted.len() as u64 > self.config.maximum_replacement_evictions {
+            return Err(ReplacementError::RejectTooManyEvictions(
+                transaction_id,
+                *replaced_id,
+                evicted.len(),
+                self.config.maximum_replacement_evictions,
+            )
+            .into());
+        }
+
+        if let Some(input) = transaction.tx.inputs.iter().find(|x| evicted.contains(&x.previous_outpoint.transaction_id)) {
+            return Err(ReplacementError::RejectSpendsEvicted(transaction_id, input.previous_outpoint.transaction_id).into());
+        }
+
+        let fee = transaction.calculated_fee.unwrap();
+        let mass = transaction.calculated_mass.unwrap();
+        let fee_rate = fee as f64 / mass as f64;
+        if fee_rate <= replaced.fee_rate() {
+            return Err(ReplacementError::RejectLowFeeRate(transaction_id, fee_rate, replaced.fee_rate(), *replaced_id).into());
+        }
+
+        let evicted_fees: u64 = evicted.iter().map(|id| self.transaction_pool.get(id).unwrap().mtx.calculated_fee.unwrap()).sum();
+        let minimum_fee = evicted_fees + self.minimum_required_transaction_relay_fee(mass);
+        if fee < minimum_fee {
+            return Err(ReplacementError::RejectInsufficientFee(transaction_id, fee, minimum_fee, *replaced_id).into());
+        }
+
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use kaspa_consensus_core::{
     api::ConsensusApi,
     tx::{MutableTransaction, TransactionId},
 };
+use kaspa_consensus_notify::notification::MempoolRemovalReason;
 use kaspa_core::debug;
 
 use super::tx::Priority;
@@ -42,7 +43,7 @@ impl Mempool {
                 } else {
                     debug!("Removing transaction {0}, it failed revalidation", transaction_id);
                     // This call cleanly removes the invalid transaction and its redeemers.
-                    self.remove_transaction(transaction_id, true)?;
+                    self.remove_transaction(transaction_id, true, MempoolRemovalReason::Invalid)?;
                 }
             }
         }
@@ -1,18 +1,22 @@
 use std::sync::Arc;
 
-use crate::mempool::{
-    errors::{RuleError, RuleResult},
-    model::{pool::Pool, tx::MempoolTransaction},
-    Mempool,
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{package::TransactionPackage, pool::Pool, tx::MempoolTransaction},
+        Mempool,
+    },
+    model::tx_insert::{TransactionAcceptance, TransactionInsertion},
 };
 use kaspa_consensus_core::{
     api::ConsensusApi,
     constants::UNACCEPTED_DAA_SCORE,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
+use kaspa_consensus_notify::notification::MempoolRemovalReason;
 use kaspa_core::info;
 
-use super::tx::{Orphan, Priority};
+use super::tx::{Orphan, Priority, RbfPolicy};
 
 impl Mempool {
     pub(crate) fn validate_and_insert_transaction(
@@ -21,8 +25,9 @@ impl Mempool {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> RuleResult<Vec<Arc<Transaction>>> {
-        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan)
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionInsertion> {
+        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan, rbf_policy)
     }
 
     pub(crate) fn validate_and_insert_mutable_transaction(
@@ -31,20 +36,28 @@ impl Mempool {
         mut transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> RuleResult<Vec<Arc<Transaction>>> {
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionInsertion> {
         // Populate mass in the beginning, it will be used in multiple places throughout the validation and insertion.
         transaction.calculated_mass = Some(consensus.calculate_transaction_mass(&transaction.tx));
 
-        self.validate_transaction_pre_utxo_entry(&transaction)?;
+        let replaced_id = self.validate_transaction_pre_utxo_entry(&transaction, rbf_policy)?;
 
         match self.populate_entries_and_try_validate(consensus, &mut transaction) {
             Ok(_) => {}
             Err(RuleError::RejectMissingOutpoint) => {
+                // The fee of an orphan is unknown so it cannot replace a mempool transaction
+                if replaced_id.is_some() {
+                    self.transaction_pool.check_double_spends(&transaction)?;
+                }
                 if orphan == Orphan::Forbidden {
                     return Err(RuleError::RejectDisallowedOrphan(transaction.id()));
                 }
-                self.orphan_pool.try_add_orphan(consensus, transaction, priority)?;
-                return Ok(vec![]);
+                let transaction_id = transaction.id();
+                let evicted_orphans = self.orphan_pool.try_add_orphan(consensus, transaction, priority)?;
+                self.record_all_removed(&evicted_orphans, true, MempoolRemovalReason::Evicted);
+                self.record_added(&transaction_id, true);
+                return Ok(TransactionInsertion::default());
             }
             Err(err) => {
                 return Err(err);
@@ -53,20 +66,135 @@ impl Mempool {
 
         self.validate_transaction_in_context(&transaction)?;
 
+        // Remove the replaced transaction and its redeemers, if the replacement is valid
+        let replaced_transaction = match replaced_id {
+            Some(replaced_id) => Some(self.replace_transaction(&transaction, &replaced_id)?),
+            None => None,
+        };
+
         // Before adding the transaction, check if there is room in the pool
-        self.transaction_pool.limit_transaction_count(1)?.iter().try_for_each(|x| self.remove_transaction(x, true))?;
+        self.transaction_pool
+            .limit_transaction_count(1, &[&transaction], priority)?
+            .iter()
+            .try_for_each(|x| self.remove_transaction(x, true, MempoolRemovalReason::Evicted))?;
 
         // Here the accepted transaction is cloned in order to prevent having self borrowed immutably for the
         // transaction reference and mutably for the call to process_orphans_after_accepted_transaction
         let accepted_transaction =
             self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority)?.mtx.tx.clone();
+        self.record_added(&accepted_transaction.id(), false);
         let accepted_orphans = self.process_orphans_after_accepted_transaction(consensus, &accepted_transaction)?;
-        Ok(accepted_orphans)
+        Ok(TransactionInsertion::new(replaced_transaction, accepted_orphans))
+    }
+
+    /// Validates and inserts a package of transactions, ordered so that every transaction follows its parents,
+    /// either all together or none of them.
+    ///
+    /// The package transactions can neither be orphans nor replace mempool transactions. If the mempool is full,
+    /// transactions having a lower fee rate than the package as a whole get evicted to make room for it.
+    ///
+    /// Returns the accepted transactions, the package transactions first followed by the unorphaned ones.
+    pub(crate) fn validate_and_insert_transaction_package(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+        priority: Priority,
+    ) -> RuleResult<Vec<Arc<Transaction>>> {
+        TransactionPackage::build(&transactions)?;
+
+        // The fees of chained transactions are known only once their parents are in the pool, so the package is
+        // inserted first, possibly exceeding the maximum transaction count until room is made for it below
+        let mut accepted_transactions = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            match self.insert_package_transaction(consensus, MutableTransaction::from_tx(transaction), priority) {
+                Ok(accepted_transaction) => accepted_transactions.push(accepted_transaction),
+                Err(err) => {
+                    self.remove_package_transactions(&accepted_transactions)?;
+                    return Err(RuleError::RejectPackageTransaction(transaction_id, Box::new(err)));
+                }
+            }
+        }
+
+        // Evict transactions by the fee rate of the whole package. Evictions are applied only once the package is fully
+        // inserted and room was found for it, so a rejected package leaves the mempool untouched
+        let package_transactions =
+            accepted_transactions.iter().map(|x| &self.transaction_pool.get(&x.id()).unwrap().mtx).collect::<Vec<_>>();
+        match self.transaction_pool.limit_transaction_count(0, &package_transactions, priority) {
+            Ok(evicted) => evicted.iter().try_for_each(|x| self.remove_transaction(x, true, MempoolRemovalReason::Evicted))?,
+            Err(err) => {
+                self.remove_package_transactions(&accepted_transactions)?;
+                return Err(err);
+            }
+        }
+
+        accepted_transactions.iter().for_each(|x| self.record_added(&x.id(), false));
+        let mut accepted_orphans = Vec::new();
+        for accepted_transaction in accepted_transactions.iter() {
+            accepted_orphans.extend(self.process_orphans_after_accepted_transaction(consensus, accepted_transaction)?);
+        }
+        accepted_transactions.extend(accepted_orphans);
+        Ok(accepted_transactions)
+    }
+
+    /// Rolls back the insertion of package transactions, the chained transactions first
+    fn remove_package_transactions(&mut self, accepted_transactions: &[Arc<Transaction>]) -> RuleResult<()> {
+        for accepted_transaction in accepted_transactions.iter().rev() {
+            let removed_transaction = self.transaction_pool.remove_transaction(&accepted_transaction.id())?;
+            self.transaction_pool.remove_transaction_utxos(&removed_transaction.mtx);
+        }
+        Ok(())
+    }
+
+    fn insert_package_transaction(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        mut transaction: MutableTransaction,
+        priority: Priority,
+    ) -> RuleResult<Arc<Transaction>> {
+        transaction.calculated_mass = Some(consensus.calculate_transaction_mass(&transaction.tx));
+        self.validate_transaction_pre_utxo_entry(&transaction, RbfPolicy::Forbidden)?;
+        self.populate_entries_and_try_validate(consensus, &mut transaction)?;
+        self.validate_transaction_in_context(&transaction)?;
+        Ok(self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority)?.mtx.tx.clone())
+    }
+
+    /// Runs `transaction` through the same validation pipeline as [`Self::validate_and_insert_mutable_transaction`]
+    /// but leaves the mempool untouched.
+    ///
+    /// A transaction with missing outpoints is rejected since it could only enter the orphan pool.
+    pub(crate) fn test_accept_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        mut transaction: MutableTransaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionAcceptance> {
+        transaction.calculated_mass = Some(consensus.calculate_transaction_mass(&transaction.tx));
+
+        let replaced_id = self.validate_transaction_pre_utxo_entry(&transaction, rbf_policy)?;
+        self.populate_entries_and_try_validate(consensus, &mut transaction)?;
+        self.validate_transaction_in_context(&transaction)?;
+
+        match replaced_id {
+            Some(replaced_id) => self.validate_replacement(&transaction, &replaced_id)?,
+            // A replacement frees at least the slot of the replaced transaction, otherwise there must be room
+            // in the pool, possibly by evicting transactions paying a lower fee rate
+            None => {
+                self.transaction_pool.limit_transaction_count(1, &[&transaction], priority)?;
+            }
+        }
+
+        Ok(TransactionAcceptance::new(transaction, replaced_id))
     }
 
-    fn validate_transaction_pre_utxo_entry(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    fn validate_transaction_pre_utxo_entry(
+        &self,
+        transaction: &MutableTransaction,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<Option<TransactionId>> {
         self.validate_transaction_in_isolation(transaction)?;
-        self.transaction_pool.check_double_spends(transaction)
+        self.get_replaced_transaction_id(transaction, rbf_policy)
     }
 
     fn validate_transaction_in_isolation(&self, transaction: &MutableTransaction) -> RuleResult<()> {
@@ -108,7 +236,9 @@ impl Mempool {
             // the transaction pool so we clone.
             added_transactions.push(transaction.mtx.tx.clone());
 
+            let transaction_id = transaction.id();
             self.transaction_pool.add_mempool_transaction(transaction)?;
+            self.record_added(&transaction_id, false);
         }
         Ok(added_transactions)
     }
@@ -176,8 +306,16 @@ impl Mempool {
         assert_eq!(transactions.len(), 1, "the list returned by remove_orphan is expected to contain exactly one transaction");
         let mut transaction = transactions.pop().unwrap();
 
-        consensus.validate_mempool_transaction_and_populate(&mut transaction.mtx)?;
-        self.validate_transaction_in_context(&transaction.mtx)?;
+        let validation_result = consensus
+            .validate_mempool_transaction_and_populate(&mut transaction.mtx)
+            .map_err(RuleError::from)
+            .and_then(|_| self.validate_transaction_in_context(&transaction.mtx));
+        let reason = match validation_result {
+            Ok(_) => MempoolRemovalReason::OrphanResolved,
+            Err(_) => MempoolRemovalReason::Invalid,
+        };
+        self.record_removed(&transaction, true, reason);
+        validation_result?;
         transaction.added_at_daa_score = consensus.get_virtual_daa_score();
         Ok(transaction)
     }
@@ -11,14 +11,30 @@ pub(crate) struct CandidateTransaction {
     pub calculated_fee: u64,
     /// Populated mass
     pub calculated_mass: u64,
+    /// Total fee of the transaction package scoring this transaction
+    pub package_fee: u64,
+    /// Total mass of the transaction package scoring this transaction
+    pub package_mass: u64,
 }
 
 impl CandidateTransaction {
-    pub(crate) fn from_mutable(tx: &MutableTransaction) -> Self {
+    /// Builds a candidate scored by the fee rate of a package made either of the transaction alone
+    /// or of one of its descendants in the mempool along with all the ancestors of this descendant.
+    ///
+    /// The descendants cannot be included in the same block as the transaction, but including
+    /// the transaction makes them eligible for the next blocks.
+    pub(crate) fn from_mutable_in_package(tx: &MutableTransaction, package_fee: u64, package_mass: u64) -> Self {
         Self {
             tx: tx.tx.clone(),
             calculated_fee: tx.calculated_fee.expect("fee is expected to be populated"),
             calculated_mass: tx.calculated_mass.expect("mass is expected to be populated"),
+            package_fee,
+            package_mass,
         }
     }
+
+    /// Fee rate of the transaction package, in sompi per gram of mass
+    pub(crate) fn package_fee_rate(&self) -> f64 {
+        self.package_fee as f64 / self.package_mass as f64
+    }
 }
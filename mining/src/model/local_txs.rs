@@ -0,0 +1,154 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_consensus_notify::notification::MempoolRemovalReason;
+use std::collections::HashMap;
+
+/// The interval before the first rebroadcast of a local transaction, doubled after every rebroadcast
+pub(crate) const INITIAL_REBROADCAST_INTERVAL_MILLISECONDS: u64 = 30_000;
+
+/// The upper bound of the interval between two rebroadcasts of a local transaction
+pub(crate) const MAXIMUM_REBROADCAST_INTERVAL_MILLISECONDS: u64 = 30 * 60_000;
+
+/// How long the status of a local transaction which left the mempool remains available
+pub(crate) const REMOVED_STATUS_RETENTION_MILLISECONDS: u64 = 24 * 60 * 60_000;
+
+/// Status of a transaction submitted locally, i.e. through RPC, as opposed to the ones relayed by peers.
+///
+/// All times are Unix timestamps in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalTransactionStatus {
+    pub submitted_at: u64,
+    pub rebroadcast_count: u32,
+    /// The time the transaction is due for its next rebroadcast, while it is in the mempool
+    pub next_rebroadcast_at: u64,
+    /// Why and when the transaction left the mempool, `None` while it is in the mempool
+    pub removal: Option<(MempoolRemovalReason, u64)>,
+}
+
+impl LocalTransactionStatus {
+    fn new(submitted_at: u64) -> Self {
+        Self {
+            submitted_at,
+            rebroadcast_count: 0,
+            next_rebroadcast_at: submitted_at + INITIAL_REBROADCAST_INTERVAL_MILLISECONDS,
+            removal: None,
+        }
+    }
+
+    /// Returns true while the transaction is in the mempool, waiting for inclusion in a block
+    pub fn is_pending(&self) -> bool {
+        self.removal.is_none()
+    }
+}
+
+/// The statuses of the local transactions, kept while they are in the mempool and for
+/// [`REMOVED_STATUS_RETENTION_MILLISECONDS`] after they left it.
+#[derive(Default)]
+pub(crate) struct LocalTransactions {
+    statuses: HashMap<TransactionId, LocalTransactionStatus>,
+}
+
+impl LocalTransactions {
+    pub(crate) fn get(&self, transaction_id: &TransactionId) -> Option<&LocalTransactionStatus> {
+        self.statuses.get(transaction_id)
+    }
+
+    /// Starts tracking a local transaction entering the mempool, unless already pending
+    pub(crate) fn track(&mut self, transaction_id: TransactionId, now: u64) {
+        match self.statuses.get(&transaction_id) {
+            Some(status) if status.is_pending() => {}
+            _ => {
+                self.statuses.insert(transaction_id, LocalTransactionStatus::new(now));
+            }
+        }
+    }
+
+    /// Registers the removal of a transaction from the mempool, ignoring orphans resolved into
+    /// the transaction pool since they remain in the mempool
+    pub(crate) fn on_removed(&mut self, transaction_id: &TransactionId, reason: MempoolRemovalReason, now: u64) {
+        if reason == MempoolRemovalReason::OrphanResolved {
+            return;
+        }
+        if let Some(status) = self.statuses.get_mut(transaction_id) {
+            if status.is_pending() {
+                status.removal = Some((reason, now));
+            }
+        }
+    }
+
+    /// Drops the statuses of the transactions removed more than [`REMOVED_STATUS_RETENTION_MILLISECONDS`] ago
+    fn prune_removed(&mut self, now: u64) {
+        self.statuses.retain(|_, status| match status.removal {
+            Some((_, removed_at)) => removed_at + REMOVED_STATUS_RETENTION_MILLISECONDS > now,
+            None => true,
+        });
+    }
+
+    /// Returns the transactions of `transaction_ids` due for a rebroadcast and schedules their next one,
+    /// doubling the interval up to [`MAXIMUM_REBROADCAST_INTERVAL_MILLISECONDS`].
+    ///
+    /// Being called periodically, this is also where the expired statuses of removed transactions are dropped.
+    pub(crate) fn take_due_for_rebroadcast(
+        &mut self,
+        transaction_ids: impl IntoIterator<Item = TransactionId>,
+        now: u64,
+    ) -> Vec<TransactionId> {
+        self.prune_removed(now);
+        transaction_ids
+            .into_iter()
+            .filter(|transaction_id| match self.statuses.get_mut(transaction_id) {
+                Some(status) if status.is_pending() && status.next_rebroadcast_at <= now => {
+                    status.rebroadcast_count += 1;
+                    let interval = INITIAL_REBROADCAST_INTERVAL_MILLISECONDS
+                        .saturating_mul(1 << status.rebroadcast_count.min(16))
+                        .min(MAXIMUM_REBROADCAST_INTERVAL_MILLISECONDS);
+                    status.next_rebroadcast_at = now + interval;
+                    true
+                }
+                _ => false,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_hashes::Hash;
+
+    #[test]
+    fn test_rebroadcast_backoff_and_retention() {
+        let mut local_transactions = LocalTransactions::default();
+        let (id, other_id) = (Hash::from_u64_word(1), Hash::from_u64_word(2));
+        local_transactions.track(id, 0);
+
+        // The rebroadcast intervals double until reaching the maximum
+        let mut now = 0;
+        let mut intervals = vec![];
+        while intervals.len() < 8 {
+            let due = local_transactions.take_due_for_rebroadcast([id, other_id], now);
+            if !due.is_empty() {
+                assert_eq!(due, vec![id], "only tracked transactions can be due");
+                intervals.push(now);
+            }
+            now += 1_000;
+        }
+        let intervals = intervals.windows(2).map(|x| (x[1] - x[0]) / 1_000).collect::<Vec<_>>();
+        assert_eq!(intervals, vec![60, 120, 240, 480, 960, 1800, 1800]);
+        assert_eq!(local_transactions.get(&id).unwrap().rebroadcast_count, 8);
+
+        // A resolved orphan is still pending, an accepted transaction is no longer rebroadcast
+        local_transactions.on_removed(&id, MempoolRemovalReason::OrphanResolved, now);
+        assert!(local_transactions.get(&id).unwrap().is_pending());
+        local_transactions.on_removed(&id, MempoolRemovalReason::Accepted, now);
+        assert_eq!(local_transactions.get(&id).unwrap().removal, Some((MempoolRemovalReason::Accepted, now)));
+        assert!(local_transactions.take_due_for_rebroadcast([id], now + MAXIMUM_REBROADCAST_INTERVAL_MILLISECONDS).is_empty());
+
+        // The status of a removed transaction is dropped by the first periodic pass after the retention period
+        local_transactions.track(other_id, now);
+        local_transactions.on_removed(&other_id, MempoolRemovalReason::Expired, now + REMOVED_STATUS_RETENTION_MILLISECONDS);
+        assert!(local_transactions.get(&id).is_some());
+        local_transactions.take_due_for_rebroadcast([], now + REMOVED_STATUS_RETENTION_MILLISECONDS);
+        assert!(local_transactions.get(&id).is_none());
+        assert!(local_transactions.get(&other_id).is_some());
+    }
+}
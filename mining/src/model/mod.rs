@@ -2,8 +2,10 @@ use kaspa_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub(crate) mod candidate_tx;
+pub mod local_txs;
 pub mod owner_txs;
 pub mod topological_index;
+pub mod tx_insert;
 
 /// A set of unique transaction ids
 pub type TransactionIdSet = HashSet<TransactionId>;
@@ -0,0 +1,46 @@
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
+use std::sync::Arc;
+
+/// Outcome of the insertion of a transaction into the mempool
+#[derive(Debug, Default)]
+pub struct TransactionInsertion {
+    /// The mempool transaction replaced by the inserted transaction, if any. Its redeemers
+    /// were evicted from the mempool along with it.
+    pub replaced: Option<Arc<Transaction>>,
+    /// Transactions unorphaned and accepted into the mempool as a consequence of the insertion
+    pub accepted: Vec<Arc<Transaction>>,
+}
+
+impl TransactionInsertion {
+    pub fn new(replaced: Option<Arc<Transaction>>, accepted: Vec<Arc<Transaction>>) -> Self {
+        Self { replaced, accepted }
+    }
+}
+
+/// Outcome of the test of a transaction against the mempool rules, the transaction being left out of the mempool
+#[derive(Debug)]
+pub struct TransactionAcceptance {
+    /// The validated transaction, with its UTXO entries, mass and fee populated
+    pub transaction: MutableTransaction,
+    /// The id of the mempool transaction the tested transaction would replace, if any
+    pub replaced: Option<TransactionId>,
+}
+
+impl TransactionAcceptance {
+    pub fn new(transaction: MutableTransaction, replaced: Option<TransactionId>) -> Self {
+        Self { transaction, replaced }
+    }
+
+    pub fn mass(&self) -> u64 {
+        self.transaction.calculated_mass.unwrap()
+    }
+
+    pub fn fee(&self) -> u64 {
+        self.transaction.calculated_fee.unwrap()
+    }
+
+    /// The fee rate, in sompi per gram of mass
+    pub fn fee_rate(&self) -> f64 {
+        self.fee() as f64 / self.mass() as f64
+    }
+}
@@ -12,6 +12,8 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
 
     fn apply_virtual_chain_changed_subscription(&self, subscription: &VirtualChainChangedSubscription) -> Option<Self>;
 
+    /// Applies an address-filtered subscription, which is used by [`EventType::UtxosChanged`] as well as
+    /// by the mempool transaction events
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription) -> Option<Self>;
 
     fn apply_subscription(&self, subscription: &dyn Single) -> Option<Self> {
@@ -19,7 +21,7 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
                 subscription.as_any().downcast_ref::<VirtualChainChangedSubscription>().unwrap(),
             ),
-            EventType::UtxosChanged => {
+            EventType::UtxosChanged | EventType::MempoolTransactionAdded | EventType::MempoolTransactionRemoved => {
                 self.apply_utxos_changed_subscription(subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap())
             }
             _ => self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap()),
@@ -36,6 +36,25 @@ where
 
 pub type DynNotify<N> = Arc<dyn Notify<N>>;
 
+/// Snapshot of the listeners and subscriptions managed by a notifier
+#[derive(Clone, Debug)]
+pub struct NotifierStats {
+    pub name: &'static str,
+
+    /// Number of registered listeners
+    pub listeners: usize,
+
+    /// Number of listeners with an active subscription, by event type
+    pub subscriptions: EventArray<usize>,
+}
+
+/// Type-erased access to the [`NotifierStats`] of a notifier, whatever its notification and connection types
+pub trait NotifierStatsProvider: Send + Sync {
+    fn stats(&self) -> NotifierStats;
+}
+
+pub type DynNotifierStatsProvider = Arc<dyn NotifierStatsProvider>;
+
 // pub trait Registrar<N>: Send + Sync + Debug
 // where
 //     N: Notification,
@@ -132,6 +151,16 @@ where
     }
 }
 
+impl<N, C> NotifierStatsProvider for Notifier<N, C>
+where
+    N: Notification,
+    C: Connection<Notification = N>,
+{
+    fn stats(&self) -> NotifierStats {
+        self.inner.stats()
+    }
+}
+
 impl<N, C> Notify<N> for Notifier<N, C>
 where
     N: Notification,
@@ -339,6 +368,15 @@ where
         Ok(())
     }
 
+    fn stats(&self) -> NotifierStats {
+        let listeners = self.listeners.lock();
+        let subscriptions = EventArray::from_fn(|i| {
+            let event = EVENT_TYPE_ARRAY[i];
+            listeners.values().filter(|listener| listener.subscriptions[event].active()).count()
+        });
+        NotifierStats { name: self.name, listeners: listeners.len(), subscriptions }
+    }
+
     fn start_notify(&self, id: ListenerId, scope: Scope) -> Result<()> {
         self.execute_subscribe_command(id, scope, Command::Start)
     }
@@ -678,6 +716,7 @@ mod tests {
         converter::ConverterFrom,
         events::EVENT_TYPE_ARRAY,
         notification::test_helpers::*,
+        scope::{BlockAddedScope, VirtualChainChangedScope},
         subscriber::test_helpers::{SubscriptionManagerMock, SubscriptionMessage},
     };
     use async_channel::{unbounded, Receiver, Sender};
@@ -835,4 +874,22 @@ mod tests {
         let test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(SUBSCRIPTION_MANAGER_ID));
         test.run().await;
     }
+
+    #[test]
+    fn test_stats() {
+        let notifier = TestNotifier::new(EVENT_TYPE_ARRAY[..].into(), vec![], vec![], 1, "test");
+        let listeners = (0..3).map(|_| notifier.register_new_listener(TestConnection::new(unbounded().0))).collect::<Vec<_>>();
+        let block_added = Scope::BlockAdded(BlockAddedScope {});
+        notifier.try_start_notify(listeners[0], block_added.clone()).unwrap();
+        notifier.try_start_notify(listeners[1], block_added.clone()).unwrap();
+        notifier.try_start_notify(listeners[1], Scope::VirtualChainChanged(VirtualChainChangedScope::default())).unwrap();
+        notifier.try_stop_notify(listeners[0], block_added).unwrap();
+
+        let stats = notifier.stats();
+        assert_eq!(stats.name, "test");
+        assert_eq!(stats.listeners, 3);
+        assert_eq!(stats.subscriptions[EventType::BlockAdded], 1);
+        assert_eq!(stats.subscriptions[EventType::VirtualChainChanged], 1);
+        assert_eq!(stats.subscriptions[EventType::UtxosChanged], 0);
+    }
 }
@@ -35,9 +35,35 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    MempoolTransactionAdded,
+    MempoolTransactionRemoved,
 }
 }
 
+impl Scope {
+    /// Builds the scope of an address-filtered event type, returning `None` for event types having no address filter
+    pub fn with_addresses(event_type: EventType, addresses: Vec<Address>) -> Option<Self> {
+        match event_type {
+            EventType::UtxosChanged => Some(Scope::UtxosChanged(UtxosChangedScope::new(addresses))),
+            EventType::MempoolTransactionAdded => Some(Scope::MempoolTransactionAdded(MempoolTransactionAddedScope::new(addresses))),
+            EventType::MempoolTransactionRemoved => {
+                Some(Scope::MempoolTransactionRemoved(MempoolTransactionRemovedScope::new(addresses)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the addresses of an address-filtered scope, `None` otherwise
+    pub fn addresses(&self) -> Option<&Vec<Address>> {
+        match self {
+            Scope::UtxosChanged(scope) => Some(&scope.addresses),
+            Scope::MempoolTransactionAdded(scope) => Some(&scope.addresses),
+            Scope::MempoolTransactionRemoved(scope) => Some(&scope.addresses),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct BlockAddedScope {}
 
@@ -88,3 +114,29 @@ pub struct PruningPointUtxoSetOverrideScope {}
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct NewBlockTemplateScope {}
+
+macro_rules! address_scope {
+    ($name:ident) => {
+        #[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+        pub struct $name {
+            pub addresses: Vec<Address>,
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.addresses.len() == other.addresses.len() && self.addresses.iter().all(|x| other.addresses.contains(x))
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl $name {
+            pub fn new(addresses: Vec<Address>) -> Self {
+                Self { addresses }
+            }
+        }
+    };
+}
+
+address_scope!(MempoolTransactionAddedScope);
+address_scope!(MempoolTransactionRemovedScope);
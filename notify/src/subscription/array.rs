@@ -9,7 +9,9 @@ impl ArrayBuilder {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: SingleSubscription = match event_type {
                 EventType::VirtualChainChanged => Box::<single::VirtualChainChangedSubscription>::default(),
-                EventType::UtxosChanged => Box::<single::UtxosChangedSubscription>::default(),
+                EventType::UtxosChanged | EventType::MempoolTransactionAdded | EventType::MempoolTransactionRemoved => {
+                    Box::new(single::UtxosChangedSubscription::with_event_type(event_type, false, vec![]))
+                }
                 _ => Box::new(single::OverallSubscription::new(event_type, false)),
             };
             subscription
@@ -21,7 +23,9 @@ impl ArrayBuilder {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: CompoundedSubscription = match event_type {
                 EventType::VirtualChainChanged => Box::<compounded::VirtualChainChangedSubscription>::default(),
-                EventType::UtxosChanged => Box::<compounded::UtxosChangedSubscription>::default(),
+                EventType::UtxosChanged | EventType::MempoolTransactionAdded | EventType::MempoolTransactionRemoved => {
+                    Box::new(compounded::UtxosChangedSubscription::new(event_type))
+                }
                 _ => Box::new(compounded::OverallSubscription::new(event_type)),
             };
             subscription
@@ -1,7 +1,7 @@
 use super::{Compounded, Mutation, Subscription};
 use crate::{
     events::EventType,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{Scope, VirtualChainChangedScope},
     subscription::Command,
 };
 use kaspa_addresses::Address;
@@ -149,29 +149,50 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
-#[derive(Clone, Default, Debug, PartialEq, Eq)]
+/// Compounded subscription filtered by a set of addresses.
+///
+/// Used by [`EventType::UtxosChanged`] and by the mempool transaction events.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UtxosChangedSubscription {
+    event_type: EventType,
     all: usize,
     addresses: HashMap<Address, usize>,
 }
 
+impl UtxosChangedSubscription {
+    pub fn new(event_type: EventType) -> Self {
+        assert!(Scope::with_addresses(event_type, vec![]).is_some(), "event type {event_type:?} has no address scope");
+        Self { event_type, all: 0, addresses: HashMap::default() }
+    }
+
+    fn scope_of(&self, addresses: Vec<Address>) -> Scope {
+        Scope::with_addresses(self.event_type, addresses).unwrap()
+    }
+}
+
+impl Default for UtxosChangedSubscription {
+    fn default() -> Self {
+        Self::new(EventType::UtxosChanged)
+    }
+}
+
 impl Compounded for UtxosChangedSubscription {
     fn compound(&mut self, mutation: Mutation) -> Option<Mutation> {
         assert_eq!(self.event_type(), mutation.event_type());
-        if let Scope::UtxosChanged(mut scope) = mutation.scope {
+        if let Some(mut scope_addresses) = mutation.scope.addresses().cloned() {
             match mutation.command {
                 Command::Start => {
-                    if scope.addresses.is_empty() {
+                    if scope_addresses.is_empty() {
                         // Add All
                         self.all += 1;
                         if self.all == 1 {
-                            return Some(Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::default())));
+                            return Some(Mutation::new(Command::Start, self.scope_of(vec![])));
                         }
                     } else {
                         // Add(A)
                         let mut added = vec![];
                         // Make sure no duplicate exists in addresses
-                        let addresses: HashSet<Address> = scope.addresses.drain(0..).collect();
+                        let addresses: HashSet<Address> = scope_addresses.drain(0..).collect();
                         for address in addresses {
                             self.addresses.entry(address.clone()).and_modify(|counter| *counter += 1).or_insert_with(|| {
                                 added.push(address);
@@ -179,16 +200,16 @@ impl Compounded for UtxosChangedSubscription {
                             });
                         }
                         if !added.is_empty() && self.all == 0 {
-                            return Some(Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::new(added))));
+                            return Some(Mutation::new(Command::Start, self.scope_of(added)));
                         }
                     }
                 }
                 Command::Stop => {
-                    if !scope.addresses.is_empty() {
+                    if !scope_addresses.is_empty() {
                         // Remove(R)
                         let mut removed = vec![];
                         // Make sure no duplicate exists in addresses
-                        let addresses: HashSet<Address> = scope.addresses.drain(0..).collect();
+                        let addresses: HashSet<Address> = scope_addresses.drain(0..).collect();
                         for address in addresses {
                             assert!(self.addresses.contains_key(&address));
                             self.addresses.entry(address.clone()).and_modify(|counter| {
@@ -203,7 +224,7 @@ impl Compounded for UtxosChangedSubscription {
                             self.addresses.remove(x);
                         });
                         if !removed.is_empty() && self.all == 0 {
-                            return Some(Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::new(removed))));
+                            return Some(Mutation::new(Command::Stop, self.scope_of(removed)));
                         }
                     } else {
                         // Remove All
@@ -211,12 +232,9 @@ impl Compounded for UtxosChangedSubscription {
                         self.all -= 1;
                         if self.all == 0 {
                             if !self.addresses.is_empty() {
-                                return Some(Mutation::new(
-                                    Command::Start,
-                                    Scope::UtxosChanged(UtxosChangedScope::new(self.addresses.keys().cloned().collect())),
-                                ));
+                                return Some(Mutation::new(Command::Start, self.scope_of(self.addresses.keys().cloned().collect())));
                             } else {
-                                return Some(Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::default())));
+                                return Some(Mutation::new(Command::Stop, self.scope_of(vec![])));
                             }
                         }
                     }
@@ -230,7 +248,7 @@ impl Compounded for UtxosChangedSubscription {
 impl Subscription for UtxosChangedSubscription {
     #[inline(always)]
     fn event_type(&self) -> EventType {
-        EventType::UtxosChanged
+        self.event_type
     }
 
     fn active(&self) -> bool {
@@ -239,7 +257,7 @@ impl Subscription for UtxosChangedSubscription {
 
     fn scope(&self) -> Scope {
         let addresses = if self.all > 0 { vec![] } else { self.addresses.keys().cloned().collect() };
-        Scope::UtxosChanged(UtxosChangedScope::new(addresses))
+        self.scope_of(addresses)
     }
 }
 
@@ -247,7 +265,10 @@ impl Subscription for UtxosChangedSubscription {
 mod tests {
     use super::super::*;
     use super::*;
-    use crate::{address::test_helpers::get_3_addresses, scope::BlockAddedScope};
+    use crate::{
+        address::test_helpers::get_3_addresses,
+        scope::{BlockAddedScope, MempoolTransactionAddedScope, UtxosChangedScope},
+    };
     use std::panic::AssertUnwindSafe;
 
     struct Step {
@@ -393,4 +414,45 @@ mod tests {
         let result = std::panic::catch_unwind(AssertUnwindSafe(|| state.compound(remove_0())));
         assert!(result.is_err(), "{}: trying to remove an address when its counter is zero must panic", test.name);
     }
+
+    #[test]
+    #[allow(clippy::redundant_clone)]
+    fn test_mempool_transaction_added_compounding() {
+        let a_stock = get_3_addresses(true);
+
+        let a = |indexes: &[usize]| indexes.iter().map(|idx| (a_stock[*idx]).clone()).collect::<Vec<_>>();
+        let m = |command: Command, indexes: &[usize]| -> Mutation {
+            Mutation { command, scope: Scope::MempoolTransactionAdded(MempoolTransactionAddedScope { addresses: a(indexes) }) }
+        };
+        let none = || Box::new(UtxosChangedSubscription::new(EventType::MempoolTransactionAdded));
+
+        let add_all = || m(Command::Start, &[]);
+        let remove_all = || m(Command::Stop, &[]);
+        let add_0 = || m(Command::Start, &[0]);
+        let add_01 = || m(Command::Start, &[0, 1]);
+        let remove_0 = || m(Command::Stop, &[0]);
+        let remove_1 = || m(Command::Stop, &[1]);
+
+        let test = Test {
+            name: "MempoolTransactionAdded",
+            initial_state: none(),
+            steps: vec![
+                Step { name: "add a0 1", mutation: add_0(), result: Some(add_0()) },
+                Step { name: "add a0a1", mutation: add_01(), result: Some(m(Command::Start, &[1])) },
+                Step { name: "add all 1, masking a0a1", mutation: add_all(), result: Some(add_all()) },
+                Step { name: "remove a1, masked by all", mutation: remove_1(), result: None },
+                Step { name: "remove all 1, revealing a0", mutation: remove_all(), result: Some(add_0()) },
+                Step { name: "remove a0 2", mutation: remove_0(), result: None },
+                Step { name: "remove a0 1", mutation: remove_0(), result: Some(remove_0()) },
+            ],
+            final_state: none(),
+        };
+        let mut state = test.run();
+        assert_eq!(state.event_type(), EventType::MempoolTransactionAdded);
+
+        // Compounding a mutation of another event type must panic
+        let utxos_changed = Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::default()));
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| state.compound(utxos_changed)));
+        assert!(result.is_err(), "{}: compounding a mutation of another event type must panic", test.name);
+    }
 }
@@ -2,7 +2,7 @@ use super::{Mutation, Single, Subscription};
 use crate::{
     address::UtxoAddress,
     events::EventType,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{Scope, VirtualChainChangedScope},
     subscription::Command,
 };
 use kaspa_addresses::Address;
@@ -148,19 +148,33 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+/// Subscription filtered by a set of addresses.
+///
+/// Used by [`EventType::UtxosChanged`] and by the mempool transaction events, which all share the same
+/// address-based scope semantics.
+#[derive(Clone, Debug)]
 pub struct UtxosChangedSubscription {
+    event_type: EventType,
     active: bool,
     addresses: HashMap<ScriptPublicKey, UtxoAddress>,
 }
 
 impl UtxosChangedSubscription {
     pub fn new(active: bool, addresses: Vec<Address>) -> Self {
-        let mut subscription = Self { active, addresses: HashMap::default() };
+        Self::with_event_type(EventType::UtxosChanged, active, addresses)
+    }
+
+    pub fn with_event_type(event_type: EventType, active: bool, addresses: Vec<Address>) -> Self {
+        assert!(Scope::with_addresses(event_type, vec![]).is_some(), "event type {event_type:?} has no address scope");
+        let mut subscription = Self { event_type, active, addresses: HashMap::default() };
         subscription.set_addresses(addresses);
         subscription
     }
 
+    fn scope_of(&self, addresses: Vec<Address>) -> Scope {
+        Scope::with_addresses(self.event_type, addresses).unwrap()
+    }
+
     fn set_addresses(&mut self, addresses: Vec<Address>) -> &mut Self {
         self.addresses = addresses
             .into_iter()
@@ -194,9 +208,15 @@ impl UtxosChangedSubscription {
     }
 }
 
+impl Default for UtxosChangedSubscription {
+    fn default() -> Self {
+        Self { event_type: EventType::UtxosChanged, active: false, addresses: HashMap::default() }
+    }
+}
+
 impl PartialEq for UtxosChangedSubscription {
     fn eq(&self, other: &Self) -> bool {
-        if self.active == other.active && self.addresses.len() == other.addresses.len() {
+        if self.event_type == other.event_type && self.active == other.active && self.addresses.len() == other.addresses.len() {
             // HashMaps are considered equal if they contain the same keys
             return self.addresses.keys().all(|x| other.addresses.contains_key(x));
         }
@@ -207,6 +227,7 @@ impl Eq for UtxosChangedSubscription {}
 
 impl Hash for UtxosChangedSubscription {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.event_type.hash(state);
         self.active.hash(state);
 
         // Since item order in hash set is undefined, build a sorted vector
@@ -219,7 +240,10 @@ impl Hash for UtxosChangedSubscription {
 
 impl Single for UtxosChangedSubscription {
     fn mutate(&mut self, mutation: Mutation) -> Option<Vec<Mutation>> {
-        if let Scope::UtxosChanged(ref scope) = mutation.scope {
+        if mutation.event_type() != self.event_type {
+            return None;
+        }
+        if let Some(scope_addresses) = mutation.scope.addresses().cloned() {
             // Here we want the code to (almost) match a double entry table structure
             // by subscription state and by mutation
             #[allow(clippy::collapsible_else_if)]
@@ -233,63 +257,63 @@ impl Single for UtxosChangedSubscription {
                     // Here is an exception to the aforementioned goal
                     // Mutations Add(A) && All
                     self.active = true;
-                    self.set_addresses(scope.addresses.clone());
+                    self.set_addresses(scope_addresses.clone());
                     Some(vec![mutation])
                 }
             } else if !self.addresses.is_empty() {
                 // State Selected(S)
                 if !mutation.active() {
-                    if scope.addresses.is_empty() {
+                    if scope_addresses.is_empty() {
                         // Mutation None
                         self.active = false;
                         let removed = self.addresses.drain().map(|(_, x)| x.into()).collect();
-                        Some(vec![Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::new(removed)))])
+                        Some(vec![Mutation::new(Command::Stop, self.scope_of(removed))])
                     } else {
                         // Mutation Remove(R)
-                        let removed: Vec<Address> = scope.addresses.iter().filter(|x| self.remove_address(x)).cloned().collect();
+                        let removed: Vec<Address> = scope_addresses.iter().filter(|x| self.remove_address(x)).cloned().collect();
                         if self.addresses.is_empty() {
                             self.active = false;
                         }
                         match removed.is_empty() {
-                            false => Some(vec![Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::new(removed)))]),
+                            false => Some(vec![Mutation::new(Command::Stop, self.scope_of(removed))]),
                             true => None,
                         }
                     }
                 } else {
-                    if !scope.addresses.is_empty() {
+                    if !scope_addresses.is_empty() {
                         // Mutation Add(A)
-                        let added = scope.addresses.iter().filter(|x| self.insert_address(x)).cloned().collect::<Vec<_>>();
+                        let added = scope_addresses.iter().filter(|x| self.insert_address(x)).cloned().collect::<Vec<_>>();
                         match added.is_empty() {
-                            false => Some(vec![Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::new(added)))]),
+                            false => Some(vec![Mutation::new(Command::Start, self.scope_of(added))]),
                             true => None,
                         }
                     } else {
                         // Mutation All
                         let removed: Vec<Address> = self.addresses.drain().map(|(_, x)| x.into()).collect();
                         Some(vec![
-                            Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::new(removed))),
-                            Mutation::new(Command::Start, Scope::UtxosChanged(UtxosChangedScope::default())),
+                            Mutation::new(Command::Stop, self.scope_of(removed)),
+                            Mutation::new(Command::Start, self.scope_of(vec![])),
                         ])
                     }
                 }
             } else {
                 // State All
                 if !mutation.active() {
-                    if scope.addresses.is_empty() {
+                    if scope_addresses.is_empty() {
                         // Mutation None
                         self.active = false;
-                        Some(vec![Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::default()))])
+                        Some(vec![Mutation::new(Command::Stop, self.scope_of(vec![]))])
                     } else {
                         // Mutation Remove(R)
                         None
                     }
                 } else {
-                    if !scope.addresses.is_empty() {
+                    if !scope_addresses.is_empty() {
                         // Mutation Add(A)
-                        scope.addresses.iter().for_each(|x| {
+                        scope_addresses.iter().for_each(|x| {
                             self.insert_address(x);
                         });
-                        Some(vec![mutation, Mutation::new(Command::Stop, Scope::UtxosChanged(UtxosChangedScope::default()))])
+                        Some(vec![mutation, Mutation::new(Command::Stop, self.scope_of(vec![]))])
                     } else {
                         // Mutation All
                         None
@@ -304,7 +328,7 @@ impl Single for UtxosChangedSubscription {
 
 impl Subscription for UtxosChangedSubscription {
     fn event_type(&self) -> EventType {
-        EventType::UtxosChanged
+        self.event_type
     }
 
     fn active(&self) -> bool {
@@ -312,7 +336,7 @@ impl Subscription for UtxosChangedSubscription {
     }
 
     fn scope(&self) -> Scope {
-        Scope::UtxosChanged(UtxosChangedScope::new(self.addresses.values().map(|x| &**x).cloned().collect()))
+        self.scope_of(self.addresses.values().map(|x| &**x).cloned().collect())
     }
 }
 
@@ -320,7 +344,10 @@ impl Subscription for UtxosChangedSubscription {
 mod tests {
     use super::super::*;
     use super::*;
-    use crate::{address::test_helpers::get_3_addresses, scope::BlockAddedScope};
+    use crate::{
+        address::test_helpers::get_3_addresses,
+        scope::{BlockAddedScope, UtxosChangedScope},
+    };
     use std::collections::hash_map::DefaultHasher;
 
     #[test]
@@ -1,9 +1,10 @@
+use crate::flow_registry::FlowRegistry;
 use crate::flowcontext::{
+    ibd_peers::{BodiesRequest, IbdPeer, IbdPeers, MAX_IBD_HELPER_PEERS},
     orphans::{OrphanBlocksPool, MAX_ORPHANS},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
 };
-use crate::v5;
 use async_trait::async_trait;
 use kaspa_addressmanager::AddressManager;
 use kaspa_connectionmanager::ConnectionManager;
@@ -24,15 +25,15 @@ use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::{
     manager::MiningManager,
-    mempool::tx::{Orphan, Priority},
+    mempool::tx::{Orphan, Priority, RbfPolicy},
 };
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
     convert::model::version::Version,
     make_message,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
+    pb::{kaspad_message::Payload, InvRelayBlockMessage, KaspadMessage},
+    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router, UploadLimiter,
 };
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
@@ -45,12 +46,9 @@ use std::{
         Arc,
     },
 };
-use tokio::sync::RwLock as AsyncRwLock;
+use tokio::sync::{mpsc::Sender, RwLock as AsyncRwLock};
 use uuid::Uuid;
 
-/// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 5;
-
 pub struct FlowContextInner {
     pub node_id: PeerId,
     pub consensus_manager: Arc<ConsensusManager>,
@@ -66,6 +64,9 @@ pub struct FlowContextInner {
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
     mining_manager: Arc<MiningManager>,
     notification_root: Arc<ConsensusNotificationRoot>,
+    flow_registry: FlowRegistry,
+    ibd_upload_limiter: Option<UploadLimiter>,
+    ibd_peers: IbdPeers,
 }
 
 #[derive(Clone)]
@@ -118,6 +119,8 @@ impl FlowContext {
         notification_root: Arc<ConsensusNotificationRoot>,
     ) -> Self {
         let hub = Hub::new();
+        let ibd_upload_limiter = config.ibd_upload_rate_limit.map(UploadLimiter::new);
+        let transactions_spread = TransactionsSpread::new(hub.clone(), config.whitelist.clone());
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
@@ -125,7 +128,7 @@ impl FlowContext {
                 config,
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(MAX_ORPHANS)),
                 shared_block_requests: Arc::new(Mutex::new(HashSet::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                transactions_spread: AsyncRwLock::new(transactions_spread),
                 shared_transaction_requests: Arc::new(Mutex::new(HashSet::new())),
                 is_ibd_running: Default::default(),
                 ibd_peer_key: Default::default(),
@@ -134,6 +137,9 @@ impl FlowContext {
                 connection_manager: Default::default(),
                 mining_manager,
                 notification_root,
+                flow_registry: FlowRegistry::default(),
+                ibd_upload_limiter,
+                ibd_peers: Default::default(),
             }),
         }
     }
@@ -146,6 +152,11 @@ impl FlowContext {
         self.connection_manager.read().clone()
     }
 
+    /// Returns whether the peer belongs to a whitelisted network
+    pub fn is_whitelisted(&self, router: &Router) -> bool {
+        self.config.whitelist.contains(router.net_address().ip())
+    }
+
     pub fn consensus(&self) -> ConsensusInstance {
         self.consensus_manager.consensus()
     }
@@ -179,6 +190,28 @@ impl FlowContext {
         }
     }
 
+    /// Registers the peer as able to assist an IBD syncing from another peer, via block body requests sent to `sender`
+    pub fn register_ibd_peer(&self, router: Arc<Router>, sender: Sender<BodiesRequest>) {
+        self.ibd_peers.register(router, sender);
+    }
+
+    pub fn unregister_ibd_peer(&self, peer_key: PeerKey) {
+        self.ibd_peers.unregister(peer_key);
+    }
+
+    /// Returns the best ranked peers for assisting the IBD syncer (`syncer`) with downloading block bodies
+    pub fn ibd_helper_peers(&self, syncer: PeerKey) -> Vec<IbdPeer> {
+        self.ibd_peers.ranked_helpers(syncer, MAX_IBD_HELPER_PEERS, |router| self.is_whitelisted(router))
+    }
+
+    /// Waits until the global IBD upload rate limit (if set) allows sending `msg`. Should be called by
+    /// flows serving bulk IBD data right before enqueuing each message.
+    pub async fn throttle_ibd_upload(&self, msg: &KaspadMessage) {
+        if let Some(limiter) = self.ibd_upload_limiter.as_ref() {
+            limiter.acquire_for_message(msg).await;
+        }
+    }
+
     pub fn try_adding_block_request(&self, req: Hash) -> Option<RequestScope<Hash>> {
         if self.shared_block_requests.lock().insert(req) {
             Some(RequestScope::new(self.shared_block_requests.clone(), req))
@@ -207,6 +240,10 @@ impl FlowContext {
         self.orphans_pool.read().await.get_orphan_roots(consensus, orphan)
     }
 
+    pub async fn orphan_blocks_count(&self) -> usize {
+        self.orphans_pool.read().await.len()
+    }
+
     pub async fn unorphan_blocks(&self, consensus: &dyn ConsensusApi, root: Hash) -> Vec<Block> {
         self.orphans_pool.write().await.unorphan_blocks(consensus, root).await
     }
@@ -248,8 +285,7 @@ impl FlowContext {
         }
 
         if self.should_rebroadcast_transactions().await {
-            transactions_to_broadcast
-                .enqueue_chunk(self.mining_manager().revalidate_high_priority_transactions(consensus)?.into_iter());
+            transactions_to_broadcast.enqueue_chunk(self.mining_manager().rebroadcast_local_transactions(consensus)?.into_iter());
         }
 
         self.broadcast_transactions(transactions_to_broadcast).await
@@ -283,9 +319,48 @@ impl FlowContext {
         transaction: Transaction,
         orphan: Orphan,
     ) -> Result<(), ProtocolError> {
-        let accepted_transactions =
-            self.mining_manager().validate_and_insert_transaction(consensus, transaction, Priority::High, orphan)?;
-        self.broadcast_transactions(accepted_transactions.iter().map(|x| x.id())).await
+        let insertion = self.mining_manager().validate_and_insert_transaction(
+            consensus,
+            transaction,
+            Priority::High,
+            orphan,
+            RbfPolicy::Forbidden,
+        )?;
+        self.broadcast_transactions(insertion.accepted.iter().map(|x| x.id())).await
+    }
+
+    /// Adds a transaction replacing by fee the mempool transaction it double spends.
+    ///
+    /// Returns the replaced transaction.
+    pub async fn add_transaction_replacement(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: Transaction,
+    ) -> Result<Arc<Transaction>, ProtocolError> {
+        let insertion = self.mining_manager().validate_and_insert_transaction(
+            consensus,
+            transaction,
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Mandatory,
+        )?;
+        self.broadcast_transactions(insertion.accepted.iter().map(|x| x.id())).await?;
+        Ok(insertion.replaced.expect("a mandatory replacement always replaces a transaction"))
+    }
+
+    /// Adds a package of chained transactions, ordered so that every transaction follows its parents,
+    /// either all together or none of them.
+    ///
+    /// Returns the ids of the package transactions.
+    pub async fn add_transaction_package(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<TransactionId>, ProtocolError> {
+        let package_ids = transactions.iter().map(|x| x.id()).collect::<Vec<_>>();
+        let accepted = self.mining_manager().validate_and_insert_transaction_package(consensus, transactions, Priority::High)?;
+        self.broadcast_transactions(accepted.iter().map(|x| x.id())).await?;
+        Ok(package_ids)
     }
 
     /// Returns true if the time for a rebroadcast of the mempool high priority transactions has come.
@@ -321,11 +396,13 @@ impl ConnectionInitializer for FlowContext {
 
         // Build the local version message
         // Subnets are not currently supported
-        let mut self_version_message = Version::new(None, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
+        let self_protocol_version = self.flow_registry.max_version();
+        let mut self_version_message = Version::new(None, self.node_id, network_name.clone(), None, self_protocol_version);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
         // TODO: full and accurate version info
         // TODO: get number of live services
-        // TODO: disable_relay_tx from config/cmd
+        // Light nodes have no UTXO state hence cannot validate transactions, so we ask peers not to relay them
+        self_version_message.disable_relay_tx = self.config.is_light;
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message.into()).await?;
@@ -343,14 +420,15 @@ impl ConnectionInitializer for FlowContext {
             return Err(ProtocolError::WrongNetwork(network_name, peer_version.network));
         }
 
-        debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
+        // Negotiate the highest protocol version supported by both sides
+        let (applied_protocol_version, register_flows) = self.flow_registry.negotiate(peer_version.protocol_version)?;
+        debug!(
+            "protocol versions - self: {}, peer: {}, applied: {}",
+            self_protocol_version, peer_version.protocol_version, applied_protocol_version
+        );
 
-        // Register all flows according to version
-        let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            PROTOCOL_VERSION => (v5::register(self.clone(), router.clone()), PROTOCOL_VERSION),
-            // TODO: different errors for obsolete (low version) vs unknown (high)
-            v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
-        };
+        // Register all flows according to the applied version
+        let flows = register_flows(self.clone(), router.clone());
 
         // Build and register the peer properties
         let peer_properties = Arc::new(PeerProperties {
@@ -366,7 +444,7 @@ impl ConnectionInitializer for FlowContext {
         // Send and receive the ready signal
         handshake.exchange_ready_messages().await?;
 
-        info!("Registering p2p flows for peer {} for protocol version {}", router, peer_version.protocol_version);
+        info!("Registering p2p flows for peer {} for protocol version {}", router, applied_protocol_version);
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
@@ -0,0 +1,186 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow, v5, v6};
+use kaspa_p2p_lib::{common::ProtocolError, Router};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A function building and registering the full flow set of a specific P2P protocol version
+pub type FlowRegistrationFn = fn(FlowContext, Arc<Router>) -> Vec<Box<dyn Flow>>;
+
+/// Maps each supported P2P protocol version to the function registering its flow set.
+///
+/// During the handshake, the applied protocol version is negotiated as the highest version
+/// supported by both sides, that is, the highest registered version which is not above the
+/// version advertised by the peer.
+pub struct FlowRegistry {
+    versions: BTreeMap<u32, FlowRegistrationFn>,
+}
+
+impl FlowRegistry {
+    pub fn new() -> Self {
+        Self { versions: BTreeMap::new() }
+    }
+
+    /// Registers (or overrides) the flow set of protocol `version`
+    pub fn register(&mut self, version: u32, registration: FlowRegistrationFn) {
+        self.versions.insert(version, registration);
+    }
+
+    /// The highest supported protocol version. This is the version advertised to peers.
+    pub fn max_version(&self) -> u32 {
+        *self.versions.keys().next_back().expect("at least one protocol version is registered")
+    }
+
+    /// The lowest supported protocol version
+    pub fn min_version(&self) -> u32 {
+        *self.versions.keys().next().expect("at least one protocol version is registered")
+    }
+
+    pub fn supported_versions(&self) -> impl Iterator<Item = u32> + '_ {
+        self.versions.keys().copied()
+    }
+
+    /// Negotiates the highest protocol version supported by both this node and a peer advertising
+    /// `peer_version`, returning the applied version along with its flow registration function
+    pub fn negotiate(&self, peer_version: u32) -> Result<(u32, FlowRegistrationFn), ProtocolError> {
+        match self.versions.range(..=peer_version).next_back() {
+            Some((&version, &registration)) => Ok((version, registration)),
+            None => Err(ProtocolError::ObsoleteVersion(self.min_version(), peer_version)),
+        }
+    }
+}
+
+impl Default for FlowRegistry {
+    /// Builds a registry with all flow sets currently supported by this node.
+    ///
+    /// Note that nodes predating the registry reject any version other than 5, so they only accept
+    /// connections initiated by nodes advertising exactly that version.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(5, v5::register);
+        registry.register(6, v6::register);
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use kaspa_core::time::unix_now;
+    use kaspa_p2p_lib::{pb::VersionMessage, Adaptor, ConnectionInitializer, Hub, KaspadHandshake};
+    use kaspa_utils::networking::NetAddress;
+    use parking_lot::Mutex;
+    use std::{str::FromStr, time::Duration};
+    use uuid::Uuid;
+
+    fn empty_registration(_ctx: FlowContext, _router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+        vec![]
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        let mut registry = FlowRegistry::new();
+        registry.register(5, empty_registration);
+        registry.register(6, empty_registration);
+        registry.register(8, empty_registration);
+
+        assert_eq!(registry.min_version(), 5);
+        assert_eq!(registry.max_version(), 8);
+
+        // Tuples of (peer advertised version, expected negotiated version)
+        let tests = [(5, Some(5)), (6, Some(6)), (7, Some(6)), (8, Some(8)), (100, Some(8)), (4, None), (0, None)];
+        for (peer_version, expected) in tests {
+            match (registry.negotiate(peer_version), expected) {
+                (Ok((version, _)), Some(expected)) => assert_eq!(version, expected, "peer version {peer_version}"),
+                (Err(ProtocolError::ObsoleteVersion(min, remote)), None) => {
+                    assert_eq!(min, 5);
+                    assert_eq!(remote, peer_version);
+                }
+                (res, _) => panic!("unexpected negotiation result {:?} for peer version {}", res.map(|(v, _)| v), peer_version),
+            }
+        }
+    }
+
+    fn version_message(protocol_version: u32) -> VersionMessage {
+        VersionMessage {
+            protocol_version,
+            services: 0,
+            timestamp: unix_now() as i64,
+            address: None,
+            id: Vec::from(Uuid::new_v4().as_ref()),
+            user_agent: String::new(),
+            disable_relay_tx: false,
+            subnetwork_id: None,
+            network: "kaspa-mainnet".to_string(),
+        }
+    }
+
+    /// Handshakes like `FlowContext`, advertising and negotiating the versions of its registry
+    struct RegistryInitializer {
+        registry: FlowRegistry,
+        applied_version: Mutex<Option<u32>>,
+    }
+
+    #[async_trait]
+    impl ConnectionInitializer for RegistryInitializer {
+        async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+            let mut handshake = KaspadHandshake::new(&router);
+            router.start();
+            let peer_version = handshake.handshake(version_message(self.registry.max_version())).await?;
+            let (applied_version, _) = self.registry.negotiate(peer_version.protocol_version)?;
+            *self.applied_version.lock() = Some(applied_version);
+            handshake.exchange_ready_messages().await
+        }
+    }
+
+    impl RegistryInitializer {
+        fn new(registry: FlowRegistry) -> Arc<Self> {
+            Arc::new(Self { registry, applied_version: Mutex::new(None) })
+        }
+    }
+
+    /// Connects a node using `outbound_registry` to a node using `inbound_registry` and returns the
+    /// protocol versions applied by both sides
+    async fn handshake_versions(
+        outbound_registry: FlowRegistry,
+        inbound_registry: FlowRegistry,
+        ports: (u16, u16),
+    ) -> (Option<u32>, Option<u32>) {
+        let inbound = RegistryInitializer::new(inbound_registry);
+        let inbound_address = NetAddress::from_str(&format!("[::1]:{}", ports.0)).unwrap();
+        let inbound_adaptor = Adaptor::bidirectional(inbound_address, Hub::new(), inbound.clone()).unwrap();
+
+        let outbound = RegistryInitializer::new(outbound_registry);
+        let outbound_address = NetAddress::from_str(&format!("[::1]:{}", ports.1)).unwrap();
+        let outbound_adaptor = Adaptor::bidirectional(outbound_address, Hub::new(), outbound.clone()).unwrap();
+
+        outbound_adaptor
+            .connect_peer_with_retries(format!("[::1]:{}", ports.0), 16, Duration::from_secs(1))
+            .await
+            .expect("peer connection failed");
+
+        // Wait for handshake completion
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        // Peers are removed on handshake error
+        assert_eq!(outbound_adaptor.active_peers().len(), 1, "handshake failed -- outbound peer is missing");
+        assert_eq!(inbound_adaptor.active_peers().len(), 1, "handshake failed -- inbound peer is missing");
+
+        outbound_adaptor.close().await;
+        inbound_adaptor.close().await;
+
+        let applied_versions = (*outbound.applied_version.lock(), *inbound.applied_version.lock());
+        applied_versions
+    }
+
+    #[tokio::test]
+    async fn test_handshake_version_negotiation() {
+        let mut v5_registry = FlowRegistry::new();
+        v5_registry.register(5, v5::register);
+
+        // A v6 node connecting to a node supporting only v5 falls back to the v5 flow set
+        assert_eq!(handshake_versions(FlowRegistry::default(), v5_registry, (50063, 50064)).await, (Some(5), Some(5)));
+
+        // Two up-to-date nodes apply the v6 flow set
+        assert_eq!(handshake_versions(FlowRegistry::default(), FlowRegistry::default(), (50065, 50066)).await, (Some(6), Some(6)));
+    }
+}
@@ -0,0 +1,119 @@
+use kaspa_consensus_core::block::Block;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{KaspadMessagePayloadType, PeerKey, Router};
+use parking_lot::Mutex;
+use std::{cmp::Reverse, collections::HashMap, fmt::Display, sync::Arc};
+use tokio::sync::{mpsc::Sender, oneshot};
+
+/// The maximum number of peers assisting the IBD syncer with downloading block bodies
+pub const MAX_IBD_HELPER_PEERS: usize = 4;
+
+/// A request for downloading a chunk of block bodies from a peer which is not the IBD syncer
+pub struct BodiesRequest {
+    pub hashes: Vec<Hash>,
+    /// Receives the requested blocks, or `None` if the peer failed to provide them
+    pub response: oneshot::Sender<Option<Vec<Block>>>,
+}
+
+/// A connected peer able to serve block body requests on behalf of an ongoing IBD
+#[derive(Clone)]
+pub struct IbdPeer {
+    pub router: Arc<Router>,
+    pub sender: Sender<BodiesRequest>,
+}
+
+impl Display for IbdPeer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.router)
+    }
+}
+
+/// IBD related metrics of a peer, as collected by its router
+#[derive(Debug, Clone, Copy)]
+pub struct IbdPeerMetrics {
+    pub is_whitelisted: bool,
+    /// Whether the peer has relayed blocks to us, which indicates that it is synced
+    pub relays_blocks: bool,
+    /// Average rate (in bytes per second) at which the peer sent us blocks over the connection lifetime
+    pub throughput: u64,
+    /// Last ping duration in milliseconds, where zero means not yet measured
+    pub latency: u64,
+}
+
+impl IbdPeerMetrics {
+    pub fn from_router(router: &Router, is_whitelisted: bool) -> Self {
+        let traffic = router.traffic();
+        let block_bytes: u64 = [KaspadMessagePayloadType::IbdBlock, KaspadMessagePayloadType::Block]
+            .iter()
+            .filter_map(|msg_type| traffic.inbound.get(msg_type))
+            .map(|counter| counter.bytes)
+            .sum();
+        let connected_secs = (router.time_connected() / 1000).max(1);
+        Self {
+            is_whitelisted,
+            relays_blocks: traffic.inbound.contains_key(&KaspadMessagePayloadType::InvRelayBlock),
+            throughput: block_bytes / connected_secs,
+            latency: router.last_ping_duration(),
+        }
+    }
+
+    /// A sort key where lower means more preferred: whitelisted peers first, then by
+    /// descending throughput and finally by ascending latency (unmeasured latency last)
+    fn rank(&self) -> (bool, Reverse<u64>, u64) {
+        let latency = if self.latency == 0 { u64::MAX } else { self.latency };
+        (!self.is_whitelisted, Reverse(self.throughput), latency)
+    }
+}
+
+/// Registry of the peers currently able to assist IBD with downloading block bodies
+#[derive(Default)]
+pub struct IbdPeers {
+    peers: Mutex<HashMap<PeerKey, IbdPeer>>,
+}
+
+impl IbdPeers {
+    pub fn register(&self, router: Arc<Router>, sender: Sender<BodiesRequest>) {
+        self.peers.lock().insert(router.key(), IbdPeer { router, sender });
+    }
+
+    pub fn unregister(&self, key: PeerKey) {
+        self.peers.lock().remove(&key);
+    }
+
+    /// Returns up to `max_peers` synced peers other than `syncer`, ordered from most to least preferred
+    pub fn ranked_helpers(&self, syncer: PeerKey, max_peers: usize, is_whitelisted: impl Fn(&Router) -> bool) -> Vec<IbdPeer> {
+        let mut candidates = self
+            .peers
+            .lock()
+            .iter()
+            .filter(|(&key, _)| key != syncer)
+            .map(|(_, peer)| (IbdPeerMetrics::from_router(&peer.router, is_whitelisted(&peer.router)), peer.clone()))
+            .filter(|(metrics, _)| metrics.relays_blocks)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(metrics, _)| metrics.rank());
+        candidates.into_iter().take(max_peers).map(|(_, peer)| peer).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ibd_peer_ranking() {
+        let metrics =
+            |is_whitelisted, throughput, latency| IbdPeerMetrics { is_whitelisted, relays_blocks: true, throughput, latency };
+        let mut peers = [
+            ("slow", metrics(false, 10, 50)),
+            ("unmeasured", metrics(false, 0, 0)),
+            ("fast", metrics(false, 1000, 200)),
+            ("whitelisted", metrics(true, 0, 300)),
+            ("idle-near", metrics(false, 0, 20)),
+        ];
+        peers.sort_by_key(|(_, m)| m.rank());
+        assert_eq!(
+            peers.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            vec!["whitelisted", "fast", "slow", "idle-near", "unmeasured"]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod ibd_peers;
 pub mod orphans;
 pub(crate) mod process_queue;
 pub mod transactions;
@@ -33,6 +33,15 @@ impl OrphanBlocksPool {
         Self { orphans: IndexMap::with_capacity(max_orphans), max_orphans }
     }
 
+    /// Returns the number of orphan blocks in the pool
+    pub fn len(&self) -> usize {
+        self.orphans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orphans.is_empty()
+    }
+
     /// Adds the provided block to the orphan pool
     pub fn add_orphan(&mut self, orphan_block: Block) {
         if self.orphans.len() == self.max_orphans {
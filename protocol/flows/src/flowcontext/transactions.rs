@@ -6,8 +6,9 @@ use kaspa_p2p_lib::{
     common::ProtocolError,
     make_message,
     pb::{kaspad_message::Payload, InvTransactionsMessage, KaspadMessage},
-    Hub,
+    Hub, Router,
 };
+use kaspa_utils::networking::Whitelist;
 use std::time::{Duration, Instant};
 
 const REBROADCAST_INTERVAL: Duration = Duration::from_secs(30);
@@ -16,14 +17,22 @@ pub(crate) const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
 
 pub struct TransactionsSpread {
     hub: Hub,
+    /// Networks of whitelisted peers, which receive transaction invs immediately rather than in batches
+    whitelist: Whitelist,
     last_rebroadcast_time: Instant,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
-        Self { hub, last_rebroadcast_time: Instant::now(), transaction_ids: ProcessQueue::new(), last_broadcast_time: Instant::now() }
+    pub fn new(hub: Hub, whitelist: Whitelist) -> Self {
+        Self {
+            hub,
+            whitelist,
+            last_rebroadcast_time: Instant::now(),
+            transaction_ids: ProcessQueue::new(),
+            last_broadcast_time: Instant::now(),
+        }
     }
 
     /// Returns true if the time for a rebroadcast of the mempool high priority transactions has come.
@@ -43,14 +52,24 @@ impl TransactionsSpread {
     ///
     /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
     /// every [`BROADCAST_INTERVAL`] milliseconds or when the queue length is larger than the Inv message
-    /// capacity.
+    /// capacity. Whitelisted peers are exempt from this batching and receive the IDs immediately.
     ///
     /// _GO-KASPAD: EnqueueTransactionIDsForPropagation_
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(
         &mut self,
         transaction_ids: I,
     ) -> Result<(), ProtocolError> {
-        self.transaction_ids.enqueue_chunk(transaction_ids);
+        if self.whitelist.is_empty() {
+            self.transaction_ids.enqueue_chunk(transaction_ids);
+        } else {
+            let transaction_ids = transaction_ids.into_iter().collect_vec();
+            for chunk in transaction_ids.chunks(MAX_INV_PER_TX_INV_MSG) {
+                let ids = chunk.iter().map(|&x| x.into()).collect_vec();
+                let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids });
+                self.hub.broadcast_filtered(msg, |router| self.is_whitelisted(router)).await;
+            }
+            self.transaction_ids.enqueue_chunk(transaction_ids);
+        }
 
         let now = Instant::now();
         if now - self.last_broadcast_time < BROADCAST_INTERVAL && self.transaction_ids.len() < MAX_INV_PER_TX_INV_MSG {
@@ -68,7 +87,16 @@ impl TransactionsSpread {
         Ok(())
     }
 
+    /// Broadcasts a batched message to all peers which were not already sent its content immediately
     async fn broadcast(&self, msg: KaspadMessage) {
-        self.hub.broadcast(msg).await
+        if self.whitelist.is_empty() {
+            self.hub.broadcast(msg).await
+        } else {
+            self.hub.broadcast_filtered(msg, |router| !self.is_whitelisted(router)).await
+        }
+    }
+
+    fn is_whitelisted(&self, router: &Router) -> bool {
+        self.whitelist.contains(router.net_address().ip())
     }
 }
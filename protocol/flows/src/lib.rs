@@ -1,5 +1,7 @@
 pub mod flow_context;
+pub mod flow_registry;
 pub mod flow_trait;
 pub mod flowcontext;
 pub mod service;
 pub mod v5;
+pub mod v6;
@@ -66,6 +66,7 @@ impl AsyncService for P2pService {
             p2p_adaptor.clone(),
             self.outbound_target,
             self.inbound_limit,
+            self.flow_context.config.whitelist.clone(),
             self.dns_seeders,
             self.default_port,
             self.flow_context.address_manager.clone(),
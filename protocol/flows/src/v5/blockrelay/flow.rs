@@ -3,13 +3,16 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::ORPHAN_RESOLUTION_RANGE,
 };
-use kaspa_consensus_core::{api::ConsensusApi, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use kaspa_consensus_core::{api::ConsensusApi, block::Block, blockstatus::BlockStatus, errors::block::RuleError, header::Header};
 use kaspa_core::{debug, info};
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
     common::ProtocolError,
     dequeue, dequeue_with_timeout, make_message,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlockHeadersMessage,
+        RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router,
 };
 use kaspa_utils::option::OptionExtensions;
@@ -56,6 +59,9 @@ pub struct HandleRelayInvsFlow {
     msg_route: IncomingRoute,
     /// A channel sender for sending blocks to be handled by the IBD flow (of this peer)
     ibd_sender: Sender<Block>,
+    /// Indicates whether the peer serves header-only relay requests (protocol version 6 and above), in which case
+    /// `msg_route` also receives RelayBlockHeaders messages
+    header_relay: bool,
 }
 
 #[async_trait::async_trait]
@@ -80,8 +86,9 @@ impl HandleRelayInvsFlow {
         invs_route: IncomingRoute,
         msg_route: IncomingRoute,
         ibd_sender: Sender<Block>,
+        header_relay: bool,
     ) -> Self {
-        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender }
+        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender, header_relay }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -93,7 +100,9 @@ impl HandleRelayInvsFlow {
             let session = consensus.session().await;
 
             match session.get_block_status(inv.hash) {
-                None | Some(BlockStatus::StatusHeaderOnly) => {} // Continue processing this missing inv
+                None => {} // Continue processing this missing inv
+                // Light nodes keep only headers, so for them a header-only block is a known block
+                Some(BlockStatus::StatusHeaderOnly) if !self.ctx.config.is_light => {}
                 Some(BlockStatus::StatusInvalid) => {
                     // Report a protocol error
                     return Err(ProtocolError::OtherOwned(format!("sent inv of an invalid block {}", inv.hash)));
@@ -123,8 +132,9 @@ impl HandleRelayInvsFlow {
                 continue;
             };
 
-            if block.is_header_only() {
-                return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
+            if self.ctx.config.is_light {
+                self.process_relay_header(session.deref(), block).await?;
+                continue;
             }
 
             // Note we do not apply the heuristic below if inv was queued indirectly (as an orphan root), since
@@ -176,6 +186,23 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Processes a relay block on a light node. Only the header is validated and stored (and the block body,
+    /// if downloaded from a peer not serving header-only relay, is discarded). Since light nodes do
+    /// not track virtual state, the header is not relayed further and missing parents are always resolved via IBD
+    async fn process_relay_header(&mut self, consensus: &dyn ConsensusApi, block: Block) -> Result<(), ProtocolError> {
+        match consensus.validate_and_insert_block(Block::from_header_arc(block.header.clone())).await {
+            Ok(_) => {
+                info!("Accepted header {} via relay", block.hash());
+                Ok(())
+            }
+            Err(RuleError::MissingParents(missing_parents)) => {
+                debug!("Header {} has missing parents: {:?}", block.hash(), missing_parents);
+                self.trigger_ibd(block)
+            }
+            Err(rule_error) => Err(rule_error.into()),
+        }
+    }
+
     async fn enqueue_orphan_roots(&mut self, consensus: &dyn ConsensusApi, orphan: Hash) {
         if let Some(roots) = self.ctx.get_orphan_roots(consensus, orphan).await {
             if roots.is_empty() {
@@ -186,9 +213,16 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Requests the relay block from the peer. Light nodes only need the header, so they request just the header from
+    /// peers supporting header-only relay. Peers of protocol version 5 can serve only full blocks, so with such peers
+    /// light nodes fall back to downloading the full block, since they would otherwise be unable to follow the DAG
     async fn request_block(&mut self, requested_hash: Hash) -> Result<Option<(Block, RequestScope<Hash>)>, ProtocolError> {
         // Note: the request scope is returned and should be captured until block processing is completed
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else { return Ok(None); };
+        if self.ctx.config.is_light && self.header_relay {
+            let header = self.request_header(requested_hash).await?;
+            return Ok(Some((Block::from_header_arc(header), request_scope)));
+        }
         self.router
             .enqueue(make_message!(Payload::RequestRelayBlocks, RequestRelayBlocksMessage { hashes: vec![requested_hash.into()] }))
             .await?;
@@ -196,11 +230,28 @@ impl HandleRelayInvsFlow {
         let block: Block = msg.try_into()?;
         if block.hash() != requested_hash {
             Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
+        } else if block.is_header_only() {
+            Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())))
         } else {
             Ok(Some((block, request_scope)))
         }
     }
 
+    async fn request_header(&mut self, requested_hash: Hash) -> Result<Arc<Header>, ProtocolError> {
+        self.router
+            .enqueue(make_message!(
+                Payload::RequestRelayBlockHeaders,
+                RequestRelayBlockHeadersMessage { hashes: vec![requested_hash.into()] }
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::RelayBlockHeaders)?;
+        let headers: Vec<Arc<Header>> = msg.try_into()?;
+        match headers.as_slice() {
+            [header] if header.hash == requested_hash => Ok(header.clone()),
+            _ => Err(ProtocolError::OtherOwned(format!("unexpected relay headers response for requested block {}", requested_hash))),
+        }
+    }
+
     async fn process_orphan(&mut self, consensus: &dyn ConsensusApi, block: Block) -> Result<(), ProtocolError> {
         // Return if the block has been orphaned from elsewhere already
         if self.ctx.is_known_orphan(block.hash()).await {
@@ -213,17 +264,21 @@ impl HandleRelayInvsFlow {
             self.ctx.add_orphan(block).await;
             self.enqueue_orphan_roots(consensus, hash).await;
         } else {
-            // Send the block to IBD flow via the dedicated channel.
-            // Note that this is a non-blocking send and we don't care about being rejected if channel is full,
-            // since if IBD is already running, there is no need to trigger it
-            match self.ibd_sender.try_send(block) {
-                Ok(_) | Err(TrySendError::Full(_)) => {}
-                Err(TrySendError::Closed(_)) => return Err(ProtocolError::ConnectionClosed), // This indicates that IBD flow has exited
-            }
+            self.trigger_ibd(block)?;
         }
         Ok(())
     }
 
+    /// Sends the block to IBD flow via the dedicated channel.
+    /// Note that this is a non-blocking send and we don't care about being rejected if channel is full,
+    /// since if IBD is already running, there is no need to trigger it
+    fn trigger_ibd(&self, block: Block) -> Result<(), ProtocolError> {
+        match self.ibd_sender.try_send(block) {
+            Ok(_) | Err(TrySendError::Full(_)) => Ok(()),
+            Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed), // This indicates that IBD flow has exited
+        }
+    }
+
     /// Finds out whether the given block hash should be retrieved via the unorphaning
     /// mechanism or via IBD. This method sends a BlockLocator request to the peer with
     /// a limit of ORPHAN_RESOLUTION_RANGE. In the response, if we know none of the hashes,
@@ -0,0 +1,300 @@
+use crate::flowcontext::ibd_peers::{BodiesRequest, IbdPeer, MAX_IBD_HELPER_PEERS};
+use futures::future::select_all;
+use kaspa_consensus_core::block::Block;
+use kaspa_core::{debug, warn};
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::common::ProtocolError;
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Display,
+};
+use tokio::sync::{
+    mpsc::Sender,
+    oneshot::{self, error::TryRecvError},
+};
+
+/// The maximum number of chunks, starting at the next chunk to be handed out, which can be assigned for downloading.
+/// This bounds the number of downloaded chunks held in memory while waiting for a slow or rescheduled chunk.
+pub(super) const MAX_LOOKAHEAD_CHUNKS: usize = 4 * MAX_IBD_HELPER_PEERS;
+
+/// A peer able to download block bodies on behalf of the IBD syncer
+pub(super) trait BodiesHelper: Display {
+    fn sender(&self) -> &Sender<BodiesRequest>;
+}
+
+impl BodiesHelper for IbdPeer {
+    fn sender(&self) -> &Sender<BodiesRequest> {
+        &self.sender
+    }
+}
+
+struct HelperDownload<P> {
+    index: usize,
+    peer: P,
+    response: oneshot::Receiver<Option<Vec<Block>>>,
+}
+
+/// Schedules the download of block body chunks between the IBD syncer and helper peers.
+///
+/// Each helper is assigned a single chunk at a time, so faster peers naturally download more chunks. A chunk
+/// which failed downloading is rescheduled and the failing peer is no longer used. Downloaded chunks are
+/// handed out in their original (topological) order, and no chunk beyond [`MAX_LOOKAHEAD_CHUNKS`] from the
+/// next one to be handed out is assigned.
+pub(super) struct BodiesScheduler<'a, P: BodiesHelper = IbdPeer> {
+    chunks: Vec<&'a [Hash]>,
+    /// Indices of chunks which are yet to be assigned
+    pending: BTreeSet<usize>,
+    /// Downloaded chunks waiting for all previous chunks to be downloaded as well
+    downloaded: BTreeMap<usize, Vec<Block>>,
+    in_flight: Vec<HelperDownload<P>>,
+    idle_helpers: VecDeque<P>,
+    /// Index of the next chunk to be handed out
+    next: usize,
+}
+
+impl<'a, P: BodiesHelper> BodiesScheduler<'a, P> {
+    pub fn new(hashes: &'a [Hash], chunk_size: usize, helpers: Vec<P>) -> Self {
+        let chunks = hashes.chunks(chunk_size).collect::<Vec<_>>();
+        Self {
+            pending: (0..chunks.len()).collect(),
+            chunks,
+            downloaded: BTreeMap::new(),
+            in_flight: Vec::new(),
+            idle_helpers: helpers.into(),
+            next: 0,
+        }
+    }
+
+    /// Indicates whether all chunks were handed out
+    pub fn is_done(&self) -> bool {
+        self.next == self.chunks.len()
+    }
+
+    /// Indicates whether some helper peer is still available
+    pub fn has_helpers(&self) -> bool {
+        !self.idle_helpers.is_empty() || !self.in_flight.is_empty()
+    }
+
+    /// The lowest pending chunk index, unless it is beyond the look-ahead window
+    fn next_pending(&self) -> Option<usize> {
+        self.pending.first().copied().filter(|&index| index < self.next + MAX_LOOKAHEAD_CHUNKS)
+    }
+
+    /// Assigns pending chunks to idle helpers
+    pub fn dispatch_to_helpers(&mut self) {
+        while let Some(index) = self.next_pending() {
+            let Some(peer) = self.idle_helpers.pop_front() else { return };
+            self.pending.remove(&index);
+            let (sender, receiver) = oneshot::channel();
+            match peer.sender().try_send(BodiesRequest { hashes: self.chunks[index].to_vec(), response: sender }) {
+                Ok(()) => self.in_flight.push(HelperDownload { index, peer, response: receiver }),
+                Err(_) => {
+                    // The peer flow has exited in the meantime
+                    debug!("IBD helper peer {} is no longer available", peer);
+                    self.pending.insert(index);
+                }
+            }
+        }
+    }
+
+    /// Takes the next pending chunk for downloading directly from the syncer.
+    ///
+    /// Returns `None` if no chunk is pending within the look-ahead window, in which case the next chunk
+    /// to be handed out is being downloaded by a helper.
+    pub fn next_for_syncer(&mut self) -> Option<(usize, &'a [Hash])> {
+        let index = self.next_pending()?;
+        self.pending.remove(&index);
+        Some((index, self.chunks[index]))
+    }
+
+    pub fn complete(&mut self, index: usize, blocks: Vec<Block>) {
+        self.downloaded.insert(index, blocks);
+    }
+
+    /// Reschedules a chunk which failed downloading
+    pub fn reschedule(&mut self, index: usize) {
+        self.pending.insert(index);
+    }
+
+    /// Collects all helper downloads which have completed so far
+    pub fn collect_helpers(&mut self) {
+        let mut i = 0;
+        while i < self.in_flight.len() {
+            let response = match self.in_flight[i].response.try_recv() {
+                Ok(response) => response,
+                Err(TryRecvError::Closed) => None,
+                Err(TryRecvError::Empty) => {
+                    i += 1;
+                    continue;
+                }
+            };
+            let download = self.in_flight.swap_remove(i);
+            self.on_helper_response(download, response);
+        }
+    }
+
+    /// Waits for the completion of some helper download
+    pub async fn wait_for_helpers(&mut self) -> Result<(), ProtocolError> {
+        if self.in_flight.is_empty() {
+            return Err(ProtocolError::Other("no peers are left for downloading block bodies"));
+        }
+        let (response, i, _) = select_all(self.in_flight.iter_mut().map(|download| &mut download.response)).await;
+        let download = self.in_flight.swap_remove(i);
+        self.on_helper_response(download, response.ok().flatten());
+        Ok(())
+    }
+
+    fn on_helper_response(&mut self, download: HelperDownload<P>, response: Option<Vec<Block>>) {
+        match response {
+            Some(blocks) => {
+                self.downloaded.insert(download.index, blocks);
+                self.idle_helpers.push_back(download.peer);
+            }
+            None => {
+                warn!("IBD helper peer {} failed providing block bodies, rescheduling its chunk", download.peer);
+                self.pending.insert(download.index);
+            }
+        }
+    }
+
+    /// Hands out the next chunk in order, if already downloaded
+    pub fn next_ready(&mut self) -> Option<Vec<Block>> {
+        let blocks = self.downloaded.remove(&self.next)?;
+        self.next += 1;
+        Some(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc::{channel, Receiver};
+
+    struct TestHelper {
+        id: usize,
+        sender: Sender<BodiesRequest>,
+    }
+
+    impl Display for TestHelper {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "helper-{}", self.id)
+        }
+    }
+
+    impl BodiesHelper for TestHelper {
+        fn sender(&self) -> &Sender<BodiesRequest> {
+            &self.sender
+        }
+    }
+
+    fn test_hashes(count: u64) -> Vec<Hash> {
+        (0..count).map(Hash::from_u64_word).collect()
+    }
+
+    fn test_helpers(count: usize) -> (Vec<TestHelper>, Vec<Receiver<BodiesRequest>>) {
+        (0..count)
+            .map(|id| {
+                let (sender, receiver) = channel(1);
+                (TestHelper { id, sender }, receiver)
+            })
+            .unzip()
+    }
+
+    /// Answers the pending request of a helper with its blocks, or with a failure, and returns the requested hashes
+    fn answer(receiver: &mut Receiver<BodiesRequest>, succeed: bool) -> Vec<Hash> {
+        let request = receiver.try_recv().expect("the helper should have a pending request");
+        let blocks = request.hashes.iter().map(|&hash| Block::from_precomputed_hash(hash, vec![])).collect();
+        request.response.send(succeed.then_some(blocks)).unwrap();
+        request.hashes
+    }
+
+    fn block_hashes(blocks: Vec<Block>) -> Vec<Hash> {
+        blocks.iter().map(|block| block.hash()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_in_order_hand_out() {
+        let hashes = test_hashes(6);
+        let (helpers, mut receivers) = test_helpers(2);
+        let mut scheduler = BodiesScheduler::new(&hashes, 2, helpers);
+
+        // Chunks 0 and 1 go to the helpers, chunk 2 to the syncer
+        scheduler.dispatch_to_helpers();
+        let (index, chunk) = scheduler.next_for_syncer().unwrap();
+        assert_eq!(index, 2);
+        scheduler.complete(index, chunk.iter().map(|&hash| Block::from_precomputed_hash(hash, vec![])).collect());
+        assert!(scheduler.next_for_syncer().is_none());
+        assert!(scheduler.next_ready().is_none(), "chunk 2 must wait for chunks 0 and 1");
+
+        assert_eq!(answer(&mut receivers[1], true), hashes[2..4]);
+        scheduler.wait_for_helpers().await.unwrap();
+        assert!(scheduler.next_ready().is_none(), "chunk 1 must wait for chunk 0");
+
+        assert_eq!(answer(&mut receivers[0], true), hashes[0..2]);
+        scheduler.wait_for_helpers().await.unwrap();
+        let handed_out = std::iter::from_fn(|| scheduler.next_ready()).flat_map(block_hashes).collect::<Vec<_>>();
+        assert_eq!(handed_out, hashes);
+        assert!(scheduler.is_done());
+    }
+
+    #[tokio::test]
+    async fn test_failed_helper_is_rescheduled_and_not_reused() {
+        let hashes = test_hashes(4);
+        let (helpers, mut receivers) = test_helpers(2);
+        let mut scheduler = BodiesScheduler::new(&hashes, 2, helpers);
+        scheduler.dispatch_to_helpers();
+
+        // Helper 0 fails its chunk, which is then assigned to helper 1 once it completed its own
+        answer(&mut receivers[0], false);
+        scheduler.wait_for_helpers().await.unwrap();
+        assert_eq!(answer(&mut receivers[1], true), hashes[2..4]);
+        scheduler.collect_helpers();
+        scheduler.dispatch_to_helpers();
+        assert!(receivers[0].try_recv().is_err(), "a failed helper must not be reused");
+        assert_eq!(answer(&mut receivers[1], true), hashes[0..2]);
+        scheduler.wait_for_helpers().await.unwrap();
+
+        let handed_out = std::iter::from_fn(|| scheduler.next_ready()).flat_map(block_hashes).collect::<Vec<_>>();
+        assert_eq!(handed_out, hashes);
+        assert!(scheduler.is_done());
+    }
+
+    #[tokio::test]
+    async fn test_no_helpers_left() {
+        let hashes = test_hashes(4);
+        let (helpers, mut receivers) = test_helpers(1);
+        let mut scheduler = BodiesScheduler::new(&hashes, 2, helpers);
+        scheduler.dispatch_to_helpers();
+        assert!(scheduler.has_helpers());
+
+        // A dropped response counts as a failure as well
+        drop(receivers[0].try_recv().unwrap());
+        scheduler.wait_for_helpers().await.unwrap();
+        assert!(!scheduler.has_helpers());
+        assert!(scheduler.wait_for_helpers().await.is_err());
+
+        // The syncer can still download the rescheduled chunk
+        assert_eq!(scheduler.next_for_syncer().map(|(index, _)| index), Some(0));
+    }
+
+    #[test]
+    fn test_lookahead_is_bounded() {
+        let hashes = test_hashes(4 * MAX_LOOKAHEAD_CHUNKS as u64);
+        let (helpers, mut receivers) = test_helpers(MAX_LOOKAHEAD_CHUNKS + 1);
+        let mut scheduler = BodiesScheduler::new(&hashes, 1, helpers);
+
+        // Only the chunks within the window are assigned, to helpers or to the syncer
+        scheduler.dispatch_to_helpers();
+        let mut requests = receivers.iter_mut().filter_map(|receiver| receiver.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(requests.len(), MAX_LOOKAHEAD_CHUNKS);
+        assert!(scheduler.next_for_syncer().is_none());
+
+        // Handing out the next chunk moves the window forward
+        let request = requests.remove(0);
+        request.response.send(Some(vec![Block::from_precomputed_hash(request.hashes[0], vec![])])).unwrap();
+        scheduler.collect_helpers();
+        assert!(scheduler.next_ready().is_some());
+        assert_eq!(scheduler.next_for_syncer().map(|(index, _)| index), Some(MAX_LOOKAHEAD_CHUNKS));
+        assert!(scheduler.next_for_syncer().is_none());
+    }
+}
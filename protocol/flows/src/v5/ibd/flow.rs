@@ -1,5 +1,6 @@
 use crate::{
     flow_context::FlowContext,
+    flowcontext::ibd_peers::BodiesRequest,
     v5::{
         ibd::{HeadersChunkStream, TrustedEntryStream},
         Flow,
@@ -15,7 +16,7 @@ use kaspa_consensus_core::{
     BlockHashSet,
 };
 use kaspa_consensusmanager::StagingConsensus;
-use kaspa_core::{debug, info};
+use kaspa_core::{debug, info, warn};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
 use kaspa_p2p_lib::{
@@ -33,9 +34,9 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::mpsc::Receiver;
+use tokio::{select, sync::mpsc::Receiver};
 
-use super::{progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, IBD_BATCH_SIZE};
+use super::{bodies::BodiesScheduler, progress::ProgressReporter, HeadersChunk, PruningPointUtxosetChunkStream, IBD_BATCH_SIZE};
 
 /// Flow for managing IBD - Initial Block Download
 pub struct IbdFlow {
@@ -58,7 +59,9 @@ impl Flow for IbdFlow {
     }
 
     async fn start(&mut self) -> Result<(), ProtocolError> {
-        self.start_impl().await
+        let res = self.start_impl().await;
+        self.ctx.unregister_ibd_peer(self.router.key());
+        res
     }
 }
 
@@ -76,23 +79,54 @@ impl IbdFlow {
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
-        while let Some(relay_block) = self.relay_receiver.recv().await {
-            if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key()) {
-                info!("IBD started with peer {}", self.router);
-
-                match self.ibd(relay_block).await {
-                    Ok(_) => info!("IBD with peer {} completed successfully", self.router),
-                    Err(e) => {
-                        info!("IBD with peer {} completed with error: {:?}", self.router, e);
-                        return Err(e);
-                    }
+        // While not syncing from this peer, it can assist an IBD syncing from another peer by serving block body requests
+        let (bodies_sender, mut bodies_receiver) = tokio::sync::mpsc::channel(1);
+        self.ctx.register_ibd_peer(self.router.clone(), bodies_sender);
+
+        loop {
+            select! {
+                relay_block = self.relay_receiver.recv() => {
+                    let Some(relay_block) = relay_block else { break };
+                    self.handle_relay_block(relay_block).await?;
                 }
+                Some(request) = bodies_receiver.recv() => self.serve_bodies_request(request).await?,
             }
         }
 
         Ok(())
     }
 
+    async fn handle_relay_block(&mut self, relay_block: Block) -> Result<(), ProtocolError> {
+        if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key()) {
+            info!("IBD started with peer {}", self.router);
+
+            match self.ibd(relay_block).await {
+                Ok(_) => info!("IBD with peer {} completed successfully", self.router),
+                Err(e) => {
+                    info!("IBD with peer {} completed with error: {:?}", self.router, e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Downloads block bodies requested by the IBD flow of another (syncer) peer
+    async fn serve_bodies_request(&mut self, request: BodiesRequest) -> Result<(), ProtocolError> {
+        match self.request_block_bodies(&request.hashes).await {
+            Ok(blocks) => {
+                let _ = request.response.send(Some(blocks));
+                Ok(())
+            }
+            Err(err) => {
+                let _ = request.response.send(None);
+                Err(err)
+            }
+        }
+    }
+
+    /// Note: on light nodes the relay block might be header-only (see `HandleRelayInvsFlow::request_block`),
+    /// which suffices since only its header is used by header sync
     async fn ibd(&mut self, relay_block: Block) -> Result<(), ProtocolError> {
         let mut session = self.ctx.consensus().session_owned().await;
 
@@ -116,7 +150,9 @@ impl IbdFlow {
                 match self.ibd_with_headers_proof(&staging, negotiation_output.syncer_header_selected_tip, &relay_block).await {
                     Ok(()) => {
                         staging.commit();
-                        self.ctx.on_pruning_point_utxoset_override();
+                        if !self.ctx.config.is_light {
+                            self.ctx.on_pruning_point_utxoset_override();
+                        }
                         // This will reobtain the freshly committed staging consensus
                         session = self.ctx.consensus().session_owned().await;
                     }
@@ -128,6 +164,11 @@ impl IbdFlow {
             }
         }
 
+        // Light nodes keep only headers, so there are no bodies to sync
+        if self.ctx.config.is_light {
+            return Ok(());
+        }
+
         // Sync missing bodies in the past of syncer selected tip
         self.sync_missing_block_bodies(session.deref(), negotiation_output.syncer_header_selected_tip).await?;
 
@@ -188,7 +229,10 @@ impl IbdFlow {
         let pruning_point = self.sync_and_validate_pruning_proof(consensus).await?;
         self.sync_headers(consensus, syncer_header_selected_tip, pruning_point, relay_block).await?;
         self.validate_staging_timestamps(self.ctx.consensus().session().await.deref(), consensus)?;
-        self.sync_pruning_point_utxoset(consensus, pruning_point).await?;
+        // Light nodes do not maintain a UTXO set
+        if !self.ctx.config.is_light {
+            self.sync_pruning_point_utxoset(consensus, pruning_point).await?;
+        }
         Ok(())
     }
 
@@ -415,19 +459,45 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
         let high_header = consensus.get_header(*hashes.last().expect("hashes was non empty"))?;
         let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks");
 
-        let mut iter = hashes.chunks(IBD_BATCH_SIZE);
-        let (mut prev_jobs, mut prev_daa_score) =
-            self.queue_block_processing_chunk(consensus, iter.next().expect("hashes was non empty")).await?;
+        // Bodies are downloaded in parallel from the syncer and from the best ranked other peers. If the syncer
+        // fails providing bodies, the remaining chunks are downloaded from the other peers without restarting IBD
+        let helpers = self.ctx.ibd_helper_peers(self.router.key());
+        debug!("IBD: downloading {} block bodies with the assistance of {} additional peers", hashes.len(), helpers.len());
+        let mut scheduler = BodiesScheduler::new(&hashes, IBD_BATCH_SIZE, helpers);
+        let mut syncer_failed = false;
+        let mut prev_jobs: Vec<BlockValidationFuture> = Vec::new();
+        let mut prev_daa_score = 0;
+
+        while !scheduler.is_done() {
+            scheduler.dispatch_to_helpers();
+            let syncer_chunk = if syncer_failed { None } else { scheduler.next_for_syncer() };
+            match syncer_chunk {
+                Some((index, chunk)) => match self.request_block_bodies(chunk).await {
+                    Ok(blocks) => scheduler.complete(index, blocks),
+                    Err(err) if scheduler.has_helpers() => {
+                        warn!("IBD: syncer {} failed providing block bodies ({}), continuing with other peers", self.router, err);
+                        syncer_failed = true;
+                        scheduler.reschedule(index);
+                    }
+                    Err(err) => return Err(err),
+                },
+                None => scheduler.wait_for_helpers().await?,
+            }
+            scheduler.collect_helpers();
 
-        for chunk in iter {
-            let (current_jobs, current_daa_score) = self.queue_block_processing_chunk(consensus, chunk).await?;
-            let prev_chunk_len = prev_jobs.len();
-            // Join the previous chunk so that we always concurrently process a chunk and receive another
-            try_join_all(prev_jobs).await?;
-            // Log the progress
-            progress_reporter.report(prev_chunk_len, prev_daa_score);
-            prev_daa_score = current_daa_score;
-            prev_jobs = current_jobs;
+            while let Some(blocks) = scheduler.next_ready() {
+                let current_daa_score = blocks.last().expect("chunk is never empty").header.daa_score;
+                let current_jobs = blocks.into_iter().map(|block| consensus.validate_and_insert_block(block)).collect();
+                let prev_chunk_len = prev_jobs.len();
+                // Join the previous chunk so that we always concurrently process a chunk and receive another
+                try_join_all(prev_jobs).await?;
+                // Log the progress
+                if prev_chunk_len > 0 {
+                    progress_reporter.report(prev_chunk_len, prev_daa_score);
+                }
+                prev_daa_score = current_daa_score;
+                prev_jobs = current_jobs;
+            }
         }
 
         let prev_chunk_len = prev_jobs.len();
@@ -436,22 +506,22 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
 
         self.ctx.on_new_block_template().await?;
 
+        if syncer_failed {
+            // All bodies were obtained from other peers, but the syncer itself is misbehaving or disconnected
+            return Err(ProtocolError::OtherOwned(format!("syncer {} failed providing block bodies", self.router)));
+        }
+
         Ok(())
     }
 
-    async fn queue_block_processing_chunk(
-        &mut self,
-        consensus: &dyn ConsensusApi,
-        chunk: &[Hash],
-    ) -> Result<(Vec<BlockValidationFuture>, u64), ProtocolError> {
-        let mut jobs = Vec::with_capacity(chunk.len());
-        let mut current_daa_score = 0;
+    async fn request_block_bodies(&mut self, chunk: &[Hash]) -> Result<Vec<Block>, ProtocolError> {
         self.router
             .enqueue(make_message!(
                 Payload::RequestIbdBlocks,
                 RequestIbdBlocksMessage { hashes: chunk.iter().map(|h| h.into()).collect() }
             ))
             .await?;
+        let mut blocks = Vec::with_capacity(chunk.len());
         for &expected_hash in chunk {
             let msg = dequeue_with_timeout!(self.incoming_route, Payload::IbdBlock)?;
             let block: Block = msg.try_into()?;
@@ -461,10 +531,8 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
             if block.is_header_only() {
                 return Err(ProtocolError::OtherOwned(format!("sent header of {} where expected block with body", block.hash())));
             }
-            current_daa_score = block.header.daa_score;
-            jobs.push(consensus.validate_and_insert_block(block));
+            blocks.push(block);
         }
-
-        Ok((jobs, current_daa_score))
+        Ok(blocks)
     }
 }
@@ -34,6 +34,17 @@ mod request_pruning_point_utxo_set;
 mod txrelay;
 
 pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+    register_flows(ctx, router, false)
+}
+
+/// Builds the version 5 flow set. If `header_relay` is set, the relay flow may request header-only relay blocks
+/// (a version 6 addition) from the peer
+pub(crate) fn register_flows(ctx: FlowContext, router: Arc<Router>, header_relay: bool) -> Vec<Box<dyn Flow>> {
+    let mut relay_message_types = vec![KaspadMessagePayloadType::Block, KaspadMessagePayloadType::BlockLocator];
+    if header_relay {
+        relay_message_types.push(KaspadMessagePayloadType::RelayBlockHeaders);
+    }
+
     // IBD flow <-> invs flow channel requires no buffering hence the minimal size possible
     let (ibd_sender, relay_receiver) = tokio::sync::mpsc::channel(1);
     let flows: Vec<Box<dyn Flow>> = vec![
@@ -62,8 +73,9 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             ctx.clone(),
             router.clone(),
             router.subscribe(vec![KaspadMessagePayloadType::InvRelayBlock]),
-            router.subscribe(vec![KaspadMessagePayloadType::Block, KaspadMessagePayloadType::BlockLocator]),
+            router.subscribe(relay_message_types),
             ibd_sender,
+            header_relay,
         )),
         Box::new(HandleRelayBlockRequests::new(
             ctx.clone(),
@@ -3,6 +3,10 @@ use kaspa_core::debug;
 use kaspa_p2p_lib::{common::ProtocolError, dequeue, make_message, pb::kaspad_message::Payload, IncomingRoute, Router};
 use std::sync::Arc;
 
+/// The number of requested blocks fetched from the database at once, bounding the blocks held in
+/// memory while being sent
+const SEND_BATCH_SIZE: usize = 16;
+
 pub struct HandleIbdBlockRequests {
     ctx: FlowContext,
     router: Arc<Router>,
@@ -36,11 +40,18 @@ impl HandleIbdBlockRequests {
 
             debug!("got request for {} IBD blocks", hashes.len());
             let consensus = self.ctx.consensus();
-            let session = consensus.session().await;
-
-            for hash in hashes {
-                let block = session.get_block(hash)?;
-                self.router.enqueue(make_message!(Payload::IbdBlock, (&block).into())).await?;
+            for batch in hashes.chunks(SEND_BATCH_SIZE) {
+                let blocks = {
+                    // We avoid holding the consensus session while possibly being throttled below
+                    let session = consensus.session().await;
+                    batch.iter().map(|&hash| session.get_block(hash)).collect::<Result<Vec<_>, _>>()?
+                };
+
+                for block in blocks {
+                    let msg = make_message!(Payload::IbdBlock, (&block).into());
+                    self.ctx.throttle_ibd_upload(&msg).await;
+                    self.router.enqueue(msg).await?;
+                }
             }
         }
     }
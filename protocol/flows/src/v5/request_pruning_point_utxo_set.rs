@@ -52,34 +52,29 @@ impl RequestPruningPointUtxoSetFlow {
         let mut chunks_sent = 0;
 
         let consensus = self.ctx.consensus();
-        let mut session = consensus.session().await;
 
         loop {
-            // We avoid keeping the consensus session across the limitless dequeue call below
-            let pp_utxos = match session.get_pruning_point_utxos(expected_pp, from_outpoint, CHUNK_SIZE, chunks_sent != 0) {
+            // We avoid keeping the consensus session across the limitless dequeue and throttling calls below
+            let res = consensus.session().await.get_pruning_point_utxos(expected_pp, from_outpoint, CHUNK_SIZE, chunks_sent != 0);
+            let pp_utxos = match res {
                 Err(ConsensusError::UnexpectedPruningPoint) => return self.send_unexpected_pruning_point_message().await,
                 res => res,
             }?;
             debug!("Retrieved {} UTXOs for pruning point {}", pp_utxos.len(), expected_pp);
 
             // Send the chunk
-            self.router
-                .enqueue(make_message!(
-                    Payload::PruningPointUtxoSetChunk,
-                    PruningPointUtxoSetChunkMessage {
-                        outpoint_and_utxo_entry_pairs: pp_utxos
-                            .iter()
-                            .map(|(outpoint, entry)| { (outpoint, entry).into() })
-                            .collect_vec()
-                    }
-                ))
-                .await?;
+            let msg = make_message!(
+                Payload::PruningPointUtxoSetChunk,
+                PruningPointUtxoSetChunkMessage {
+                    outpoint_and_utxo_entry_pairs: pp_utxos.iter().map(|(outpoint, entry)| { (outpoint, entry).into() }).collect_vec()
+                }
+            );
+            self.ctx.throttle_ibd_upload(&msg).await;
+            self.router.enqueue(msg).await?;
 
             chunks_sent += 1;
             if chunks_sent % IBD_BATCH_SIZE == 0 {
-                drop(session); // Avoid holding the session through dequeue calls
                 dequeue!(self.incoming_route, Payload::RequestNextPruningPointUtxoSetChunk)?;
-                session = consensus.session().await;
             }
 
             // This indicates that there are no more entries to query
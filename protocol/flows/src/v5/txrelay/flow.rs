@@ -11,7 +11,7 @@ use kaspa_mining::{
     errors::MiningManagerError,
     mempool::{
         errors::RuleError,
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
     },
 };
 use kaspa_p2p_lib::{
@@ -94,8 +94,9 @@ impl RelayTransactionsFlow {
             let consensus = self.ctx.consensus();
             let session = consensus.session().await;
 
-            // Transaction relay is disabled if the node is out of sync and thus not mining
-            if !session.is_nearly_synced() {
+            // Transaction relay is disabled if the node is out of sync and thus not mining, or if it is a
+            // light node and thus cannot validate transactions
+            if self.ctx.config.is_light || !session.is_nearly_synced() {
                 continue;
             }
 
@@ -184,10 +185,16 @@ impl RelayTransactionsFlow {
                 )));
             }
             let Response::Transaction(transaction) = response else { continue; };
-            match self.ctx.mining_manager().validate_and_insert_transaction(consensus, transaction, Priority::Low, Orphan::Allowed) {
-                Ok(accepted_transactions) => {
-                    // trace!("Broadcast {} accepted transaction ids", accepted_transactions.len());
-                    self.ctx.broadcast_transactions(accepted_transactions.iter().map(|x| x.id())).await?;
+            match self.ctx.mining_manager().validate_and_insert_transaction(
+                consensus,
+                transaction,
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Allowed,
+            ) {
+                Ok(insertion) => {
+                    // trace!("Broadcast {} accepted transaction ids", insertion.accepted.len());
+                    self.ctx.broadcast_transactions(insertion.accepted.iter().map(|x| x.id())).await?;
                 }
                 Err(MiningManagerError::MempoolError(err)) => {
                     if let RuleError::RejectInvalid(_) = err {
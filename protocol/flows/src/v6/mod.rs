@@ -0,0 +1,18 @@
+use self::request_relay_block_headers::HandleRelayBlockHeaderRequests;
+use crate::{flow_context::FlowContext, flow_trait::Flow, v5};
+
+use kaspa_p2p_lib::{KaspadMessagePayloadType, Router};
+use std::sync::Arc;
+
+mod request_relay_block_headers;
+
+/// Registers the flows of protocol version 6, which extends version 5 with header-only block relay
+pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
+    let mut flows = v5::register_flows(ctx.clone(), router.clone(), true);
+    flows.push(Box::new(HandleRelayBlockHeaderRequests::new(
+        ctx,
+        router.clone(),
+        router.subscribe(vec![KaspadMessagePayloadType::RequestRelayBlockHeaders]),
+    )));
+    flows
+}
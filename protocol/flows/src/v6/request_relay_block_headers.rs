@@ -0,0 +1,52 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_core::debug;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    dequeue, make_message,
+    pb::{kaspad_message::Payload, RelayBlockHeadersMessage},
+    IncomingRoute, Router,
+};
+use std::sync::Arc;
+
+/// Serves relay requests of peers which only need the headers of the relayed blocks (e.g. light nodes)
+pub struct HandleRelayBlockHeaderRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleRelayBlockHeaderRequests {
+    fn name(&self) -> &'static str {
+        "HANDLE_RELAY_BLOCK_HEADER_REQUESTS"
+    }
+
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleRelayBlockHeaderRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let msg = dequeue!(self.incoming_route, Payload::RequestRelayBlockHeaders)?;
+            let hashes: Vec<_> = msg.try_into()?;
+
+            let consensus = self.ctx.consensus();
+            let session = consensus.session().await;
+
+            let block_headers =
+                hashes.iter().map(|&hash| session.get_header(hash).map(|header| (&*header).into())).collect::<Result<_, _>>()?;
+            self.router.enqueue(make_message!(Payload::RelayBlockHeaders, RelayBlockHeadersMessage { block_headers })).await?;
+            debug!("relayed {} block headers to peer {}", hashes.len(), self.router);
+        }
+    }
+}
@@ -15,6 +15,9 @@ pub enum ProtocolError {
     #[error("P2P protocol version mismatch - local: {0}, remote: {1}")]
     VersionMismatch(u32, u32),
 
+    #[error("P2P protocol version of peer is obsolete - minimal supported: {0}, remote: {1}")]
+    ObsoleteVersion(u32, u32),
+
     #[error("Network mismatch - local: {0}, remote: {1}")]
     WrongNetwork(String, String),
 
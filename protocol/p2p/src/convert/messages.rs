@@ -159,6 +159,21 @@ impl TryFrom<protowire::RequestRelayBlocksMessage> for Vec<Hash> {
     }
 }
 
+impl TryFrom<protowire::RequestRelayBlockHeadersMessage> for Vec<Hash> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestRelayBlockHeadersMessage) -> Result<Self, Self::Error> {
+        msg.hashes.into_iter().map(|v| v.try_into()).collect()
+    }
+}
+
+impl TryFrom<protowire::RelayBlockHeadersMessage> for Vec<Arc<Header>> {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RelayBlockHeadersMessage) -> Result<Self, Self::Error> {
+        msg.block_headers.into_iter().map(|v| v.try_into().map(Arc::new)).collect()
+    }
+}
+
 impl TryFrom<protowire::RequestIbdBlocksMessage> for Vec<Hash> {
     type Error = ConversionError;
 
@@ -0,0 +1,183 @@
+use crate::{pb::KaspadMessage, KaspadMessagePayloadType};
+use parking_lot::Mutex;
+use prost::Message;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Message and byte counts of a single message type in a single direction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficCounter {
+    pub messages: u64,
+    pub bytes: u64,
+}
+
+impl TrafficCounter {
+    fn add(&mut self, bytes: u64) {
+        self.messages += 1;
+        self.bytes += bytes;
+    }
+}
+
+impl std::ops::AddAssign for TrafficCounter {
+    fn add_assign(&mut self, rhs: Self) {
+        self.messages += rhs.messages;
+        self.bytes += rhs.bytes;
+    }
+}
+
+/// A point-in-time copy of the traffic exchanged with a peer, broken down by message type
+#[derive(Debug, Default, Clone)]
+pub struct TrafficSnapshot {
+    pub inbound: HashMap<KaspadMessagePayloadType, TrafficCounter>,
+    pub outbound: HashMap<KaspadMessagePayloadType, TrafficCounter>,
+}
+
+impl TrafficSnapshot {
+    pub fn total_inbound(&self) -> TrafficCounter {
+        Self::total(&self.inbound)
+    }
+
+    pub fn total_outbound(&self) -> TrafficCounter {
+        Self::total(&self.outbound)
+    }
+
+    /// Adds the counters of `other` to the counters of this snapshot
+    pub fn merge(&mut self, other: &TrafficSnapshot) {
+        for (&msg_type, &counter) in other.inbound.iter() {
+            *self.inbound.entry(msg_type).or_default() += counter;
+        }
+        for (&msg_type, &counter) in other.outbound.iter() {
+            *self.outbound.entry(msg_type).or_default() += counter;
+        }
+    }
+
+    fn total(counters: &HashMap<KaspadMessagePayloadType, TrafficCounter>) -> TrafficCounter {
+        counters.values().fold(TrafficCounter::default(), |mut acc, &c| {
+            acc += c;
+            acc
+        })
+    }
+}
+
+/// Live traffic counters of a single peer connection
+#[derive(Debug, Default)]
+pub struct TrafficStats {
+    inner: Mutex<TrafficSnapshot>,
+}
+
+impl TrafficStats {
+    pub fn record_inbound(&self, msg_type: KaspadMessagePayloadType, bytes: usize) {
+        self.inner.lock().inbound.entry(msg_type).or_default().add(bytes as u64);
+    }
+
+    pub fn record_outbound(&self, msg_type: KaspadMessagePayloadType, bytes: usize) {
+        self.inner.lock().outbound.entry(msg_type).or_default().add(bytes as u64);
+    }
+
+    pub fn snapshot(&self) -> TrafficSnapshot {
+        self.inner.lock().clone()
+    }
+}
+
+struct TokenBucket {
+    /// Available bytes. Can become negative, in which case it represents a debt which callers must wait for
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A token bucket limiting the aggregated upload rate of all callers sharing it.
+///
+/// The bucket holds at most one second worth of bytes, so short bursts up to the rate are allowed.
+pub struct UploadLimiter {
+    /// Maximum upload rate in bytes per second
+    rate: u64,
+    bucket: Mutex<TokenBucket>,
+}
+
+impl UploadLimiter {
+    pub fn new(rate: u64) -> Self {
+        assert!(rate > 0, "upload rate must be positive");
+        Self { rate, bucket: Mutex::new(TokenBucket { tokens: rate as f64, last_refill: Instant::now() }) }
+    }
+
+    /// The maximum upload rate in bytes per second
+    pub fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    /// Consumes `bytes` from the bucket and returns the time the caller should wait before sending them
+    fn reserve(&self, bytes: usize) -> Duration {
+        let mut bucket = self.bucket.lock();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate as f64).min(self.rate as f64);
+        bucket.last_refill = now;
+        bucket.tokens -= bytes as f64;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate as f64)
+        }
+    }
+
+    /// Waits until sending `bytes` complies with the upload rate
+    pub async fn acquire(&self, bytes: usize) {
+        let wait = self.reserve(bytes);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Waits until sending `msg` complies with the upload rate
+    pub async fn acquire_for_message(&self, msg: &KaspadMessage) {
+        self.acquire(msg.encoded_len()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_stats() {
+        let stats = TrafficStats::default();
+        stats.record_inbound(KaspadMessagePayloadType::Block, 100);
+        stats.record_inbound(KaspadMessagePayloadType::Block, 50);
+        stats.record_inbound(KaspadMessagePayloadType::Ping, 8);
+        stats.record_outbound(KaspadMessagePayloadType::Pong, 8);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.inbound[&KaspadMessagePayloadType::Block], TrafficCounter { messages: 2, bytes: 150 });
+        assert_eq!(snapshot.total_inbound(), TrafficCounter { messages: 3, bytes: 158 });
+        assert_eq!(snapshot.total_outbound(), TrafficCounter { messages: 1, bytes: 8 });
+    }
+
+    #[test]
+    fn test_traffic_snapshot_merge() {
+        let (first, second) = (TrafficStats::default(), TrafficStats::default());
+        first.record_inbound(KaspadMessagePayloadType::Block, 100);
+        first.record_outbound(KaspadMessagePayloadType::Ping, 8);
+        second.record_inbound(KaspadMessagePayloadType::Block, 50);
+        second.record_inbound(KaspadMessagePayloadType::Pong, 8);
+
+        let mut snapshot = first.snapshot();
+        snapshot.merge(&second.snapshot());
+        assert_eq!(snapshot.inbound[&KaspadMessagePayloadType::Block], TrafficCounter { messages: 2, bytes: 150 });
+        assert_eq!(snapshot.inbound[&KaspadMessagePayloadType::Pong], TrafficCounter { messages: 1, bytes: 8 });
+        assert_eq!(snapshot.total_outbound(), TrafficCounter { messages: 1, bytes: 8 });
+    }
+
+    #[test]
+    fn test_upload_limiter_reserve() {
+        let limiter = UploadLimiter::new(1000);
+        // The initial burst is covered by the bucket
+        assert!(limiter.reserve(1000).is_zero());
+        // Anything beyond is a debt which must be waited for
+        let wait = limiter.reserve(500);
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500), "unexpected wait {wait:?}");
+        let wait = limiter.reserve(500);
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_millis(1000), "unexpected wait {wait:?}");
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{common::ProtocolError, pb::KaspadMessage, ConnectionInitializer, Peer, Router};
+use crate::{common::ProtocolError, pb::KaspadMessage, ConnectionInitializer, Peer, Router, TrafficSnapshot};
 use kaspa_core::{debug, info, warn};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 
@@ -19,11 +19,14 @@ pub struct Hub {
     ///
     /// Note: the map key holds the node id and IP to prevent node impersonating.
     pub(crate) peers: Arc<RwLock<HashMap<PeerKey, Arc<Router>>>>,
+
+    /// Accumulated traffic of all peers which were removed from the hub
+    closed_traffic: Arc<Mutex<TrafficSnapshot>>,
 }
 
 impl Hub {
     pub fn new() -> Self {
-        Self { peers: Arc::new(RwLock::new(HashMap::new())) }
+        Self { peers: Arc::new(RwLock::new(HashMap::new())), closed_traffic: Default::default() }
     }
 
     /// Starts a loop for receiving central hub events from all peer routers. This mechanism is used for
@@ -58,6 +61,7 @@ impl Hub {
                     }
                     HubEvent::PeerClosing(peer_key) => {
                         if let Some(router) = self.peers.write().remove(&peer_key) {
+                            self.closed_traffic.lock().merge(&router.traffic());
                             debug!("P2P, Hub event loop, removing peer, router-id: {}", router.identity());
                         }
                     }
@@ -87,6 +91,14 @@ impl Hub {
         }
     }
 
+    /// Broadcast a message to all peers for which `filter` returns true
+    pub async fn broadcast_filtered(&self, msg: KaspadMessage, filter: impl Fn(&Router) -> bool) {
+        let peers = self.peers.read().values().filter(|router| filter(router)).cloned().collect::<Vec<_>>();
+        for router in peers {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+    }
+
     /// Terminate a specific peer
     pub async fn terminate(&self, peer_key: PeerKey) {
         let op = self.peers.read().get(&peer_key).cloned();
@@ -109,6 +121,15 @@ impl Hub {
         self.peers.read().values().map(|r| r.as_ref().into()).collect()
     }
 
+    /// Returns the overall traffic of the node since startup, including the traffic of peers which are no longer connected
+    pub fn traffic(&self) -> TrafficSnapshot {
+        let mut traffic = self.closed_traffic.lock().clone();
+        for router in self.peers.read().values() {
+            traffic.merge(&router.traffic());
+        }
+        traffic
+    }
+
     /// Returns whether there are currently active peers
     pub fn has_peers(&self) -> bool {
         !self.peers.read().is_empty()
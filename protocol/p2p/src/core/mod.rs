@@ -1,4 +1,5 @@
 pub mod adaptor;
+pub mod bandwidth;
 pub mod connection_handler;
 pub mod hub;
 pub mod payload_type;
@@ -46,6 +46,8 @@ pub enum KaspadMessagePayloadType {
     IbdChainBlockLocator,
     RequestAnticone,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    RequestRelayBlockHeaders,
+    RelayBlockHeaders,
 }
 
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
@@ -100,6 +102,8 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
             KaspadMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            KaspadMessagePayload::RequestRelayBlockHeaders(_) => KaspadMessagePayloadType::RequestRelayBlockHeaders,
+            KaspadMessagePayload::RelayBlockHeaders(_) => KaspadMessagePayloadType::RelayBlockHeaders,
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::core::bandwidth::{TrafficSnapshot, TrafficStats};
 use crate::core::hub::HubEvent;
 use crate::pb::KaspadMessage;
 use crate::Peer;
@@ -5,6 +6,7 @@ use crate::{common::ProtocolError, KaspadMessagePayloadType};
 use kaspa_core::{debug, error, info, trace};
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
+use prost::Message;
 use seqlock::SeqLock;
 use std::fmt::{Debug, Display};
 use std::net::SocketAddr;
@@ -87,6 +89,9 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Inbound and outbound message and byte counters, broken down by message type
+    traffic: TrafficStats,
 }
 
 impl Display for Router {
@@ -110,6 +115,7 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.traffic.snapshot(),
         )
     }
 }
@@ -140,6 +146,7 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            traffic: Default::default(),
         });
 
         let router_clone = router.clone();
@@ -233,6 +240,11 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Returns a snapshot of the inbound and outbound traffic counters of this peer
+    pub fn traffic(&self) -> TrafficSnapshot {
+        self.traffic.snapshot()
+    }
+
     fn incoming_flow_channel_size() -> usize {
         // TODO: reevaluate when the node is fully functional
         // Note: in go-kaspad this is set to 200
@@ -285,6 +297,7 @@ impl Router {
             return Err(ProtocolError::Other("received kaspad p2p message with empty payload"));
         }
         let msg_type: KaspadMessagePayloadType = msg.payload.as_ref().expect("payload was just verified").into();
+        self.traffic.record_inbound(msg_type, msg.encoded_len());
         let op = self.routing_map.read().get(&msg_type).cloned();
         if let Some(sender) = op {
             match sender.try_send(msg) {
@@ -307,9 +320,13 @@ impl Router {
 
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
-        assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
+        let msg_type: KaspadMessagePayloadType = msg.payload.as_ref().expect("Kaspad P2P message should always have a value").into();
+        let msg_len = msg.encoded_len();
         match self.outgoing_route.try_send(msg) {
-            Ok(_) => Ok(()),
+            Ok(_) => {
+                self.traffic.record_outbound(msg_type, msg_len);
+                Ok(())
+            }
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
             Err(TrySendError::Full(_)) => Err(ProtocolError::OutgoingRouteCapacityReached(self.to_string())),
         }
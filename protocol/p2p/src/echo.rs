@@ -64,6 +64,8 @@ impl EchoFlow {
             KaspadMessagePayloadType::IbdChainBlockLocator,
             KaspadMessagePayloadType::RequestAnticone,
             KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            KaspadMessagePayloadType::RequestRelayBlockHeaders,
+            KaspadMessagePayloadType::RelayBlockHeaders,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
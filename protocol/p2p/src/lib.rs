@@ -11,6 +11,7 @@ mod core;
 mod handshake;
 
 pub use crate::core::adaptor::{Adaptor, ConnectionInitializer};
+pub use crate::core::bandwidth::{TrafficCounter, TrafficSnapshot, UploadLimiter};
 pub use crate::core::connection_handler::ConnectionError;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::KaspadMessagePayloadType;
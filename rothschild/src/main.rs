@@ -1,9 +1,10 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, path::Path, time::Duration};
 
-use clap::{Arg, Command};
+use clap::{parser::ValueSource, Arg, Command};
 use itertools::Itertools;
-use kaspa_addresses::Address;
+use kaspa_addresses::{Address, Prefix};
 use kaspa_consensus_core::{
+    config::network::load_network_params,
     constants::TX_VERSION,
     sign::sign,
     subnets::SUBNETWORK_ID_NATIVE,
@@ -32,15 +33,25 @@ pub struct Args {
     pub private_key: Option<String>,
     pub tps: u64,
     pub rpc_server: String,
+    pub prefix: Prefix,
 }
 
 impl Args {
     fn parse() -> Self {
         let m = cli().get_matches();
+        let params = m
+            .get_one::<String>("networkfile")
+            .map(|path| load_network_params(Path::new(path)).unwrap_or_else(|err| panic!("Failed loading the network file: {err}")));
+        // A custom network provides the default RPC port unless the RPC server is explicitly specified
+        let rpc_server = match (&params, m.value_source("rpcserver")) {
+            (Some(params), Some(ValueSource::DefaultValue)) => format!("localhost:{}", params.default_rpc_port()),
+            _ => m.get_one::<String>("rpcserver").cloned().unwrap_or("localhost:16210".to_owned()),
+        };
         Args {
             private_key: m.get_one::<String>("private-key").cloned(),
             tps: m.get_one::<u64>("tps").cloned().unwrap(),
-            rpc_server: m.get_one::<String>("rpcserver").cloned().unwrap_or("localhost:16210".to_owned()),
+            rpc_server,
+            prefix: params.map_or(Prefix::Testnet, |params| params.prefix()),
         }
     }
 }
@@ -67,6 +78,12 @@ pub fn cli() -> Command {
                 .default_value("localhost:16210")
                 .help("RPC server"),
         )
+        .arg(
+            Arg::new("networkfile")
+                .long("networkfile")
+                .value_name("networkfile")
+                .help("TOML file defining a custom network (sets the address prefix and the default RPC port)"),
+        )
 }
 
 #[tokio::main]
@@ -87,8 +104,7 @@ async fn main() {
         secp256k1::KeyPair::from_seckey_slice(secp256k1::SECP256K1, &private_key_bytes).unwrap()
     } else {
         let (sk, pk) = &secp256k1::generate_keypair(&mut thread_rng());
-        let kaspa_addr =
-            Address::new(kaspa_addresses::Prefix::Testnet, kaspa_addresses::Version::PubKey, &pk.x_only_public_key().0.serialize());
+        let kaspa_addr = Address::new(args.prefix, kaspa_addresses::Version::PubKey, &pk.x_only_public_key().0.serialize());
         info!(
             "Generated private key {} and address {}. Send some funds to this address and rerun rothschild with `--private-key {}`",
             sk.display_secret(),
@@ -98,15 +114,12 @@ async fn main() {
         return;
     };
 
-    let kaspa_addr = Address::new(
-        kaspa_addresses::Prefix::Testnet,
-        kaspa_addresses::Version::PubKey,
-        &schnorr_key.x_only_public_key().0.serialize(),
-    );
+    let kaspa_addr = Address::new(args.prefix, kaspa_addresses::Version::PubKey, &schnorr_key.x_only_public_key().0.serialize());
 
     info!("Using Rothschild with private key {} and address {}", schnorr_key.display_secret(), String::from(&kaspa_addr));
 
     let mut utxos = refresh_utxos(&rpc_client, kaspa_addr.clone(), &mut pending).await;
+    let mut fee_per_mass = fetch_fee_per_mass(&rpc_client).await;
     let mut ticker = interval(Duration::from_secs_f64(1.0 / (args.tps as f64)));
     ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
 
@@ -116,8 +129,17 @@ async fn main() {
         ticker.tick().await;
         maximize_inputs = should_maximize_inputs(maximize_inputs, &utxos, &pending);
         let now = unix_now();
-        let has_funds =
-            maybe_send_tx(&rpc_client, kaspa_addr.clone(), &mut utxos, &mut pending, schnorr_key, &mut stats, maximize_inputs).await;
+        let has_funds = maybe_send_tx(
+            &rpc_client,
+            kaspa_addr.clone(),
+            &mut utxos,
+            &mut pending,
+            schnorr_key,
+            &mut stats,
+            maximize_inputs,
+            fee_per_mass,
+        )
+        .await;
         if !has_funds {
             info!("Has not enough funds");
         }
@@ -125,6 +147,7 @@ async fn main() {
             info!("Refetching UTXO set");
             tokio::time::sleep(Duration::from_millis(100)).await; // We don't want this operation to be too frequent since its heavy on the node, so we wait some time before executing it.
             utxos = refresh_utxos(&rpc_client, kaspa_addr.clone(), &mut pending).await;
+            fee_per_mass = fetch_fee_per_mass(&rpc_client).await;
             last_refresh = unix_now();
             pause_if_mempool_is_full(&rpc_client).await;
         }
@@ -162,6 +185,18 @@ async fn pause_if_mempool_is_full(rpc_client: &GrpcClient) {
     }
 }
 
+/// Returns the fee per gram of mass estimated by the node for an inclusion within about a minute,
+/// never lower than [`FEE_PER_MASS`]
+async fn fetch_fee_per_mass(rpc_client: &GrpcClient) -> u64 {
+    match rpc_client.get_fee_estimate().await {
+        Ok(estimate) => FEE_PER_MASS.max(estimate.normal_bucket.fee_rate.ceil() as u64),
+        Err(e) => {
+            warn!("RPC error: {}", e);
+            FEE_PER_MASS
+        }
+    }
+}
+
 async fn refresh_utxos(
     rpc_client: &GrpcClient,
     kaspa_addr: Address,
@@ -208,6 +243,7 @@ fn is_utxo_spendable(entry: &UtxoEntry, virtual_daa_score: u64) -> bool {
     entry.block_daa_score + needed_confs < virtual_daa_score
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn maybe_send_tx(
     rpc_client: &GrpcClient,
     kaspa_addr: Address,
@@ -216,9 +252,10 @@ async fn maybe_send_tx(
     schnorr_key: KeyPair,
     stats: &mut Stats,
     maximize_inputs: bool,
+    fee_per_mass: u64,
 ) -> bool {
     let num_outs = if maximize_inputs { 1 } else { 2 };
-    let (selected_utxos, selected_amount) = select_utxos(utxos, DEFAULT_SEND_AMOUNT, num_outs, maximize_inputs, pending);
+    let (selected_utxos, selected_amount) = select_utxos(utxos, DEFAULT_SEND_AMOUNT, num_outs, maximize_inputs, pending, fee_per_mass);
     if selected_amount == 0 {
         return false;
     }
@@ -271,8 +308,8 @@ fn clean_old_pending_outpoints(pending: &mut HashMap<TransactionOutpoint, u64>)
     }
 }
 
-fn required_fee(num_utxos: usize, num_outs: u64) -> u64 {
-    FEE_PER_MASS * estimated_mass(num_utxos, num_outs)
+fn required_fee(num_utxos: usize, num_outs: u64, fee_per_mass: u64) -> u64 {
+    fee_per_mass * estimated_mass(num_utxos, num_outs)
 }
 
 fn estimated_mass(num_utxos: usize, num_outs: u64) -> u64 {
@@ -307,6 +344,7 @@ fn select_utxos(
     num_outs: u64,
     maximize_utxos: bool,
     pending: &HashMap<TransactionOutpoint, u64>,
+    fee_per_mass: u64,
 ) -> (Vec<(TransactionOutpoint, UtxoEntry)>, u64) {
     const MAX_UTXOS: usize = 84;
     let mut selected_amount: u64 = 0;
@@ -315,7 +353,7 @@ fn select_utxos(
         selected_amount += entry.amount;
         selected.push((outpoint, entry));
 
-        let fee = required_fee(selected.len(), num_outs);
+        let fee = required_fee(selected.len(), num_outs, fee_per_mass);
 
         if selected_amount >= min_amount + fee && (!maximize_utxos || selected.len() == MAX_UTXOS) {
             return (selected, selected_amount - fee);
@@ -44,6 +44,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionAdded notification: {} addresses", "_0.addresses.len()")]
+    MempoolTransactionAdded(MempoolTransactionAddedNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: {} addresses, reason {:?}", "_0.addresses.len()", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -60,6 +66,8 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::MempoolTransactionAdded(v) => to_value(&v),
+            Notification::MempoolTransactionRemoved(v) => to_value(&v),
         }
     }
 }
@@ -92,10 +100,18 @@ impl NotificationTrait for Notification {
 
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription) -> Option<Self> {
         match subscription.active() {
-            true => {
-                let Self::UtxosChanged(notification) = self else { return None };
-                notification.apply_utxos_changed_subscription(subscription).map(Self::UtxosChanged)
-            }
+            true => match self {
+                Self::UtxosChanged(notification) => {
+                    notification.apply_utxos_changed_subscription(subscription).map(Self::UtxosChanged)
+                }
+                Self::MempoolTransactionAdded(notification) => {
+                    notification.apply_utxos_changed_subscription(subscription).map(Self::MempoolTransactionAdded)
+                }
+                Self::MempoolTransactionRemoved(notification) => {
+                    notification.apply_utxos_changed_subscription(subscription).map(Self::MempoolTransactionRemoved)
+                }
+                _ => None,
+            },
             false => None,
         }
     }
@@ -48,6 +48,14 @@ pub enum RpcApiOps {
     EstimateNetworkHashesPerSecond,
     GetMempoolEntriesByAddresses,
     GetCoinSupply,
+    GetLogLevels,
+    SetLogLevels,
+    SubmitTransactionReplacement,
+    GetFeeEstimate,
+    GetMempoolPolicy,
+    TestMempoolAccept,
+    SubmitTransactionPackage,
+    GetLocalTransactionStatus,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -59,6 +67,8 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
     NotifySinkBlueScoreChanged,
+    NotifyMempoolTransactionAdded,
+    NotifyMempoolTransactionRemoved,
 
     // ~
     Subscribe,
@@ -79,6 +89,8 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+    MempoolTransactionAddedNotification,
+    MempoolTransactionRemovedNotification,
 }
 //});
 
@@ -102,6 +114,8 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::MempoolTransactionAdded => RpcApiOps::MempoolTransactionAddedNotification,
+            EventType::MempoolTransactionRemoved => RpcApiOps::MempoolTransactionRemovedNotification,
         }
     }
 }
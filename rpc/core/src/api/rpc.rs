@@ -109,6 +109,39 @@ pub trait RpcApi: Sync + Send {
     }
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse>;
 
+    /// Submits a transaction replacing by fee the mempool transaction it double spends.
+    async fn submit_transaction_replacement(&self, transaction: RpcTransaction) -> RpcResult<SubmitTransactionReplacementResponse> {
+        self.submit_transaction_replacement_call(SubmitTransactionReplacementRequest::new(transaction)).await
+    }
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse>;
+
+    /// Submits a package of chained transactions, inserted into the mempool all together or not at all.
+    async fn submit_transaction_package(&self, transactions: Vec<RpcTransaction>) -> RpcResult<Vec<RpcTransactionId>> {
+        Ok(self.submit_transaction_package_call(SubmitTransactionPackageRequest::new(transactions)).await?.transaction_ids)
+    }
+    async fn submit_transaction_package_call(
+        &self,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse>;
+
+    /// Validates a transaction against the mempool rules without submitting it.
+    async fn test_mempool_accept(&self, transaction: RpcTransaction, replace_by_fee: bool) -> RpcResult<TestMempoolAcceptResponse> {
+        self.test_mempool_accept_call(TestMempoolAcceptRequest::new(transaction, replace_by_fee)).await
+    }
+    async fn test_mempool_accept_call(&self, request: TestMempoolAcceptRequest) -> RpcResult<TestMempoolAcceptResponse>;
+
+    /// Requests the rebroadcast status of a transaction submitted to this node through RPC.
+    async fn get_local_transaction_status(&self, transaction_id: RpcTransactionId) -> RpcResult<RpcLocalTransactionStatus> {
+        Ok(self.get_local_transaction_status_call(GetLocalTransactionStatusRequest::new(transaction_id)).await?.status)
+    }
+    async fn get_local_transaction_status_call(
+        &self,
+        request: GetLocalTransactionStatusRequest,
+    ) -> RpcResult<GetLocalTransactionStatusResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -273,6 +306,31 @@ pub trait RpcApi: Sync + Send {
     }
     async fn get_coin_supply_call(&self, request: GetCoinSupplyRequest) -> RpcResult<GetCoinSupplyResponse>;
 
+    /// Returns the current log levels of the node.
+    async fn get_log_levels(&self) -> RpcResult<GetLogLevelsResponse> {
+        self.get_log_levels_call(GetLogLevelsRequest {}).await
+    }
+    async fn get_log_levels_call(&self, request: GetLogLevelsRequest) -> RpcResult<GetLogLevelsResponse>;
+
+    /// Changes the log levels of the node at runtime, using the `--loglevel` syntax.
+    async fn set_log_levels(&self, filters: String) -> RpcResult<SetLogLevelsResponse> {
+        self.set_log_levels_call(SetLogLevelsRequest::new(filters)).await
+    }
+    async fn set_log_levels_call(&self, request: SetLogLevelsRequest) -> RpcResult<SetLogLevelsResponse>;
+
+    /// Returns the fee rates expected to get a transaction included with priority, within about a minute
+    /// and within about an hour, along with the expected inclusion times.
+    async fn get_fee_estimate(&self) -> RpcResult<RpcFeeEstimate> {
+        Ok(self.get_fee_estimate_call(GetFeeEstimateRequest {}).await?.estimate)
+    }
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse>;
+
+    /// Returns the effective mempool policy of the node.
+    async fn get_mempool_policy(&self) -> RpcResult<RpcMempoolPolicy> {
+        Ok(self.get_mempool_policy_call(GetMempoolPolicyRequest {}).await?.policy)
+    }
+    async fn get_mempool_policy_call(&self, request: GetMempoolPolicyRequest) -> RpcResult<GetMempoolPolicyResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1,6 +1,7 @@
 use crate::{
     utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, SinkBlueScoreChangedNotification,
+    MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, NewBlockTemplateNotification, Notification,
+    PruningPointUtxoSetOverrideNotification, RpcMempoolEntry, RpcMempoolRemovalReason, SinkBlueScoreChangedNotification,
     UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
@@ -29,6 +30,8 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::MempoolTransactionAdded(msg) => Notification::MempoolTransactionAdded(msg.into()),
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => Notification::MempoolTransactionRemoved(msg.into()),
         }
     }
 }
@@ -93,6 +96,41 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<consensus_notify::MempoolRemovalReason> for RpcMempoolRemovalReason {
+    fn from(item: consensus_notify::MempoolRemovalReason) -> Self {
+        match item {
+            consensus_notify::MempoolRemovalReason::Accepted => RpcMempoolRemovalReason::Accepted,
+            consensus_notify::MempoolRemovalReason::Expired => RpcMempoolRemovalReason::Expired,
+            consensus_notify::MempoolRemovalReason::Evicted => RpcMempoolRemovalReason::Evicted,
+            consensus_notify::MempoolRemovalReason::DoubleSpent => RpcMempoolRemovalReason::DoubleSpent,
+            consensus_notify::MempoolRemovalReason::OrphanResolved => RpcMempoolRemovalReason::OrphanResolved,
+            consensus_notify::MempoolRemovalReason::Invalid => RpcMempoolRemovalReason::Invalid,
+        }
+    }
+}
+
+impl From<&consensus_notify::MempoolTransactionAddedNotification> for MempoolTransactionAddedNotification {
+    // This is not intended to be ever called because no address prefix is available.
+    // Use kaspa_rpc_service::converter::consensus::ConsensusConverter instead.
+    fn from(item: &consensus_notify::MempoolTransactionAddedNotification) -> Self {
+        let transaction = &item.transaction;
+        let entry =
+            RpcMempoolEntry::new(transaction.calculated_fee.unwrap_or_default(), transaction.tx.as_ref().into(), item.is_orphan);
+        Self { entry, addresses: vec![] }
+    }
+}
+
+impl From<&consensus_notify::MempoolTransactionRemovedNotification> for MempoolTransactionRemovedNotification {
+    // This is not intended to be ever called because no address prefix is available.
+    // Use kaspa_rpc_service::converter::consensus::ConsensusConverter instead.
+    fn from(item: &consensus_notify::MempoolTransactionRemovedNotification) -> Self {
+        let transaction = &item.transaction;
+        let entry =
+            RpcMempoolEntry::new(transaction.calculated_fee.unwrap_or_default(), transaction.tx.as_ref().into(), item.is_orphan);
+        Self { entry, reason: item.reason.into(), addresses: vec![] }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -43,6 +43,9 @@ pub enum RpcError {
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
+    #[error("Rejected transaction package: {0}")]
+    RejectedTransactionPackage(String),
+
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
@@ -67,12 +70,24 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsafe argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method unavailable on a light node. Run the node without the --light argument.")]
+    UnavailableInLightMode,
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
+    #[error("Cannot ban IP {0} because it is whitelisted.")]
+    IpIsWhitelisted(IpAddress),
+
     #[error("IP {0} is not registered as banned.")]
     IpIsNotBanned(IpAddress),
 
+    #[error("Invalid log level filter: {0}")]
+    InvalidLogLevelFilter(String),
+
+    #[error("The logger of this node cannot be reconfigured at runtime.")]
+    LoggerNotReconfigurable,
+
     #[error(transparent)]
     AddressError(#[from] kaspa_addresses::AddressError),
 
@@ -0,0 +1,32 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// A fee rate, in sompi per gram of transaction mass, along with the expected delay in seconds
+/// for a transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeRateBucket {
+    pub fee_rate: f64,
+    pub estimated_seconds: f64,
+}
+
+impl RpcFeeRateBucket {
+    pub fn new(fee_rate: f64, estimated_seconds: f64) -> Self {
+        Self { fee_rate, estimated_seconds }
+    }
+}
+
+/// Fee rate estimates for inclusion in the next block, within about a minute and within about an hour
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimate {
+    pub priority_bucket: RpcFeeRateBucket,
+    pub normal_bucket: RpcFeeRateBucket,
+    pub low_bucket: RpcFeeRateBucket,
+}
+
+impl RpcFeeEstimate {
+    pub fn new(priority_bucket: RpcFeeRateBucket, normal_bucket: RpcFeeRateBucket, low_bucket: RpcFeeRateBucket) -> Self {
+        Self { priority_bucket, normal_bucket, low_bucket }
+    }
+}
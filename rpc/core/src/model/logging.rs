@@ -0,0 +1,16 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+/// The log level of a subsystem logger, where the subsystem is a module path prefix such as `kaspa_p2p_flows`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSubsystemLogLevel {
+    pub subsystem: String,
+    pub level: String,
+}
+
+impl RpcSubsystemLogLevel {
+    pub fn new(subsystem: String, level: String) -> Self {
+        Self { subsystem, level }
+    }
+}
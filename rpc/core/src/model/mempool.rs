@@ -28,3 +28,66 @@ impl RpcMempoolEntryByAddress {
         Self { address, sending, receiving }
     }
 }
+
+/// The mempool policy a node applies to incoming transactions, as configured by its operator
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolPolicy {
+    pub maximum_transaction_count: u64,
+    pub transaction_expire_interval_daa_score: u64,
+    /// The expire interval of the transactions submitted locally through RPC
+    pub local_transaction_expire_interval_daa_score: u64,
+    pub transaction_expire_scan_interval_daa_score: u64,
+    pub transaction_expire_scan_interval_milliseconds: u64,
+    pub orphan_expire_interval_daa_score: u64,
+    pub orphan_expire_scan_interval_daa_score: u64,
+    pub maximum_orphan_transaction_mass: u64,
+    pub maximum_orphan_transaction_count: u64,
+    pub maximum_replacement_evictions: u64,
+    pub accept_non_standard: bool,
+    pub maximum_mass_per_block: u64,
+    /// In sompi per 1000 grams of transaction mass
+    pub minimum_relay_transaction_fee: u64,
+    pub minimum_standard_transaction_version: u16,
+    pub maximum_standard_transaction_version: u16,
+}
+
+/// The status of a transaction submitted locally through RPC, kept while it is in the mempool and for a day after it left it.
+///
+/// All times are Unix timestamps in milliseconds.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLocalTransactionStatus {
+    pub submitted_at: u64,
+    pub rebroadcast_count: u32,
+    /// The time the transaction is due for its next rebroadcast, while it is in the mempool
+    pub next_rebroadcast_at: u64,
+    /// Why the transaction left the mempool, `None` while it is in the mempool
+    pub removal_reason: Option<RpcMempoolRemovalReason>,
+    pub removed_at: Option<u64>,
+}
+
+impl RpcLocalTransactionStatus {
+    /// Returns true while the transaction is in the mempool, waiting for inclusion in a block
+    pub fn is_pending(&self) -> bool {
+        self.removal_reason.is_none()
+    }
+}
+
+/// The reason why a transaction left the mempool
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcMempoolRemovalReason {
+    /// The transaction was accepted in a block
+    Accepted,
+    /// The low priority transaction stayed in the mempool for too long
+    Expired,
+    /// The transaction was evicted to make room for a transaction paying a higher fee rate
+    Evicted,
+    /// The transaction, or one of its ancestors, was double spent by a block or a replacing transaction
+    DoubleSpent,
+    /// The orphan got all its inputs resolved and moved to the transaction pool
+    OrphanResolved,
+    /// The transaction failed revalidation
+    Invalid,
+}
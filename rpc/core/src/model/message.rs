@@ -294,6 +294,132 @@ impl SubmitTransactionResponse {
     }
 }
 
+/// SubmitTransactionReplacementRequest submits a transaction to the mempool, replacing by fee the
+/// mempool transaction it double spends, along with all the transactions redeeming its outputs.
+///
+/// The replacement must pay a strictly higher fee rate than the replaced transaction and a fee
+/// covering the fees of all the evicted transactions plus its own minimum relay fee.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionReplacementRequest {
+    pub transaction: RpcTransaction,
+}
+
+impl SubmitTransactionReplacementRequest {
+    pub fn new(transaction: RpcTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionReplacementResponse {
+    pub transaction_id: RpcTransactionId,
+    pub replaced_transaction_id: RpcTransactionId,
+}
+
+impl SubmitTransactionReplacementResponse {
+    pub fn new(transaction_id: RpcTransactionId, replaced_transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id, replaced_transaction_id }
+    }
+}
+
+/// SubmitTransactionPackageRequest submits a package of chained transactions to the mempool, ordered so
+/// that every transaction follows its parents in the package.
+///
+/// The transactions are inserted either all together or none of them. None of them can be an orphan
+/// nor replace a mempool transaction.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageRequest {
+    pub transactions: Vec<RpcTransaction>,
+}
+
+impl SubmitTransactionPackageRequest {
+    pub fn new(transactions: Vec<RpcTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionPackageResponse {
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl SubmitTransactionPackageResponse {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+/// TestMempoolAcceptRequest runs a transaction through the mempool validation, exactly as
+/// SubmitTransactionRequest would, without inserting or relaying it.
+///
+/// When `replace_by_fee` is set, the transaction is validated as a SubmitTransactionReplacementRequest.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMempoolAcceptRequest {
+    pub transaction: RpcTransaction,
+    pub replace_by_fee: bool,
+}
+
+impl TestMempoolAcceptRequest {
+    pub fn new(transaction: RpcTransaction, replace_by_fee: bool) -> Self {
+        Self { transaction, replace_by_fee }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMempoolAcceptResponse {
+    pub transaction_id: RpcTransactionId,
+    pub mass: u64,
+    pub fee: u64,
+    /// In sompi per gram of mass
+    pub fee_rate: f64,
+    /// The mempool transaction the tested transaction would replace, if any
+    pub replaced_transaction_id: Option<RpcTransactionId>,
+}
+
+impl TestMempoolAcceptResponse {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        mass: u64,
+        fee: u64,
+        fee_rate: f64,
+        replaced_transaction_id: Option<RpcTransactionId>,
+    ) -> Self {
+        Self { transaction_id, mass, fee, fee_rate, replaced_transaction_id }
+    }
+}
+
+/// GetLocalTransactionStatusRequest requests the rebroadcast status of a transaction submitted to this node
+/// through RPC.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocalTransactionStatusRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl GetLocalTransactionStatusRequest {
+    pub fn new(transaction_id: RpcTransactionId) -> Self {
+        Self { transaction_id }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLocalTransactionStatusResponse {
+    pub status: RpcLocalTransactionStatus,
+}
+
+impl GetLocalTransactionStatusResponse {
+    pub fn new(status: RpcLocalTransactionStatus) -> Self {
+        Self { status }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetSubnetworkRequest {
@@ -703,6 +829,90 @@ impl GetProcessMetricsResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLogLevelsRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLogLevelsResponse {
+    pub root_level: String,
+    pub subsystems: Vec<RpcSubsystemLogLevel>,
+}
+
+impl GetLogLevelsResponse {
+    pub fn new(root_level: String, subsystems: Vec<RpcSubsystemLogLevel>) -> Self {
+        Self { root_level, subsystems }
+    }
+}
+
+/// SetLogLevelsRequest changes the log levels of the node at runtime.
+///
+/// `filters` uses the `--loglevel` syntax, ie. a comma separated list of `level` (for the root logger)
+/// and `subsystem=level` items, for instance `info,kaspa_p2p_flows=trace`. Subsystems which are not
+/// mentioned keep their current level.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLogLevelsRequest {
+    pub filters: String,
+}
+
+impl SetLogLevelsRequest {
+    pub fn new(filters: String) -> Self {
+        Self { filters }
+    }
+}
+
+/// The log levels in effect after the change
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLogLevelsResponse {
+    pub root_level: String,
+    pub subsystems: Vec<RpcSubsystemLogLevel>,
+}
+
+impl SetLogLevelsResponse {
+    pub fn new(root_level: String, subsystems: Vec<RpcSubsystemLogLevel>) -> Self {
+        Self { root_level, subsystems }
+    }
+}
+
+/// GetFeeEstimateRequest requests the fee rates, in sompi per gram of mass, expected to get a transaction
+/// included in the next block, within about a minute and within about an hour.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateResponse {
+    pub estimate: RpcFeeEstimate,
+}
+
+impl GetFeeEstimateResponse {
+    pub fn new(estimate: RpcFeeEstimate) -> Self {
+        Self { estimate }
+    }
+}
+
+/// GetMempoolPolicyRequest requests the mempool policy the node applies to incoming transactions,
+/// after the overrides set by the node operator.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolPolicyRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolPolicyResponse {
+    pub policy: RpcMempoolPolicy,
+}
+
+impl GetMempoolPolicyResponse {
+    pub fn new(policy: RpcMempoolPolicy) -> Self {
+        Self { policy }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -1001,6 +1211,112 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionAddedNotification
+
+/// NotifyMempoolTransactionAddedRequest registers this connection for mempoolTransactionAdded notifications
+/// for the given addresses. Depending on the provided `command`, notifications will start or stop for the
+/// provided `addresses`.
+///
+/// If `addresses` is empty, the notifications will start or stop for all addresses.
+///
+/// See: MempoolTransactionAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionAddedRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub command: Command,
+}
+
+impl NotifyMempoolTransactionAddedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionAddedResponse {}
+
+/// MempoolTransactionAddedNotification is sent whenever a transaction enters the mempool, either in the
+/// transaction pool or in the orphan pool.
+///
+/// `addresses` lists the addresses the transaction sends from, as far as its inputs are known, and to.
+///
+/// See: NotifyMempoolTransactionAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionAddedNotification {
+    pub entry: RpcMempoolEntry,
+    pub addresses: Vec<RpcAddress>,
+}
+
+impl MempoolTransactionAddedNotification {
+    pub(crate) fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription) -> Option<Self> {
+        filter_mempool_addresses(&self.addresses, subscription).map(|addresses| Self { entry: self.entry.clone(), addresses })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionRemovedNotification
+
+/// NotifyMempoolTransactionRemovedRequest registers this connection for mempoolTransactionRemoved notifications
+/// for the given addresses. Depending on the provided `command`, notifications will start or stop for the
+/// provided `addresses`.
+///
+/// If `addresses` is empty, the notifications will start or stop for all addresses.
+///
+/// See: MempoolTransactionRemovedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub command: Command,
+}
+
+impl NotifyMempoolTransactionRemovedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedResponse {}
+
+/// MempoolTransactionRemovedNotification is sent whenever a transaction leaves the mempool, along with the
+/// reason of its removal.
+///
+/// `addresses` lists the addresses the transaction sends from, as far as its inputs are known, and to.
+///
+/// See: NotifyMempoolTransactionRemovedRequest
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionRemovedNotification {
+    pub entry: RpcMempoolEntry,
+    pub reason: RpcMempoolRemovalReason,
+    pub addresses: Vec<RpcAddress>,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub(crate) fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription) -> Option<Self> {
+        filter_mempool_addresses(&self.addresses, subscription).map(|addresses| Self {
+            entry: self.entry.clone(),
+            reason: self.reason,
+            addresses,
+        })
+    }
+}
+
+/// Returns the addresses of a mempool notification matching the subscription, if any
+fn filter_mempool_addresses(addresses: &[RpcAddress], subscription: &UtxosChangedSubscription) -> Option<Vec<RpcAddress>> {
+    if subscription.to_all() {
+        return Some(addresses.to_vec());
+    }
+    let addresses = addresses.iter().filter(|x| subscription.contains_address(x)).cloned().collect::<Vec<_>>();
+    (!addresses.is_empty()).then_some(addresses)
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
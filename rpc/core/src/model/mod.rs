@@ -1,9 +1,11 @@
 pub mod address;
 pub mod block;
 pub mod blue_work;
+pub mod fee_estimate;
 pub mod hash;
 pub mod header;
 pub mod hex_cnv;
+pub mod logging;
 pub mod mempool;
 pub mod message;
 pub mod networktype;
@@ -15,9 +17,11 @@ pub mod tx;
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
+pub use fee_estimate::*;
 pub use hash::*;
 pub use header::*;
 pub use hex_cnv::*;
+pub use logging::*;
 pub use mempool::*;
 pub use message::*;
 pub use networktype::*;
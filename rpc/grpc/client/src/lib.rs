@@ -198,6 +198,12 @@ impl RpcApi for GrpcClient {
     route!(get_connected_peer_info_call, GetConnectedPeerInfo);
     route!(add_peer_call, AddPeer);
     route!(submit_transaction_call, SubmitTransaction);
+    route!(submit_transaction_replacement_call, SubmitTransactionReplacement);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(get_mempool_policy_call, GetMempoolPolicy);
+    route!(test_mempool_accept_call, TestMempoolAccept);
+    route!(submit_transaction_package_call, SubmitTransactionPackage);
+    route!(get_local_transaction_status_call, GetLocalTransactionStatus);
     route!(get_subnetwork_call, GetSubnetwork);
     route!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
     route!(get_blocks_call, GetBlocks);
@@ -215,6 +221,8 @@ impl RpcApi for GrpcClient {
     route!(estimate_network_hashes_per_second_call, EstimateNetworkHashesPerSecond);
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
+    route!(get_log_levels_call, GetLogLevels);
+    route!(set_log_levels_call, SetLogLevels);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -0,0 +1,38 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcFeeRateBucket, protowire::RpcFeeRateBucket, {
+    Self { fee_rate: item.fee_rate, estimated_seconds: item.estimated_seconds }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimate, protowire::RpcFeeEstimate, {
+    Self {
+        priority_bucket: Some((&item.priority_bucket).into()),
+        normal_bucket: Some((&item.normal_bucket).into()),
+        low_bucket: Some((&item.low_bucket).into()),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcFeeRateBucket, kaspa_rpc_core::RpcFeeRateBucket, {
+    Self { fee_rate: item.fee_rate, estimated_seconds: item.estimated_seconds }
+});
+
+try_from!(item: &protowire::RpcFeeEstimate, kaspa_rpc_core::RpcFeeEstimate, {
+    let bucket = |bucket: &Option<protowire::RpcFeeRateBucket>, field: &str| {
+        bucket.as_ref().ok_or_else(|| RpcError::MissingRpcFieldError("RpcFeeEstimate".to_string(), field.to_string()))?.try_into()
+    };
+    Self {
+        priority_bucket: bucket(&item.priority_bucket, "priority_bucket")?,
+        normal_bucket: bucket(&item.normal_bucket, "normal_bucket")?,
+        low_bucket: bucket(&item.low_bucket, "low_bucket")?,
+    }
+});
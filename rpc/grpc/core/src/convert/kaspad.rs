@@ -37,6 +37,14 @@ impl From<&kaspad_request::Payload> for RpcApiOps {
             Payload::GetCoinSupplyRequest(_) => RpcApiOps::GetCoinSupply,
             Payload::PingRequest(_) => RpcApiOps::Ping,
             Payload::GetProcessMetricsRequest(_) => RpcApiOps::GetProcessMetrics,
+            Payload::GetLogLevelsRequest(_) => RpcApiOps::GetLogLevels,
+            Payload::SetLogLevelsRequest(_) => RpcApiOps::SetLogLevels,
+            Payload::SubmitTransactionReplacementRequest(_) => RpcApiOps::SubmitTransactionReplacement,
+            Payload::GetFeeEstimateRequest(_) => RpcApiOps::GetFeeEstimate,
+            Payload::GetMempoolPolicyRequest(_) => RpcApiOps::GetMempoolPolicy,
+            Payload::TestMempoolAcceptRequest(_) => RpcApiOps::TestMempoolAccept,
+            Payload::SubmitTransactionPackageRequest(_) => RpcApiOps::SubmitTransactionPackage,
+            Payload::GetLocalTransactionStatusRequest(_) => RpcApiOps::GetLocalTransactionStatus,
 
             // Subscription commands for starting/stopping notifications
             Payload::NotifyBlockAddedRequest(_) => RpcApiOps::NotifyBlockAdded,
@@ -47,6 +55,8 @@ impl From<&kaspad_request::Payload> for RpcApiOps {
             Payload::NotifyPruningPointUtxoSetOverrideRequest(_) => RpcApiOps::NotifyPruningPointUtxoSetOverride,
             Payload::NotifyVirtualDaaScoreChangedRequest(_) => RpcApiOps::NotifyVirtualDaaScoreChanged,
             Payload::NotifyVirtualChainChangedRequest(_) => RpcApiOps::NotifyVirtualChainChanged,
+            Payload::NotifyMempoolTransactionAddedRequest(_) => RpcApiOps::NotifyMempoolTransactionAdded,
+            Payload::NotifyMempoolTransactionRemovedRequest(_) => RpcApiOps::NotifyMempoolTransactionRemoved,
 
             Payload::StopNotifyingUtxosChangedRequest(_) => RpcApiOps::NotifyUtxosChanged,
             Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_) => RpcApiOps::NotifyPruningPointUtxoSetOverride,
@@ -90,6 +100,14 @@ impl From<&kaspad_response::Payload> for RpcApiOps {
             Payload::GetCoinSupplyResponse(_) => RpcApiOps::GetCoinSupply,
             Payload::PingResponse(_) => RpcApiOps::Ping,
             Payload::GetProcessMetricsResponse(_) => RpcApiOps::GetProcessMetrics,
+            Payload::GetLogLevelsResponse(_) => RpcApiOps::GetLogLevels,
+            Payload::SetLogLevelsResponse(_) => RpcApiOps::SetLogLevels,
+            Payload::SubmitTransactionReplacementResponse(_) => RpcApiOps::SubmitTransactionReplacement,
+            Payload::GetFeeEstimateResponse(_) => RpcApiOps::GetFeeEstimate,
+            Payload::GetMempoolPolicyResponse(_) => RpcApiOps::GetMempoolPolicy,
+            Payload::TestMempoolAcceptResponse(_) => RpcApiOps::TestMempoolAccept,
+            Payload::SubmitTransactionPackageResponse(_) => RpcApiOps::SubmitTransactionPackage,
+            Payload::GetLocalTransactionStatusResponse(_) => RpcApiOps::GetLocalTransactionStatus,
 
             // Subscription commands for starting/stopping notifications
             Payload::NotifyBlockAddedResponse(_) => RpcApiOps::NotifyBlockAdded,
@@ -100,6 +118,8 @@ impl From<&kaspad_response::Payload> for RpcApiOps {
             Payload::NotifyPruningPointUtxoSetOverrideResponse(_) => RpcApiOps::NotifyPruningPointUtxoSetOverride,
             Payload::NotifyVirtualDaaScoreChangedResponse(_) => RpcApiOps::NotifyVirtualDaaScoreChanged,
             Payload::NotifyVirtualChainChangedResponse(_) => RpcApiOps::NotifyVirtualChainChanged,
+            Payload::NotifyMempoolTransactionAddedResponse(_) => RpcApiOps::NotifyMempoolTransactionAdded,
+            Payload::NotifyMempoolTransactionRemovedResponse(_) => RpcApiOps::NotifyMempoolTransactionRemoved,
 
             Payload::StopNotifyingPruningPointUtxoSetOverrideResponse(_) => RpcApiOps::NotifyPruningPointUtxoSetOverride,
             Payload::StopNotifyingUtxosChangedResponse(_) => RpcApiOps::NotifyUtxosChanged,
@@ -114,6 +134,8 @@ impl From<&kaspad_response::Payload> for RpcApiOps {
             Payload::PruningPointUtxoSetOverrideNotification(_) => RpcApiOps::Notification,
             Payload::VirtualDaaScoreChangedNotification(_) => RpcApiOps::Notification,
             Payload::VirtualChainChangedNotification(_) => RpcApiOps::Notification,
+            Payload::MempoolTransactionAddedNotification(_) => RpcApiOps::Notification,
+            Payload::MempoolTransactionRemovedNotification(_) => RpcApiOps::Notification,
         }
     }
 }
@@ -172,6 +194,14 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetCoinSupply);
     impl_into_kaspad_request!(Ping);
     impl_into_kaspad_request!(GetProcessMetrics);
+    impl_into_kaspad_request!(GetLogLevels);
+    impl_into_kaspad_request!(SetLogLevels);
+    impl_into_kaspad_request!(SubmitTransactionReplacement);
+    impl_into_kaspad_request!(GetFeeEstimate);
+    impl_into_kaspad_request!(GetMempoolPolicy);
+    impl_into_kaspad_request!(TestMempoolAccept);
+    impl_into_kaspad_request!(SubmitTransactionPackage);
+    impl_into_kaspad_request!(GetLocalTransactionStatus);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -181,6 +211,8 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyMempoolTransactionAdded);
+    impl_into_kaspad_request!(NotifyMempoolTransactionRemoved);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -300,6 +332,14 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetCoinSupply);
     impl_into_kaspad_response!(Ping);
     impl_into_kaspad_response!(GetProcessMetrics);
+    impl_into_kaspad_response!(GetLogLevels);
+    impl_into_kaspad_response!(SetLogLevels);
+    impl_into_kaspad_response!(SubmitTransactionReplacement);
+    impl_into_kaspad_response!(GetFeeEstimate);
+    impl_into_kaspad_response!(GetMempoolPolicy);
+    impl_into_kaspad_response!(TestMempoolAccept);
+    impl_into_kaspad_response!(SubmitTransactionPackage);
+    impl_into_kaspad_response!(GetLocalTransactionStatus);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -309,6 +349,8 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyMempoolTransactionAdded);
+    impl_into_kaspad_notify_response!(NotifyMempoolTransactionRemoved);
 
     macro_rules! impl_into_kaspad_response {
         ($name:tt) => {
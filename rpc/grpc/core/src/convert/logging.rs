@@ -0,0 +1,19 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcSubsystemLogLevel, protowire::SubsystemLogLevel, {
+    Self { subsystem: item.subsystem.clone(), level: item.level.clone() }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::SubsystemLogLevel, kaspa_rpc_core::RpcSubsystemLogLevel, {
+    Self { subsystem: item.subsystem.clone(), level: item.level.clone() }
+});
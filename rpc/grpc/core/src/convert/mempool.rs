@@ -18,6 +18,37 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcMempoolPolicy, protowire::RpcMempoolPolicy, {
+    Self {
+        maximum_transaction_count: item.maximum_transaction_count,
+        transaction_expire_interval_daa_score: item.transaction_expire_interval_daa_score,
+        local_transaction_expire_interval_daa_score: item.local_transaction_expire_interval_daa_score,
+        transaction_expire_scan_interval_daa_score: item.transaction_expire_scan_interval_daa_score,
+        transaction_expire_scan_interval_milliseconds: item.transaction_expire_scan_interval_milliseconds,
+        orphan_expire_interval_daa_score: item.orphan_expire_interval_daa_score,
+        orphan_expire_scan_interval_daa_score: item.orphan_expire_scan_interval_daa_score,
+        maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+        maximum_replacement_evictions: item.maximum_replacement_evictions,
+        accept_non_standard: item.accept_non_standard,
+        maximum_mass_per_block: item.maximum_mass_per_block,
+        minimum_relay_transaction_fee: item.minimum_relay_transaction_fee,
+        minimum_standard_transaction_version: item.minimum_standard_transaction_version.into(),
+        maximum_standard_transaction_version: item.maximum_standard_transaction_version.into(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcLocalTransactionStatus, protowire::RpcLocalTransactionStatus, {
+    Self {
+        submitted_at: item.submitted_at,
+        rebroadcast_count: item.rebroadcast_count,
+        next_rebroadcast_at: item.next_rebroadcast_at,
+        is_pending: item.is_pending(),
+        removal_reason: item.removal_reason.map(|x| protowire::RpcMempoolRemovalReason::from(x).into()).unwrap_or_default(),
+        removed_at: item.removed_at.unwrap_or_default(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,3 +71,41 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+try_from!(item: &protowire::RpcMempoolPolicy, kaspa_rpc_core::RpcMempoolPolicy, {
+    Self {
+        maximum_transaction_count: item.maximum_transaction_count,
+        transaction_expire_interval_daa_score: item.transaction_expire_interval_daa_score,
+        local_transaction_expire_interval_daa_score: item.local_transaction_expire_interval_daa_score,
+        transaction_expire_scan_interval_daa_score: item.transaction_expire_scan_interval_daa_score,
+        transaction_expire_scan_interval_milliseconds: item.transaction_expire_scan_interval_milliseconds,
+        orphan_expire_interval_daa_score: item.orphan_expire_interval_daa_score,
+        orphan_expire_scan_interval_daa_score: item.orphan_expire_scan_interval_daa_score,
+        maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+        maximum_replacement_evictions: item.maximum_replacement_evictions,
+        accept_non_standard: item.accept_non_standard,
+        maximum_mass_per_block: item.maximum_mass_per_block,
+        minimum_relay_transaction_fee: item.minimum_relay_transaction_fee,
+        minimum_standard_transaction_version: item.minimum_standard_transaction_version.try_into()?,
+        maximum_standard_transaction_version: item.maximum_standard_transaction_version.try_into()?,
+    }
+});
+
+try_from!(item: &protowire::RpcLocalTransactionStatus, kaspa_rpc_core::RpcLocalTransactionStatus, {
+    let removal_reason = match item.is_pending {
+        true => None,
+        false => Some(
+            protowire::RpcMempoolRemovalReason::from_i32(item.removal_reason)
+                .ok_or_else(|| RpcError::General(format!("invalid mempool removal reason {}", item.removal_reason)))?
+                .into(),
+        ),
+    };
+    Self {
+        submitted_at: item.submitted_at,
+        rebroadcast_count: item.rebroadcast_count,
+        next_rebroadcast_at: item.next_rebroadcast_at,
+        removal_reason,
+        removed_at: (!item.is_pending).then_some(item.removed_at),
+    }
+});
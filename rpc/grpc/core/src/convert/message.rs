@@ -213,6 +213,45 @@ from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionResponse>, protowire::Su
     Self { transaction_id: item.transaction_id.to_string(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::SubmitTransactionReplacementRequest, protowire::SubmitTransactionReplacementRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionReplacementResponse>, protowire::SubmitTransactionReplacementResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        replaced_transaction_id: item.replaced_transaction_id.to_string(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::SubmitTransactionPackageRequest, protowire::SubmitTransactionPackageRequestMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionPackageResponse>, protowire::SubmitTransactionPackageResponseMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::TestMempoolAcceptRequest, protowire::TestMempoolAcceptRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()), replace_by_fee: item.replace_by_fee }
+});
+from!(item: RpcResult<&kaspa_rpc_core::TestMempoolAcceptResponse>, protowire::TestMempoolAcceptResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        mass: item.mass,
+        fee: item.fee,
+        fee_rate: item.fee_rate,
+        replaced_transaction_id: item.replaced_transaction_id.map(|x| x.to_string()).unwrap_or_default(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetLocalTransactionStatusRequest, protowire::GetLocalTransactionStatusRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetLocalTransactionStatusResponse>, protowire::GetLocalTransactionStatusResponseMessage, {
+    Self { status: Some((&item.status).into()), error: None }
+});
+
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
     Self { subnetwork_id: item.subnetwork_id.to_string() }
 });
@@ -361,6 +400,36 @@ from!(item: RpcResult<&kaspa_rpc_core::GetProcessMetricsResponse>, protowire::Ge
     }
 });
 
+from!(&kaspa_rpc_core::GetLogLevelsRequest, protowire::GetLogLevelsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetLogLevelsResponse>, protowire::GetLogLevelsResponseMessage, {
+    Self { root_level: item.root_level.clone(), subsystems: item.subsystems.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::SetLogLevelsRequest, protowire::SetLogLevelsRequestMessage, { Self { filters: item.filters.clone() } });
+from!(item: RpcResult<&kaspa_rpc_core::SetLogLevelsResponse>, protowire::SetLogLevelsResponseMessage, {
+    Self { root_level: item.root_level.clone(), subsystems: item.subsystems.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetFeeEstimateRequest, protowire::GetFeeEstimateRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFeeEstimateResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), error: None }
+});
+
+from!(&kaspa_rpc_core::GetMempoolPolicyRequest, protowire::GetMempoolPolicyRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolPolicyResponse>, protowire::GetMempoolPolicyResponseMessage, {
+    Self { policy: Some((&item.policy).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::NotifyMempoolTransactionAddedRequest, protowire::NotifyMempoolTransactionAddedRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyMempoolTransactionAddedResponse>, protowire::NotifyMempoolTransactionAddedResponseMessage);
+
+from!(item: &kaspa_rpc_core::NotifyMempoolTransactionRemovedRequest, protowire::NotifyMempoolTransactionRemovedRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyMempoolTransactionRemovedResponse>, protowire::NotifyMempoolTransactionRemovedResponseMessage);
+
 from!(item: &kaspa_rpc_core::NotifyUtxosChangedRequest, protowire::NotifyUtxosChangedRequestMessage, {
     Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
 });
@@ -538,6 +607,67 @@ try_from!(item: &protowire::SubmitTransactionResponseMessage, RpcResult<kaspa_rp
     Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
 });
 
+try_from!(item: &protowire::SubmitTransactionReplacementRequestMessage, kaspa_rpc_core::SubmitTransactionReplacementRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("SubmitTransactionReplacementRequestMessage".to_string(), "transaction".to_string())
+            })?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionReplacementResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        replaced_transaction_id: RpcHash::from_str(&item.replaced_transaction_id)?,
+    }
+});
+
+try_from!(item: &protowire::SubmitTransactionPackageRequestMessage, kaspa_rpc_core::SubmitTransactionPackageRequest, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::SubmitTransactionPackageResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionPackageResponse>, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::TestMempoolAcceptRequestMessage, kaspa_rpc_core::TestMempoolAcceptRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("TestMempoolAcceptRequestMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        replace_by_fee: item.replace_by_fee,
+    }
+});
+try_from!(item: &protowire::TestMempoolAcceptResponseMessage, RpcResult<kaspa_rpc_core::TestMempoolAcceptResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        mass: item.mass,
+        fee: item.fee,
+        fee_rate: item.fee_rate,
+        replaced_transaction_id: match item.replaced_transaction_id.is_empty() {
+            true => None,
+            false => Some(RpcHash::from_str(&item.replaced_transaction_id)?),
+        },
+    }
+});
+
+try_from!(item: &protowire::GetLocalTransactionStatusRequestMessage, kaspa_rpc_core::GetLocalTransactionStatusRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetLocalTransactionStatusResponseMessage, RpcResult<kaspa_rpc_core::GetLocalTransactionStatusResponse>, {
+    Self {
+        status: item
+            .status
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetLocalTransactionStatusResponseMessage".to_string(), "status".to_string()))?
+            .try_into()?,
+    }
+});
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, kaspa_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: kaspa_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -686,6 +816,63 @@ try_from!(item: &protowire::GetProcessMetricsResponseMessage, RpcResult<kaspa_rp
     }
 });
 
+try_from!(&protowire::GetLogLevelsRequestMessage, kaspa_rpc_core::GetLogLevelsRequest);
+try_from!(item: &protowire::GetLogLevelsResponseMessage, RpcResult<kaspa_rpc_core::GetLogLevelsResponse>, {
+    Self {
+        root_level: item.root_level.clone(),
+        subsystems: item.subsystems.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::SetLogLevelsRequestMessage, kaspa_rpc_core::SetLogLevelsRequest, { Self { filters: item.filters.clone() } });
+try_from!(item: &protowire::SetLogLevelsResponseMessage, RpcResult<kaspa_rpc_core::SetLogLevelsResponse>, {
+    Self {
+        root_level: item.root_level.clone(),
+        subsystems: item.subsystems.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(&protowire::GetFeeEstimateRequestMessage, kaspa_rpc_core::GetFeeEstimateRequest);
+try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateResponse>, {
+    Self {
+        estimate: item
+            .estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateResponseMessage".to_string(), "estimate".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(&protowire::GetMempoolPolicyRequestMessage, kaspa_rpc_core::GetMempoolPolicyRequest);
+try_from!(item: &protowire::GetMempoolPolicyResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolPolicyResponse>, {
+    Self {
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetMempoolPolicyResponseMessage".to_string(), "policy".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::NotifyMempoolTransactionAddedRequestMessage, kaspa_rpc_core::NotifyMempoolTransactionAddedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(&protowire::NotifyMempoolTransactionAddedResponseMessage, RpcResult<kaspa_rpc_core::NotifyMempoolTransactionAddedResponse>);
+
+try_from!(item: &protowire::NotifyMempoolTransactionRemovedRequestMessage, kaspa_rpc_core::NotifyMempoolTransactionRemovedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(
+    &protowire::NotifyMempoolTransactionRemovedResponseMessage,
+    RpcResult<kaspa_rpc_core::NotifyMempoolTransactionRemovedResponse>
+);
+
 try_from!(item: &protowire::NotifyUtxosChangedRequestMessage, kaspa_rpc_core::NotifyUtxosChangedRequest, {
     Self {
         addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
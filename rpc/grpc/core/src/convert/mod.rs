@@ -1,8 +1,10 @@
 pub mod address;
 pub mod block;
 pub mod error;
+pub mod fee_estimate;
 pub mod header;
 pub mod kaspad;
+pub mod logging;
 pub mod mempool;
 pub mod message;
 pub mod notification;
@@ -2,9 +2,10 @@ use crate::protowire::{
     kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
+    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, MempoolTransactionAddedNotificationMessage,
+    MempoolTransactionRemovedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
     NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
+    PruningPointUtxoSetOverrideNotificationMessage, RpcMempoolRemovalReason, SinkBlueScoreChangedNotificationMessage,
     StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
     StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
     VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
@@ -34,6 +35,8 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::MempoolTransactionAdded(ref notification) => Payload::MempoolTransactionAddedNotification(notification.into()),
+        Notification::MempoolTransactionRemoved(ref notification) => Payload::MempoolTransactionRemovedNotification(notification.into()),
     }
 });
 
@@ -74,6 +77,29 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &kaspa_rpc_core::MempoolTransactionAddedNotification, MempoolTransactionAddedNotificationMessage, {
+    Self { entry: Some((&item.entry).into()), addresses: item.addresses.iter().map(|x| x.into()).collect() }
+});
+
+from!(item: &kaspa_rpc_core::MempoolTransactionRemovedNotification, MempoolTransactionRemovedNotificationMessage, {
+    Self {
+        entry: Some((&item.entry).into()),
+        reason: RpcMempoolRemovalReason::from(item.reason).into(),
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: kaspa_rpc_core::RpcMempoolRemovalReason, RpcMempoolRemovalReason, {
+    match item {
+        kaspa_rpc_core::RpcMempoolRemovalReason::Accepted => RpcMempoolRemovalReason::Accepted,
+        kaspa_rpc_core::RpcMempoolRemovalReason::Expired => RpcMempoolRemovalReason::Expired,
+        kaspa_rpc_core::RpcMempoolRemovalReason::Evicted => RpcMempoolRemovalReason::Evicted,
+        kaspa_rpc_core::RpcMempoolRemovalReason::DoubleSpent => RpcMempoolRemovalReason::DoubleSpent,
+        kaspa_rpc_core::RpcMempoolRemovalReason::OrphanResolved => RpcMempoolRemovalReason::OrphanResolved,
+        kaspa_rpc_core::RpcMempoolRemovalReason::Invalid => RpcMempoolRemovalReason::Invalid,
+    }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +143,12 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::MempoolTransactionAddedNotification(ref notification) => {
+            Notification::MempoolTransactionAdded(notification.try_into()?)
+        }
+        Payload::MempoolTransactionRemovedNotification(ref notification) => {
+            Notification::MempoolTransactionRemoved(notification.try_into()?)
+        }
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +203,44 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &MempoolTransactionAddedNotificationMessage, kaspa_rpc_core::MempoolTransactionAddedNotification, {
+    Self {
+        entry: item
+            .entry
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("MempoolTransactionAddedNotificationMessage".to_string(), "entry".to_string()))?
+            .try_into()?,
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &MempoolTransactionRemovedNotificationMessage, kaspa_rpc_core::MempoolTransactionRemovedNotification, {
+    Self {
+        entry: item
+            .entry
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("MempoolTransactionRemovedNotificationMessage".to_string(), "entry".to_string())
+            })?
+            .try_into()?,
+        reason: RpcMempoolRemovalReason::from_i32(item.reason)
+            .ok_or_else(|| RpcError::General(format!("invalid mempool removal reason {}", item.reason)))?
+            .into(),
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+from!(item: RpcMempoolRemovalReason, kaspa_rpc_core::RpcMempoolRemovalReason, {
+    match item {
+        RpcMempoolRemovalReason::Accepted => kaspa_rpc_core::RpcMempoolRemovalReason::Accepted,
+        RpcMempoolRemovalReason::Expired => kaspa_rpc_core::RpcMempoolRemovalReason::Expired,
+        RpcMempoolRemovalReason::Evicted => kaspa_rpc_core::RpcMempoolRemovalReason::Evicted,
+        RpcMempoolRemovalReason::DoubleSpent => kaspa_rpc_core::RpcMempoolRemovalReason::DoubleSpent,
+        RpcMempoolRemovalReason::OrphanResolved => kaspa_rpc_core::RpcMempoolRemovalReason::OrphanResolved,
+        RpcMempoolRemovalReason::Invalid => kaspa_rpc_core::RpcMempoolRemovalReason::Invalid,
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
@@ -19,6 +19,21 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        traffic: item.traffic.iter().map(protowire::PeerMessageTrafficMessage::from).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerMessageTraffic, protowire::PeerMessageTrafficMessage, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
     }
 });
 
@@ -40,6 +55,21 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
+        traffic: item.traffic.iter().map(kaspa_rpc_core::RpcPeerMessageTraffic::from).collect(),
+    }
+});
+
+from!(item: &protowire::PeerMessageTrafficMessage, kaspa_rpc_core::RpcPeerMessageTraffic, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_received: item.messages_received,
+        bytes_received: item.bytes_received,
+        messages_sent: item.messages_sent,
+        bytes_sent: item.bytes_sent,
     }
 });
 
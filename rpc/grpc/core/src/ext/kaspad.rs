@@ -2,9 +2,9 @@ use kaspa_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
     kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyFinalityConflictRequestMessage, NotifyMempoolTransactionAddedRequestMessage, NotifyMempoolTransactionRemovedRequestMessage,
+    NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage,
+    NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
@@ -60,6 +60,18 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::MempoolTransactionAdded(ref scope) => {
+                kaspad_request::Payload::NotifyMempoolTransactionAddedRequest(NotifyMempoolTransactionAddedRequestMessage {
+                    addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                    command: command.into(),
+                })
+            }
+            Scope::MempoolTransactionRemoved(ref scope) => {
+                kaspad_request::Payload::NotifyMempoolTransactionRemovedRequest(NotifyMempoolTransactionRemovedRequestMessage {
+                    addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                    command: command.into(),
+                })
+            }
         }
     }
 }
@@ -87,6 +99,8 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::MempoolTransactionAddedNotification(_) => true,
+            Payload::MempoolTransactionRemovedNotification(_) => true,
             _ => false,
         }
     }
@@ -11,9 +11,9 @@ use kaspa_notify::{
     listener::ListenerId,
     notifier::Notifier,
     scope::{
-        BlockAddedScope, FinalityConflictResolvedScope, FinalityConflictScope, NewBlockTemplateScope,
-        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope,
-        VirtualDaaScoreChangedScope,
+        BlockAddedScope, FinalityConflictResolvedScope, FinalityConflictScope, MempoolTransactionAddedScope,
+        MempoolTransactionRemovedScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope,
+        UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
     subscriber::{Subscriber, SubscriptionManager},
 };
@@ -159,6 +159,38 @@ impl Rpc for GrpcService {
                                     Ok(request) => core_service.ping_call(request).await.into(),
                                     Err(err) => PingResponseMessage::from(err).into(),
                                 },
+                                Payload::GetLogLevelsRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.get_log_levels_call(request).await.into(),
+                                    Err(err) => GetLogLevelsResponseMessage::from(err).into(),
+                                },
+                                Payload::SetLogLevelsRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.set_log_levels_call(request).await.into(),
+                                    Err(err) => SetLogLevelsResponseMessage::from(err).into(),
+                                },
+                                Payload::SubmitTransactionReplacementRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.submit_transaction_replacement_call(request).await.into(),
+                                    Err(err) => SubmitTransactionReplacementResponseMessage::from(err).into(),
+                                },
+                                Payload::GetFeeEstimateRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.get_fee_estimate_call(request).await.into(),
+                                    Err(err) => GetFeeEstimateResponseMessage::from(err).into(),
+                                },
+                                Payload::GetMempoolPolicyRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.get_mempool_policy_call(request).await.into(),
+                                    Err(err) => GetMempoolPolicyResponseMessage::from(err).into(),
+                                },
+                                Payload::TestMempoolAcceptRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.test_mempool_accept_call(request).await.into(),
+                                    Err(err) => TestMempoolAcceptResponseMessage::from(err).into(),
+                                },
+                                Payload::SubmitTransactionPackageRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.submit_transaction_package_call(request).await.into(),
+                                    Err(err) => SubmitTransactionPackageResponseMessage::from(err).into(),
+                                },
+                                Payload::GetLocalTransactionStatusRequest(ref request) => match request.try_into() {
+                                    Ok(request) => core_service.get_local_transaction_status_call(request).await.into(),
+                                    Err(err) => GetLocalTransactionStatusResponseMessage::from(err).into(),
+                                },
                                 Payload::GetCoinSupplyRequest(ref request) => match request.try_into() {
                                     Ok(request) => core_service.get_coin_supply_call(request).await.into(),
                                     Err(err) => GetCoinSupplyResponseMessage::from(err).into(),
@@ -426,6 +458,44 @@ impl Rpc for GrpcService {
                                     }
                                 }
 
+                                Payload::NotifyMempoolTransactionAddedRequest(ref request) => {
+                                    match kaspa_rpc_core::NotifyMempoolTransactionAddedRequest::try_from(request) {
+                                        Ok(request) => {
+                                            let result = notifier
+                                                .clone()
+                                                .execute_subscribe_command(
+                                                    listener_id,
+                                                    Scope::MempoolTransactionAdded(MempoolTransactionAddedScope::new(
+                                                        request.addresses,
+                                                    )),
+                                                    request.command,
+                                                )
+                                                .await;
+                                            NotifyMempoolTransactionAddedResponseMessage::from(result).into()
+                                        }
+                                        Err(err) => NotifyMempoolTransactionAddedResponseMessage::from(err).into(),
+                                    }
+                                }
+
+                                Payload::NotifyMempoolTransactionRemovedRequest(ref request) => {
+                                    match kaspa_rpc_core::NotifyMempoolTransactionRemovedRequest::try_from(request) {
+                                        Ok(request) => {
+                                            let result = notifier
+                                                .clone()
+                                                .execute_subscribe_command(
+                                                    listener_id,
+                                                    Scope::MempoolTransactionRemoved(MempoolTransactionRemovedScope::new(
+                                                        request.addresses,
+                                                    )),
+                                                    request.command,
+                                                )
+                                                .await;
+                                            NotifyMempoolTransactionRemovedResponseMessage::from(result).into()
+                                        }
+                                        Err(err) => NotifyMempoolTransactionRemovedResponseMessage::from(err).into(),
+                                    }
+                                }
+
                                 Payload::StopNotifyingUtxosChangedRequest(ref request) => {
                                     let notify_request = NotifyUtxosChangedRequestMessage::from(request);
                                     let response: StopNotifyingUtxosChangedResponseMessage =
@@ -15,12 +15,17 @@ use kaspa_math::Uint256;
 use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
-    RpcTransactionVerboseData,
+    BlockAddedNotification, MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, Notification,
+    RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult,
+    RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData, RpcTransactionVerboseData,
 };
 use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
-use std::{collections::HashMap, fmt::Debug, ops::Deref, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    ops::Deref,
+    sync::Arc,
+};
 
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
@@ -91,6 +96,20 @@ impl ConsensusConverter {
         RpcMempoolEntry::new(transaction.calculated_fee.unwrap_or_default(), rpc_transaction, is_orphan)
     }
 
+    /// Returns the addresses a mempool transaction sends from, as far as its UTXO entries are known, and sends to
+    pub fn get_mempool_transaction_addresses(&self, transaction: &MutableTransaction) -> Vec<Address> {
+        let mut known = HashSet::new();
+        transaction
+            .entries
+            .iter()
+            .flatten()
+            .map(|entry| &entry.script_public_key)
+            .chain(transaction.tx.outputs.iter().map(|output| &output.script_public_key))
+            .filter_map(|script_public_key| extract_script_pub_key_address(script_public_key, self.config.prefix()).ok())
+            .filter(|address| known.insert(address.clone()))
+            .collect()
+    }
+
     pub fn get_mempool_entries_by_address(
         &self,
         consensus: &dyn ConsensusApi,
@@ -195,6 +214,26 @@ impl Converter for ConsensusConverter {
 
                 Notification::BlockAdded(BlockAddedNotification { block })
             }
+            consensus_notify::Notification::MempoolTransactionAdded(msg) => {
+                let consensus = self.consensus_manager.consensus();
+                let session = consensus.session().await;
+                let entry = self.get_mempool_entry(session.deref(), &msg.transaction);
+                let entry = RpcMempoolEntry { is_orphan: msg.is_orphan, ..entry };
+                let addresses = self.get_mempool_transaction_addresses(&msg.transaction);
+                Notification::MempoolTransactionAdded(MempoolTransactionAddedNotification { entry, addresses })
+            }
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => {
+                let consensus = self.consensus_manager.consensus();
+                let session = consensus.session().await;
+                let entry = self.get_mempool_entry(session.deref(), &msg.transaction);
+                let entry = RpcMempoolEntry { is_orphan: msg.is_orphan, ..entry };
+                let addresses = self.get_mempool_transaction_addresses(&msg.transaction);
+                Notification::MempoolTransactionRemoved(MempoolTransactionRemovedNotification {
+                    entry,
+                    reason: msg.reason.into(),
+                    addresses,
+                })
+            }
             _ => (&incoming).into(),
         }
     }
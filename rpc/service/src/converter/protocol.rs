@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use kaspa_p2p_flows::flow_context::FlowContext;
-use kaspa_p2p_lib::{Peer, PeerKey};
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_p2p_lib::{Peer, PeerKey, TrafficSnapshot};
+use kaspa_rpc_core::{RpcPeerInfo, RpcPeerMessageTraffic};
 
 pub struct ProtocolConverter {
     flow_context: Arc<FlowContext>,
@@ -15,6 +15,8 @@ impl ProtocolConverter {
 
     fn get_peer_info(&self, peer: &Peer, ibd_peer_key: &Option<PeerKey>) -> RpcPeerInfo {
         let properties = peer.properties();
+        let traffic = peer.traffic();
+        let (total_received, total_sent) = (traffic.total_inbound(), traffic.total_outbound());
         RpcPeerInfo {
             id: peer.identity(),
             address: peer.net_address().into(),
@@ -25,9 +27,34 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            messages_received: total_received.messages,
+            bytes_received: total_received.bytes,
+            messages_sent: total_sent.messages,
+            bytes_sent: total_sent.bytes,
+            traffic: Self::get_message_traffic(traffic),
         }
     }
 
+    fn get_message_traffic(traffic: &TrafficSnapshot) -> Vec<RpcPeerMessageTraffic> {
+        let message_types: HashSet<_> = traffic.inbound.keys().chain(traffic.outbound.keys()).copied().collect();
+        let mut message_traffic = message_types
+            .into_iter()
+            .map(|message_type| {
+                let received = traffic.inbound.get(&message_type).copied().unwrap_or_default();
+                let sent = traffic.outbound.get(&message_type).copied().unwrap_or_default();
+                RpcPeerMessageTraffic {
+                    message_type: format!("{message_type:?}"),
+                    messages_received: received.messages,
+                    bytes_received: received.bytes,
+                    messages_sent: sent.messages,
+                    bytes_sent: sent.bytes,
+                }
+            })
+            .collect::<Vec<_>>();
+        message_traffic.sort_by(|a, b| a.message_type.cmp(&b.message_type));
+        message_traffic
+    }
+
     pub fn get_peers_info(&self, peers: &[Peer]) -> Vec<RpcPeerInfo> {
         let ibd_peer_key = self.flow_context.ibd_peer_key();
         peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key)).collect()
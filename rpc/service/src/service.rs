@@ -4,6 +4,7 @@ use super::collector::{CollectorFromConsensus, CollectorFromIndex};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use async_trait::async_trait;
 use kaspa_consensus_core::{
+    api::ConsensusApi,
     block::Block,
     coinbase::MinerData,
     config::Config,
@@ -21,7 +22,15 @@ use kaspa_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
-use kaspa_mining::{manager::MiningManager, mempool::tx::Orphan};
+use kaspa_mining::{
+    fee_estimate::{FeeEstimate, FeeRateBucket},
+    manager::MiningManager,
+    mempool::{
+        config::Config as MempoolConfig,
+        tx::{Orphan, Priority, RbfPolicy},
+    },
+    model::local_txs::LocalTransactionStatus,
+};
 use kaspa_notify::{
     collector::DynCollector,
     events::{EventSwitches, EventType, EVENT_TYPE_ARRAY},
@@ -151,6 +160,15 @@ impl RpcCoreService {
         self.notifier.clone()
     }
 
+    /// Returns the sink, or the headers selected tip for light nodes which do not track virtual state
+    fn get_selected_tip(&self, consensus: &dyn ConsensusApi) -> RpcHash {
+        if self.config.is_light {
+            consensus.get_headers_selected_tip()
+        } else {
+            consensus.get_sink()
+        }
+    }
+
     fn get_utxo_set_by_script_public_key<'a>(&self, addresses: impl Iterator<Item = &'a RpcAddress>) -> UtxoSetByScriptPublicKey {
         self.utxoindex
             .as_ref()
@@ -164,6 +182,10 @@ impl RpcCoreService {
 #[async_trait]
 impl RpcApi for RpcCoreService {
     async fn submit_block_call(&self, request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+
         let consensus = self.consensus_manager.consensus();
         let session = consensus.session().await;
 
@@ -213,6 +235,10 @@ impl RpcApi for RpcCoreService {
     async fn get_block_template_call(&self, request: GetBlockTemplateRequest) -> RpcResult<GetBlockTemplateResponse> {
         trace!("incoming GetBlockTemplate request");
 
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+
         if self.config.net == NetworkType::Mainnet {
             return Err(RpcError::General("Mining on mainnet is not supported for the Rust Alpha version".to_owned()));
         }
@@ -375,6 +401,9 @@ impl RpcApi for RpcCoreService {
     }
 
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
         let transaction: Transaction = (&request.transaction).try_into()?;
         let transaction_id = transaction.id();
         let consensus = self.consensus_manager.consensus();
@@ -387,6 +416,79 @@ impl RpcApi for RpcCoreService {
         Ok(SubmitTransactionResponse::new(transaction_id))
     }
 
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        let replaced_transaction =
+            self.flow_context.add_transaction_replacement(session.deref(), transaction).await.map_err(|err| {
+                let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                debug!("{err}");
+                err
+            })?;
+        Ok(SubmitTransactionReplacementResponse::new(transaction_id, replaced_transaction.id()))
+    }
+
+    async fn submit_transaction_package_call(
+        &self,
+        request: SubmitTransactionPackageRequest,
+    ) -> RpcResult<SubmitTransactionPackageResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        let transactions = request.transactions.iter().map(Transaction::try_from).collect::<Result<Vec<_>, _>>()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        let transaction_ids = self.flow_context.add_transaction_package(session.deref(), transactions).await.map_err(|err| {
+            let err = RpcError::RejectedTransactionPackage(err.to_string());
+            debug!("{err}");
+            err
+        })?;
+        Ok(SubmitTransactionPackageResponse::new(transaction_ids))
+    }
+
+    async fn test_mempool_accept_call(&self, request: TestMempoolAcceptRequest) -> RpcResult<TestMempoolAcceptResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let rbf_policy = if request.replace_by_fee { RbfPolicy::Mandatory } else { RbfPolicy::Forbidden };
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        // Transactions submitted through RPC are inserted with high priority
+        let acceptance =
+            self.mining_manager.test_accept_transaction(session.deref(), transaction, Priority::High, rbf_policy).map_err(|err| {
+                let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                debug!("{err}");
+                err
+            })?;
+        Ok(TestMempoolAcceptResponse::new(
+            transaction_id,
+            acceptance.mass(),
+            acceptance.fee(),
+            acceptance.fee_rate(),
+            acceptance.replaced,
+        ))
+    }
+
+    async fn get_local_transaction_status_call(
+        &self,
+        request: GetLocalTransactionStatusRequest,
+    ) -> RpcResult<GetLocalTransactionStatusResponse> {
+        let Some(status) = self.mining_manager.get_local_transaction_status(&request.transaction_id) else {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        };
+        Ok(GetLocalTransactionStatusResponse::new(rpc_local_transaction_status(status)))
+    }
+
     async fn get_current_network_call(&self, _: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
         Ok(GetCurrentNetworkResponse::new(self.config.net))
     }
@@ -396,13 +498,15 @@ impl RpcApi for RpcCoreService {
     }
 
     async fn get_selected_tip_hash_call(&self, _: GetSelectedTipHashRequest) -> RpcResult<GetSelectedTipHashResponse> {
-        Ok(GetSelectedTipHashResponse::new(self.consensus_manager.consensus().session().await.get_sink()))
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+        Ok(GetSelectedTipHashResponse::new(self.get_selected_tip(session.deref())))
     }
 
     async fn get_sink_blue_score_call(&self, _: GetSinkBlueScoreRequest) -> RpcResult<GetSinkBlueScoreResponse> {
         let consensus = self.consensus_manager.consensus();
         let session = consensus.session().await;
-        Ok(GetSinkBlueScoreResponse::new(session.get_ghostdag_data(session.get_sink())?.blue_score))
+        Ok(GetSinkBlueScoreResponse::new(session.get_ghostdag_data(self.get_selected_tip(session.deref()))?.blue_score))
     }
 
     async fn get_virtual_chain_from_block_call(
@@ -411,6 +515,15 @@ impl RpcApi for RpcCoreService {
     ) -> RpcResult<GetVirtualChainFromBlockResponse> {
         let consensus = self.consensus_manager.consensus();
         let session = consensus.session().await;
+        if self.config.is_light {
+            // Light nodes do not track virtual state, so the chain is reported up to the headers selected tip
+            // and no acceptance data is available
+            if request.include_accepted_transaction_ids {
+                return Err(RpcError::UnavailableInLightMode);
+            }
+            let chain = session.get_headers_selected_chain_from_block(request.start_hash)?;
+            return Ok(GetVirtualChainFromBlockResponse::new(chain.removed, chain.added, vec![]));
+        }
         let virtual_chain = session.get_virtual_chain_from_block(request.start_hash)?;
         let accepted_transaction_ids = if request.include_accepted_transaction_ids {
             self.consensus_converter.get_virtual_chain_accepted_transaction_ids(session.deref(), &virtual_chain)?
@@ -476,14 +589,50 @@ impl RpcApi for RpcCoreService {
         Ok(PingResponse {})
     }
 
-    async fn get_headers_call(&self, _request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_headers_call(&self, request: GetHeadersRequest) -> RpcResult<GetHeadersResponse> {
+        if !request.is_ascending {
+            return Err(RpcError::General("Only ascending header requests are currently supported".to_owned()));
+        }
+
+        let consensus = self.consensus_manager.consensus();
+        let session = consensus.session().await;
+
+        // Make sure start_hash points to an existing and valid block
+        session.get_ghostdag_data(request.start_hash)?;
+
+        // Similar to `get_blocks`, we walk up to the headers selected tip in bounded steps
+        // and the start hash is returned as well. A zero limit means no further limit.
+        let max_blocks = self.config.mergeset_size_limit as usize + 1;
+        let (hashes, _) = session.get_hashes_between(request.start_hash, session.get_headers_selected_tip(), max_blocks)?;
+        let limit = if request.limit == 0 { usize::MAX } else { request.limit as usize };
+        let headers = once(request.start_hash)
+            .chain(hashes)
+            .take(limit)
+            .map(|hash| Ok((*session.get_header(hash)?).clone()))
+            .collect::<RpcResult<Vec<_>>>()?;
+        Ok(GetHeadersResponse::new(headers))
     }
 
     async fn get_block_dag_info_call(&self, _: GetBlockDagInfoRequest) -> RpcResult<GetBlockDagInfoResponse> {
         let consensus = self.consensus_manager.consensus();
         let session = consensus.session().await;
         let sync_info = session.get_sync_info();
+        if self.config.is_light {
+            // Light nodes do not track virtual state nor body tips, so we report the properties of the headers selected tip instead
+            let hst = session.get_headers_selected_tip();
+            let hst_header = session.get_header(hst)?;
+            return Ok(GetBlockDagInfoResponse::new(
+                self.config.net,
+                sync_info.block_count,
+                sync_info.header_count,
+                vec![hst],
+                self.consensus_converter.get_difficulty_ratio(hst_header.bits),
+                hst_header.timestamp,
+                vec![hst],
+                session.pruning_point().unwrap_or_default(),
+                hst_header.daa_score,
+            ));
+        }
         Ok(GetBlockDagInfoResponse::new(
             self.config.net,
             sync_info.block_count,
@@ -521,7 +670,7 @@ impl RpcApi for RpcCoreService {
             warn!("AddPeer RPC command called while node in safe RPC mode -- ignoring.");
             return Err(RpcError::UnavailableInSafeMode);
         }
-        let peer_address = request.peer_address.normalize(self.config.net.default_p2p_port());
+        let peer_address = request.peer_address.normalize(self.config.default_p2p_port());
         if let Some(connection_manager) = self.flow_context.connection_manager() {
             connection_manager.add_connection_request(peer_address.into(), request.is_permanent).await;
         } else {
@@ -542,6 +691,9 @@ impl RpcApi for RpcCoreService {
         }
         if let Some(connection_manager) = self.flow_context.connection_manager() {
             let ip = request.ip.into();
+            if connection_manager.is_whitelisted(ip) {
+                return Err(RpcError::IpIsWhitelisted(request.ip));
+            }
             if connection_manager.ip_has_permanent_connection(ip).await {
                 return Err(RpcError::IpHasPermanentConnection(request.ip));
             }
@@ -589,6 +741,35 @@ impl RpcApi for RpcCoreService {
         Ok(ShutdownResponse {})
     }
 
+    async fn get_log_levels_call(&self, _: GetLogLevelsRequest) -> RpcResult<GetLogLevelsResponse> {
+        let (root_level, subsystems) = rpc_log_levels(kaspa_core::log::log_levels().ok_or(RpcError::LoggerNotReconfigurable)?);
+        Ok(GetLogLevelsResponse::new(root_level, subsystems))
+    }
+
+    async fn set_log_levels_call(&self, request: SetLogLevelsRequest) -> RpcResult<SetLogLevelsResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetLogLevels RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let levels = kaspa_core::log::set_log_levels(&request.filters)
+            .map_err(|err| RpcError::InvalidLogLevelFilter(err.to_string()))?
+            .ok_or(RpcError::LoggerNotReconfigurable)?;
+        info!("Log levels changed by RPC command to `{}`", request.filters);
+        let (root_level, subsystems) = rpc_log_levels(levels);
+        Ok(SetLogLevelsResponse::new(root_level, subsystems))
+    }
+
+    async fn get_fee_estimate_call(&self, _: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        if self.config.is_light {
+            return Err(RpcError::UnavailableInLightMode);
+        }
+        Ok(GetFeeEstimateResponse::new(rpc_fee_estimate(self.mining_manager.get_fee_estimate())))
+    }
+
+    async fn get_mempool_policy_call(&self, _: GetMempoolPolicyRequest) -> RpcResult<GetMempoolPolicyResponse> {
+        Ok(GetMempoolPolicyResponse::new(rpc_mempool_policy(&self.mining_manager.mempool_config())))
+    }
+
     async fn resolve_finality_conflict_call(
         &self,
         _request: ResolveFinalityConflictRequest,
@@ -635,3 +816,44 @@ impl RpcApi for RpcCoreService {
         Ok(())
     }
 }
+
+fn rpc_mempool_policy(config: &MempoolConfig) -> RpcMempoolPolicy {
+    RpcMempoolPolicy {
+        maximum_transaction_count: config.maximum_transaction_count,
+        transaction_expire_interval_daa_score: config.transaction_expire_interval_daa_score,
+        local_transaction_expire_interval_daa_score: config.local_transaction_expire_interval_daa_score,
+        transaction_expire_scan_interval_daa_score: config.transaction_expire_scan_interval_daa_score,
+        transaction_expire_scan_interval_milliseconds: config.transaction_expire_scan_interval_milliseconds,
+        orphan_expire_interval_daa_score: config.orphan_expire_interval_daa_score,
+        orphan_expire_scan_interval_daa_score: config.orphan_expire_scan_interval_daa_score,
+        maximum_orphan_transaction_mass: config.maximum_orphan_transaction_mass,
+        maximum_orphan_transaction_count: config.maximum_orphan_transaction_count,
+        maximum_replacement_evictions: config.maximum_replacement_evictions,
+        accept_non_standard: config.accept_non_standard,
+        maximum_mass_per_block: config.maximum_mass_per_block,
+        minimum_relay_transaction_fee: config.minimum_relay_transaction_fee,
+        minimum_standard_transaction_version: config.minimum_standard_transaction_version,
+        maximum_standard_transaction_version: config.maximum_standard_transaction_version,
+    }
+}
+
+fn rpc_local_transaction_status(status: LocalTransactionStatus) -> RpcLocalTransactionStatus {
+    RpcLocalTransactionStatus {
+        submitted_at: status.submitted_at,
+        rebroadcast_count: status.rebroadcast_count,
+        next_rebroadcast_at: status.next_rebroadcast_at,
+        removal_reason: status.removal.map(|(reason, _)| reason.into()),
+        removed_at: status.removal.map(|(_, removed_at)| removed_at),
+    }
+}
+
+fn rpc_fee_estimate(estimate: FeeEstimate) -> RpcFeeEstimate {
+    let bucket = |bucket: FeeRateBucket| RpcFeeRateBucket::new(bucket.fee_rate, bucket.estimated_seconds);
+    RpcFeeEstimate::new(bucket(estimate.priority_bucket), bucket(estimate.normal_bucket), bucket(estimate.low_bucket))
+}
+
+fn rpc_log_levels(levels: kaspa_core::log::LogLevels) -> (String, Vec<RpcSubsystemLogLevel>) {
+    let level_name = |level: log::LevelFilter| level.as_str().to_lowercase();
+    let subsystems = levels.subsystems.into_iter().map(|(name, level)| RpcSubsystemLogLevel::new(name, level_name(level))).collect();
+    (level_name(levels.root), subsystems)
+}
@@ -51,6 +51,8 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::MempoolTransactionAddedNotification,
+            RpcApiOps::MempoolTransactionRemovedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -258,8 +260,14 @@ impl RpcApi for KaspaRpcClient {
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetCurrentNetwork,
+            GetFeeEstimate,
+            GetMempoolPolicy,
+            TestMempoolAccept,
+            SubmitTransactionPackage,
+            GetLocalTransactionStatus,
             GetHeaders,
             GetInfo,
+            GetLogLevels,
             GetMempoolEntries,
             GetMempoolEntriesByAddresses,
             GetMempoolEntry,
@@ -272,9 +280,11 @@ impl RpcApi for KaspaRpcClient {
             GetVirtualChainFromBlock,
             Ping,
             ResolveFinalityConflict,
+            SetLogLevels,
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            SubmitTransactionReplacement,
             Unban,
         ]
     );
@@ -191,6 +191,8 @@ build_wrpc_wasm_bindgen_subscriptions!([
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    //MempoolTransactionAdded, // can't used this here due to non-C-style enum variant
+    //MempoolTransactionRemoved, // can't used this here due to non-C-style enum variant
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -205,7 +207,13 @@ build_wrpc_wasm_bindgen_interface!(
         GetBlockDagInfo,
         GetCoinSupply,
         GetConnectedPeerInfo,
+        GetFeeEstimate,
+        GetMempoolPolicy,
+        TestMempoolAccept,
+        SubmitTransactionPackage,
+        GetLocalTransactionStatus,
         GetInfo,
+        GetLogLevels,
         GetPeerAddresses,
         GetProcessMetrics,
         GetSelectedTipHash,
@@ -232,8 +240,10 @@ build_wrpc_wasm_bindgen_interface!(
         GetUtxosByAddresses,
         GetVirtualChainFromBlock,
         ResolveFinalityConflict,
+        SetLogLevels,
         SubmitBlock,
         SubmitTransaction,
+        SubmitTransactionReplacement,
         Unban,
     ]
 );
@@ -45,9 +45,15 @@ impl Router {
                 GetCoinSupply,
                 GetConnectedPeerInfo,
                 GetCurrentNetwork,
+                GetFeeEstimate,
+                GetMempoolPolicy,
+                TestMempoolAccept,
+                SubmitTransactionPackage,
+                GetLocalTransactionStatus,
                 GetHeaders,
                 GetInfo,
                 GetInfo,
+                GetLogLevels,
                 GetMempoolEntries,
                 GetMempoolEntriesByAddresses,
                 GetMempoolEntry,
@@ -60,9 +66,11 @@ impl Router {
                 GetVirtualChainFromBlock,
                 Ping,
                 ResolveFinalityConflict,
+                SetLogLevels,
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
+                SubmitTransactionReplacement,
                 Unban,
             ]
         );
@@ -16,15 +16,15 @@ use kaspa_consensus::{
     processes::ghostdag::ordering::SortableBlock,
 };
 use kaspa_consensus_core::{
-    api::ConsensusApi, block::Block, blockstatus::BlockStatus, errors::block::BlockProcessResult, header::Header, BlockHashSet,
-    BlockLevel, HashMapCustomHasher,
+    api::ConsensusApi, block::Block, blockstatus::BlockStatus, config::network::load_network_params,
+    errors::block::BlockProcessResult, header::Header, BlockHashSet, BlockLevel, HashMapCustomHasher,
 };
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::{info, warn};
 use kaspa_database::utils::{create_temp_db_with_parallelism, load_existing_db};
 use kaspa_hashes::Hash;
 use simulator::network::KaspaNetworkSimulator;
-use std::{collections::VecDeque, mem::size_of, sync::Arc};
+use std::{collections::VecDeque, mem::size_of, path::Path, sync::Arc};
 
 pub mod simulator;
 
@@ -83,6 +83,11 @@ struct Args {
     /// the DAG in a separate consensus following the simulation phase
     #[arg(long, default_value_t = false)]
     test_pruning: bool,
+
+    /// TOML file defining a custom network to simulate instead of devnet (params derived from --bps and --delay
+    /// still apply on top of the network params)
+    #[arg(long)]
+    network_file: Option<String>,
 }
 
 /// Calculates the k parameter of the GHOSTDAG protocol such that anticones lager than k will be created
@@ -125,7 +130,10 @@ fn main() {
             args.miners
         );
     }
-    let mut params = DEVNET_PARAMS;
+    let mut params = match args.network_file.as_deref() {
+        Some(path) => load_network_params(Path::new(path)).unwrap_or_else(|err| panic!("Failed loading the network file: {err}")),
+        None => DEVNET_PARAMS,
+    };
     let mut perf_params = PERF_PARAMS;
     adjust_consensus_params(&args, &mut params);
     adjust_perf_params(&args, &params, &mut perf_params);
@@ -15,6 +15,8 @@ use kaspa_consensus::model::stores::headers::HeaderStoreReader;
 use kaspa_consensus::model::stores::reachability::DbReachabilityStore;
 use kaspa_consensus::model::stores::relations::DbRelationsStore;
 use kaspa_consensus::model::stores::selected_chain::SelectedChainStoreReader;
+use kaspa_consensus::model::stores::utxo_diffs::UtxoDiffsStoreReader;
+use kaspa_consensus::model::stores::utxo_multisets::UtxoMultisetsStoreReader;
 use kaspa_consensus::params::{Params, DEVNET_PARAMS, DIFFICULTY_MAX, DIFFICULTY_MAX_AS_F64, MAINNET_PARAMS};
 use kaspa_consensus::pipeline::monitor::ConsensusMonitor;
 use kaspa_consensus::pipeline::ProcessingCounters;
@@ -23,6 +25,7 @@ use kaspa_consensus_core::api::ConsensusApi;
 use kaspa_consensus_core::block::Block;
 use kaspa_consensus_core::blockhash::new_unique;
 use kaspa_consensus_core::blockstatus::BlockStatus;
+use kaspa_consensus_core::coinbase::MinerData;
 use kaspa_consensus_core::constants::BLOCK_VERSION;
 use kaspa_consensus_core::errors::block::{BlockProcessResult, RuleError};
 use kaspa_consensus_core::header::Header;
@@ -50,6 +53,7 @@ use kaspa_math::Uint256;
 use kaspa_muhash::MuHash;
 use kaspa_utxoindex::api::UtxoIndexApi;
 use kaspa_utxoindex::UtxoIndex;
+use kaspa_utxosnapshot::{export_snapshot, import_snapshot, SnapshotError};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashSet;
@@ -60,6 +64,7 @@ use std::{
     fs::File,
     future::Future,
     io::{BufRead, BufReader},
+    iter::once,
     str::{from_utf8, FromStr},
 };
 
@@ -738,6 +743,9 @@ impl KaspadGoParams {
             dns_seeders: &[],
             net: Mainnet,
             net_suffix: None,
+            custom_prefix: None,
+            custom_p2p_port: None,
+            custom_rpc_port: None,
             genesis: GENESIS,
             ghostdag_k: self.K,
             timestamp_deviation_tolerance: self.TimestampDeviationTolerance,
@@ -775,6 +783,11 @@ async fn goref_custom_pruning_depth_test() {
     json_test("testdata/dags_for_json_tests/goref_custom_pruning_depth", false).await
 }
 
+#[tokio::test]
+async fn goref_custom_pruning_depth_archival_test() {
+    json_test_with_archival("testdata/dags_for_json_tests/goref_custom_pruning_depth", false, true).await
+}
+
 #[tokio::test]
 async fn goref_notx_test() {
     json_test("testdata/dags_for_json_tests/goref-notx-5000-blocks", false).await
@@ -830,6 +843,10 @@ fn gzip_file_lines(path: &Path) -> impl Iterator<Item = String> {
 }
 
 async fn json_test(file_path: &str, concurrency: bool) {
+    json_test_with_archival(file_path, concurrency, false).await
+}
+
+async fn json_test_with_archival(file_path: &str, concurrency: bool, is_archival: bool) {
     kaspa_core::log::try_init_logger("info");
     let main_path = Path::new(file_path);
     let proof_exists = common::file_exists(&main_path.join("proof.json.gz"));
@@ -856,6 +873,7 @@ async fn json_test(file_path: &str, concurrency: bool) {
     if proof_exists {
         config.process_genesis = false;
     }
+    config.is_archival = is_archival;
     let config = Arc::new(config);
 
     let (notification_send, notification_recv) = unbounded();
@@ -990,6 +1008,43 @@ async fn json_test(file_path: &str, concurrency: bool) {
     assert_eq!(virtual_utxos.len(), utxoindex_utxos.len());
     assert!(virtual_utxos.is_subset(&utxoindex_utxos));
     assert!(utxoindex_utxos.is_subset(&virtual_utxos));
+
+    if is_archival && !proof_exists {
+        // Archival nodes keep the full history below the pruning point, including the bodies of non-chain blocks
+        let pruning_point = tc.pruning_point().unwrap();
+        assert_ne!(pruning_point, config.genesis.hash, "expected the pruning point to advance");
+        let chain = once(config.genesis.hash).chain(tc.get_virtual_chain_from_block(config.genesis.hash).unwrap().added).collect_vec();
+        let mut merged_blocks = 0;
+        for &hash in chain.iter() {
+            tc.get_block(hash).unwrap_or_else(|e| panic!("missing body of chain block {hash}: {e}"));
+            tc.get_block_acceptance_data(hash).unwrap_or_else(|e| panic!("missing acceptance data of chain block {hash}: {e}"));
+            for merged in tc.ghostdag_store().get_data(hash).unwrap().unordered_mergeset_without_selected_parent() {
+                tc.get_block(merged).unwrap_or_else(|e| panic!("missing body of merged block {merged}: {e}"));
+                merged_blocks += 1;
+            }
+        }
+        assert!(merged_blocks > 0, "expected the chain blocks to merge non-chain blocks");
+
+        // Whereas the UTXO state data of blocks in the past of the pruning point is pruned
+        let (utxo_diffs_store, utxo_multisets_store) = (tc.utxo_diffs_store(), tc.utxo_multisets_store());
+        let pruning_point_position = chain.iter().position(|&h| h == pruning_point).unwrap();
+        for (i, &hash) in chain[..=pruning_point_position].iter().enumerate() {
+            let mut pruned_blocks =
+                tc.ghostdag_store().get_data(hash).unwrap().unordered_mergeset_without_selected_parent().collect_vec();
+            if i < pruning_point_position {
+                pruned_blocks.push(hash);
+            }
+            for block in pruned_blocks {
+                assert!(utxo_diffs_store.get(block).is_err(), "UTXO diff of block {block} below the pruning point should be pruned");
+                assert!(
+                    utxo_multisets_store.get(block).is_err(),
+                    "UTXO multiset of block {block} below the pruning point should be pruned"
+                );
+            }
+        }
+        // The pruning point keeps its UTXO state data, which is the base of the UTXO state of its future
+        assert!(utxo_diffs_store.get(pruning_point).is_ok() && utxo_multisets_store.get(pruning_point).is_ok());
+    }
 }
 
 fn submit_header_chunk(
@@ -1450,7 +1505,8 @@ async fn staging_consensus_test() {
     let notification_root = Arc::new(ConsensusNotificationRoot::new(notification_send));
     let counters = Arc::new(ProcessingCounters::default());
 
-    let consensus_factory = Arc::new(ConsensusFactory::new(meta_db, &config, consensus_db_dir, 4, notification_root, counters));
+    let consensus_factory =
+        Arc::new(ConsensusFactory::new(meta_db, &config, consensus_db_dir.clone(), 4, notification_root, counters));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
 
     let core = Arc::new(Core::new());
@@ -1460,6 +1516,70 @@ async fn staging_consensus_test() {
     let staging = consensus_manager.new_staging_consensus();
     staging.commit();
 
+    // A cancelled staging consensus is deleted from disk
+    let staging = consensus_manager.new_staging_consensus();
+    assert!(consensus_db_dir.join("consensus-003").exists());
+    staging.cancel();
+    assert!(!consensus_db_dir.join("consensus-003").exists());
+
     core.shutdown();
     core.join(joins);
 }
+
+#[tokio::test]
+async fn utxo_snapshot_test() {
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            // Use small depths so that the pruning point advances and its anticone becomes final quickly
+            p.ghostdag_k = 4;
+            p.mergeset_size_limit = 16;
+            p.finality_depth = 16;
+            p.merge_depth = 16;
+            p.pruning_proof_m = 16;
+            p.difficulty_window_size = 64;
+            p.timestamp_deviation_tolerance = 16;
+            p.pruning_depth = p.anticone_finalization_depth();
+        })
+        .build();
+
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+    let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+    let mut timestamp = config.genesis.timestamp;
+    // Mine a chain long enough for the pruning point to have a full DAA window in its past
+    for _ in 0..config.pruning_depth + config.difficulty_window_size as u64 + 3 * config.finality_depth {
+        timestamp += config.target_time_per_block;
+        let mut template = consensus.build_block_template(miner_data.clone(), vec![]).unwrap();
+        template.block.header.timestamp = timestamp;
+        template.block.header.finalize();
+        let status = consensus.validate_and_insert_block(template.block.to_immutable()).await.unwrap();
+        assert_eq!(status, BlockStatus::StatusUTXOValid);
+    }
+    consensus.shutdown(wait_handles);
+
+    let pruning_point = consensus.pruning_point().unwrap();
+    assert_ne!(pruning_point, config.genesis.hash, "expected the pruning point to advance");
+
+    let mut snapshot = Vec::new();
+    let exported = export_snapshot(consensus.consensus_clone().as_ref(), &config.network_name(), &mut snapshot).unwrap();
+    assert_eq!(exported.pruning_point, pruning_point);
+
+    // Bootstrap a fresh consensus from the snapshot, similarly to a staging consensus created for IBD
+    let config2 = config.to_builder().skip_adding_genesis().build();
+    let consensus2 = TestConsensus::new(&config2);
+    let wait_handles = consensus2.init();
+    assert!(matches!(
+        import_snapshot(consensus2.consensus_clone().as_ref(), "kaspa-other", snapshot.as_slice(), true).await,
+        Err(SnapshotError::NetworkMismatch(..))
+    ));
+    let imported =
+        import_snapshot(consensus2.consensus_clone().as_ref(), &config2.network_name(), snapshot.as_slice(), true).await.unwrap();
+    assert_eq!(imported, exported);
+    assert_eq!(consensus2.pruning_point(), Some(pruning_point));
+    assert_eq!(
+        consensus2.get_pruning_point_utxos(pruning_point, None, usize::MAX, false).unwrap(),
+        consensus.get_pruning_point_utxos(pruning_point, None, usize::MAX, false).unwrap()
+    );
+    consensus2.shutdown(wait_handles);
+}
@@ -3,5 +3,8 @@ pub mod common;
 #[cfg(test)]
 pub mod integration_tests;
 
+#[cfg(test)]
+pub mod light_node_tests;
+
 #[cfg(test)]
 pub mod pipeline_tests;
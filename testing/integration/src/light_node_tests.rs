@@ -0,0 +1,248 @@
+//!
+//! Light node integration tests
+//!
+
+use kaspa_addressmanager::AddressManager;
+use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+use kaspa_consensus_core::{
+    api::ConsensusApi, block::Block, blockstatus::BlockStatus, coinbase::MinerData, config::Config, tx::ScriptPublicKey,
+};
+use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::core::Core;
+use kaspa_database::utils::{create_temp_db, DbLifetime};
+use kaspa_hashes::Hash;
+use kaspa_mining::manager::MiningManager;
+use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_p2p_lib::{
+    make_message,
+    pb::{kaspad_message::Payload, InvRelayBlockMessage},
+    Adaptor,
+};
+use kaspa_rpc_core::{api::rpc::RpcApi, RpcError};
+use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_utils::networking::NetAddress;
+use std::{iter::once, str::FromStr, sync::Arc, thread::JoinHandle, time::Duration};
+
+/// An in-process node running a test consensus along with the P2P flows
+struct TestNode {
+    consensus: Arc<TestConsensus>,
+    consensus_manager: Arc<ConsensusManager>,
+    flow_context: Arc<FlowContext>,
+    mining_manager: Arc<MiningManager>,
+    notify_service: NotifyService,
+    config: Arc<Config>,
+    wait_handles: Vec<JoinHandle<()>>,
+    _db_lifetime: DbLifetime,
+}
+
+impl TestNode {
+    fn new(config: Config) -> Self {
+        let config = Arc::new(config);
+        let (notification_sender, notification_receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(notification_sender.clone()));
+        let consensus = Arc::new(TestConsensus::with_notifier(&config, notification_sender));
+        let wait_handles = consensus.init();
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(consensus.consensus_clone()));
+        let (db_lifetime, db) = create_temp_db();
+        let mining_manager = Arc::new(MiningManager::new(config.target_time_per_block, false, config.max_block_mass, None));
+        let flow_context = Arc::new(FlowContext::new(
+            consensus_manager.clone(),
+            AddressManager::new(db),
+            config.clone(),
+            mining_manager.clone(),
+            notification_root.clone(),
+        ));
+        let notify_service = NotifyService::new(notification_root, notification_receiver);
+        Self {
+            consensus,
+            consensus_manager,
+            flow_context,
+            mining_manager,
+            notify_service,
+            config,
+            wait_handles,
+            _db_lifetime: db_lifetime,
+        }
+    }
+
+    fn listen(&self, address: &str) -> Arc<Adaptor> {
+        Adaptor::bidirectional(NetAddress::from_str(address).unwrap(), self.flow_context.hub().clone(), self.flow_context.clone())
+            .unwrap()
+    }
+
+    fn rpc_service(&self) -> RpcCoreService {
+        RpcCoreService::new(
+            self.consensus_manager.clone(),
+            self.notify_service.notifier(),
+            None,
+            self.mining_manager.clone(),
+            self.flow_context.clone(),
+            None,
+            self.config.clone(),
+            Arc::new(Core::new()),
+        )
+    }
+
+    /// Mines `count` chain blocks and announces each of them to the connected peers
+    async fn mine_blocks(&self, count: u64) -> Vec<Hash> {
+        let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+        let mut hashes = Vec::new();
+        for _ in 0..count {
+            let block: Block = self.consensus.build_block_template(miner_data.clone(), vec![]).unwrap().block.to_immutable();
+            let status = self.consensus.validate_and_insert_block(block.clone()).await.unwrap();
+            assert_eq!(status, BlockStatus::StatusUTXOValid);
+            // Announce the block to connected peers, as done by the relay flow for blocks accepted via relay
+            self.flow_context
+                .hub()
+                .broadcast(make_message!(Payload::InvRelayBlock, InvRelayBlockMessage { hash: Some(block.hash().into()) }))
+                .await;
+            hashes.push(block.hash());
+        }
+        hashes
+    }
+
+    /// Shuts down the consensus once all other holders of it were dropped. Expected to be called after the runtime
+    /// running the P2P flows was dropped
+    fn shutdown(self) {
+        let Self { consensus, consensus_manager, flow_context, mining_manager, notify_service, config, wait_handles, _db_lifetime } =
+            self;
+        drop((flow_context, consensus_manager, mining_manager, notify_service, config));
+        consensus.shutdown(wait_handles);
+    }
+}
+
+/// Waits until the headers selected tip of `light` reaches the sink of `full`
+async fn wait_for_header_sync(light: &TestNode, full: &TestNode) {
+    let sink = full.consensus.get_sink();
+    for _ in 0..300 {
+        if light.consensus.get_headers_selected_tip() == sink {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("light node failed to sync the headers up to {}", sink);
+}
+
+#[test]
+fn light_node_sync_test() {
+    kaspa_core::log::try_init_logger("info");
+    let config = ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            // Use small depths so that the pruning point advances quickly
+            p.ghostdag_k = 4;
+            p.mergeset_size_limit = 16;
+            p.finality_depth = 16;
+            p.merge_depth = 16;
+            p.pruning_proof_m = 16;
+            p.difficulty_window_size = 64;
+            p.timestamp_deviation_tolerance = 16;
+            p.pruning_depth = p.anticone_finalization_depth();
+        })
+        .build();
+    let full = TestNode::new(config.clone());
+    let light = TestNode::new(config.to_builder().set_light().enable_sanity_checks().build());
+
+    // Run the nodes on a dedicated runtime. Dropping it cancels flows which are still pending (e.g., waiting for the next ping),
+    // thus releasing their references to the consensus before shutdown
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    runtime.block_on(sync_light_node(&config, &full, &light));
+    drop(runtime);
+    light.shutdown();
+    full.shutdown();
+}
+
+async fn sync_light_node(config: &Config, full: &TestNode, light: &TestNode) {
+    let genesis = config.genesis.hash;
+
+    // Mine a chain shorter than the pruning depth, so the light node syncs headers directly (without a pruning proof)
+    let first_chain = full.mine_blocks(config.pruning_depth / 2).await;
+
+    let full_adaptor = full.listen("[::1]:50070");
+    let light_adaptor = light.listen("[::1]:50071");
+    light_adaptor.connect_peer_with_retries(String::from("[::1]:50070"), 16, Duration::from_secs(1)).await.expect("connection failed");
+
+    // Once connected, the full node relays its sink, triggering header IBD on the light node
+    wait_for_header_sync(light, full).await;
+    assert!(first_chain.iter().all(|&hash| light.consensus.get_block_status(hash) == Some(BlockStatus::StatusHeaderOnly)));
+    assert_eq!(light.consensus.get_sync_info().header_count, full.consensus.get_sync_info().header_count);
+
+    let rpc = light.rpc_service();
+    let sink = *first_chain.last().unwrap();
+    assert_eq!(full.consensus.get_sink(), sink);
+
+    // Headers are served ascending from the start hash, which is returned as well
+    let headers = rpc.get_headers(genesis, 0, true).await.unwrap();
+    let (hashes, _) = full.consensus.get_hashes_between(genesis, sink, config.mergeset_size_limit as usize + 1).unwrap();
+    assert_eq!(headers.iter().map(|header| header.hash).collect::<Vec<_>>(), once(genesis).chain(hashes).collect::<Vec<_>>());
+    assert_eq!(headers[1].hash, first_chain[0]);
+    let headers = rpc.get_headers(first_chain[first_chain.len() - 3], 2, true).await.unwrap();
+    assert_eq!(
+        headers.iter().map(|header| header.hash).collect::<Vec<_>>(),
+        first_chain[first_chain.len() - 3..first_chain.len() - 1]
+    );
+
+    // The DAG info reports the headers selected tip in place of the body tips and the virtual parents
+    let dag_info = rpc.get_block_dag_info().await.unwrap();
+    assert_eq!(dag_info.tip_hashes, vec![sink]);
+    assert_eq!(dag_info.virtual_parent_hashes, vec![sink]);
+    assert_eq!(dag_info.header_count, full.consensus.get_sync_info().header_count);
+    assert_eq!(dag_info.virtual_daa_score, full.consensus.get_header(sink).unwrap().daa_score);
+    assert_eq!(dag_info.pruning_point_hash, genesis);
+
+    // The chain is reported up to the headers selected tip, without acceptance data
+    let chain = rpc.get_virtual_chain_from_block(genesis, false).await.unwrap();
+    assert!(chain.removed_chain_block_hashes.is_empty());
+    assert_eq!(chain.added_chain_block_hashes, first_chain);
+    assert_eq!(chain.added_chain_block_hashes, full.consensus.get_virtual_chain_from_block(genesis).unwrap().added);
+    assert!(matches!(rpc.get_virtual_chain_from_block(genesis, true).await, Err(RpcError::UnavailableInLightMode)));
+
+    // Keep mining well beyond the pruning depth. New blocks reach the light node via header-only relay
+    // and the light node advances its pruning point by the headers selected tip
+    let second_chain = full.mine_blocks(2 * config.pruning_depth).await;
+    wait_for_header_sync(light, full).await;
+    let pruning_point = full.consensus.pruning_point().unwrap();
+    assert_ne!(pruning_point, genesis, "expected the pruning point to advance");
+    for _ in 0..300 {
+        if light.consensus.pruning_point() == Some(pruning_point) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(light.consensus.pruning_point(), Some(pruning_point));
+    assert!(second_chain
+        .iter()
+        .rev()
+        .take(10)
+        .all(|&hash| light.consensus.get_block_status(hash) == Some(BlockStatus::StatusHeaderOnly)));
+
+    // Wait for both nodes to complete pruning and verify the light node keeps exactly the headers kept by the full node,
+    // i.e., old headers and relations are pruned the same way
+    let header_kept = |node: &TestNode, hash: Hash| node.consensus.get_header(hash).is_ok();
+    let retains_same_headers =
+        || first_chain.iter().chain(second_chain.iter()).all(|&hash| header_kept(light, hash) == header_kept(full, hash));
+    for _ in 0..300 {
+        if retains_same_headers() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(retains_same_headers(), "expected the light node to prune old headers like a full node");
+    assert!(header_kept(light, pruning_point));
+
+    // The light node serves the pruning point proof and trusted data to syncing peers, based on its headers selected tip
+    let proof_hashes = |node: &TestNode| {
+        node.consensus
+            .get_pruning_point_proof()
+            .iter()
+            .map(|level| level.iter().map(|h| h.hash).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(proof_hashes(light), proof_hashes(full));
+    let trusted_data = light.consensus.get_pruning_point_anticone_and_trusted_data().unwrap();
+    assert_eq!(trusted_data.anticone, vec![pruning_point]);
+
+    light_adaptor.close().await;
+    full_adaptor.close().await;
+}
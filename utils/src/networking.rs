@@ -1,3 +1,4 @@
+use crate::option::OptionExtensions;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -5,6 +6,7 @@ use std::{
     net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     ops::Deref,
     str::FromStr,
+    sync::Arc,
 };
 use uuid::Uuid;
 
@@ -214,6 +216,144 @@ impl Display for ContextualNetAddress {
         }
     }
 }
+
+/// An IP network in CIDR notation, such as `10.0.0.0/8` or `2001:db8::/32`.
+///
+/// A plain IP address is parsed as a network with a full-length prefix, i.e., matching only itself.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct IpNetwork {
+    ip: IpAddress,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn new(ip: IpAddress, prefix_len: u8) -> Result<Self, IpNetworkParseError> {
+        let max_prefix_len = Self::max_prefix_len(&ip);
+        if prefix_len > max_prefix_len {
+            return Err(IpNetworkParseError::InvalidPrefixLength(prefix_len, max_prefix_len));
+        }
+        Ok(Self { ip, prefix_len })
+    }
+
+    pub fn ip(&self) -> IpAddress {
+        self.ip
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    fn max_prefix_len(ip: &IpAddr) -> u8 {
+        match ip {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    /// Returns whether `ip` belongs to this network. IPv4-mapped IPv6 addresses are matched against IPv4 networks.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.ip.0, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => Self::prefix_matches(&net.octets(), &ip.octets(), self.prefix_len),
+            (IpAddr::V4(net), IpAddr::V6(ip)) => {
+                ip.to_ipv4_mapped().has_value_and(|ip| Self::prefix_matches(&net.octets(), &ip.octets(), self.prefix_len))
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => Self::prefix_matches(&net.octets(), &ip.octets(), self.prefix_len),
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+
+    fn prefix_matches(net: &[u8], ip: &[u8], prefix_len: u8) -> bool {
+        let full_bytes = prefix_len as usize / 8;
+        let remaining_bits = prefix_len % 8;
+        if net[..full_bytes] != ip[..full_bytes] {
+            return false;
+        }
+        if remaining_bits == 0 {
+            return true;
+        }
+        let mask = u8::MAX << (8 - remaining_bits);
+        net[full_bytes] & mask == ip[full_bytes] & mask
+    }
+}
+
+impl From<IpAddress> for IpNetwork {
+    fn from(ip: IpAddress) -> Self {
+        Self { ip, prefix_len: Self::max_prefix_len(&ip) }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IpNetworkParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((ip, prefix_len)) => {
+                let prefix_len = prefix_len.parse().map_err(|_| IpNetworkParseError::InvalidPrefix(prefix_len.to_owned()))?;
+                Self::new(IpAddress::from_str(ip)?, prefix_len)
+            }
+            None => Ok(IpAddress::from_str(s)?.into()),
+        }
+    }
+}
+
+impl Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.ip, self.prefix_len)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpNetworkParseError {
+    InvalidAddress(AddrParseError),
+    InvalidPrefix(String),
+    InvalidPrefixLength(u8, u8),
+}
+
+impl From<AddrParseError> for IpNetworkParseError {
+    fn from(err: AddrParseError) -> Self {
+        Self::InvalidAddress(err)
+    }
+}
+
+impl Display for IpNetworkParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidAddress(err) => write!(f, "invalid IP address: {err}"),
+            Self::InvalidPrefix(prefix) => write!(f, "invalid network prefix: {prefix}"),
+            Self::InvalidPrefixLength(len, max) => write!(f, "network prefix length {len} exceeds the maximum of {max}"),
+        }
+    }
+}
+
+impl std::error::Error for IpNetworkParseError {}
+
+/// The IP networks of trusted peers. Cloning is cheap, all clones sharing the same networks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Whitelist {
+    networks: Arc<Vec<IpNetwork>>,
+}
+
+impl Whitelist {
+    pub fn new(networks: Vec<IpNetwork>) -> Self {
+        Self { networks: Arc::new(networks) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Returns whether `ip` belongs to some whitelisted network
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+}
+
+impl From<Vec<IpNetwork>> for Whitelist {
+    fn from(networks: Vec<IpNetwork>) -> Self {
+        Self::new(networks)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, Default)]
 #[repr(transparent)]
 pub struct PeerId(pub Uuid);
@@ -333,4 +473,41 @@ mod tests {
         let addr_v6 = NetAddress::from_str("[2a01:4f8:191:1143::2]:5678");
         assert!(addr_v6.is_ok());
     }
+
+    #[test]
+    fn test_ip_network() {
+        let net = IpNetwork::from_str("10.1.0.0/16").unwrap();
+        assert!(net.contains(IpAddr::from_str("10.1.2.3").unwrap()));
+        assert!(!net.contains(IpAddr::from_str("10.2.0.1").unwrap()));
+        // IPv4-mapped IPv6 addresses are matched as IPv4
+        assert!(net.contains(IpAddr::from_str("::ffff:10.1.200.1").unwrap()));
+
+        let net = IpNetwork::from_str("192.168.1.128/25").unwrap();
+        assert!(net.contains(IpAddr::from_str("192.168.1.200").unwrap()));
+        assert!(!net.contains(IpAddr::from_str("192.168.1.100").unwrap()));
+
+        let net = IpNetwork::from_str("2001:db8::/32").unwrap();
+        assert!(net.contains(IpAddr::from_str("2001:db8:1::7").unwrap()));
+        assert!(!net.contains(IpAddr::from_str("2001:db9::7").unwrap()));
+        assert!(!net.contains(IpAddr::from_str("10.1.2.3").unwrap()));
+
+        // A plain address matches only itself
+        let net = IpNetwork::from_str("1.2.3.4").unwrap();
+        assert_eq!(net.prefix_len(), 32);
+        assert!(net.contains(IpAddr::from_str("1.2.3.4").unwrap()));
+        assert!(!net.contains(IpAddr::from_str("1.2.3.5").unwrap()));
+
+        // A zero-length prefix matches any address of the same family
+        assert!(IpNetwork::from_str("0.0.0.0/0").unwrap().contains(IpAddr::from_str("8.8.8.8").unwrap()));
+
+        assert_eq!(IpNetwork::from_str("1.2.3.4/33"), Err(IpNetworkParseError::InvalidPrefixLength(33, 32)));
+        assert!(matches!(IpNetwork::from_str("1.2.3.4/x"), Err(IpNetworkParseError::InvalidPrefix(_))));
+        assert!(matches!(IpNetwork::from_str("1.2.3/8"), Err(IpNetworkParseError::InvalidAddress(_))));
+
+        let whitelist = Whitelist::new(vec![IpNetwork::from_str("10.1.0.0/16").unwrap(), IpNetwork::from_str("1.2.3.4").unwrap()]);
+        assert!(whitelist.contains(IpAddr::from_str("10.1.2.3").unwrap()));
+        assert!(whitelist.contains(IpAddr::from_str("1.2.3.4").unwrap()));
+        assert!(!whitelist.contains(IpAddr::from_str("1.2.3.5").unwrap()));
+        assert!(!Whitelist::default().contains(IpAddr::from_str("1.2.3.4").unwrap()));
+    }
 }